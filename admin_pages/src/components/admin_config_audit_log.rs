@@ -0,0 +1,102 @@
+use data_model::*;
+use tracing::info;
+use web_sys::{Event, HtmlSelectElement};
+use yew::prelude::*;
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(ConfigAuditLog)]
+pub(crate) fn config_audit_log() -> Html {
+    let audit_log: UseStateHandle<Option<Vec<ConfigAuditEvent>>> = use_state_eq(|| None);
+    let section_filter = use_state_eq(String::new);
+
+    {
+        let audit_log = audit_log.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Loading config audit log");
+                match get_config_audit_log().await {
+                    Ok(events) => audit_log.set(Some(events)),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to load audit log: {err:#?}"));
+                        audit_log.set(Some(Vec::new()));
+                    }
+                }
+            });
+            || {}
+        });
+    }
+
+    let on_section_filter_change = {
+        let section_filter = section_filter.clone();
+        Callback::from(move |evt: Event| {
+            let value = evt.target_unchecked_into::<HtmlSelectElement>().value();
+            section_filter.set(value);
+        })
+    };
+
+    let Some(events) = &*audit_log else {
+        return html! {
+            <div class="spinner-border" role="status">
+                <span class="visually-hidden">{"Loading..."}</span>
+            </div>
+        };
+    };
+
+    html! {
+        <div class="card">
+            <div class="card-body">
+                <h5 class="card-title">
+                    {"Configuration Changes"}
+                </h5>
+                <div class="form-floating mb-2">
+                    <select class="form-select" id="formAuditSectionFilter" onchange={on_section_filter_change}>
+                        <option value="" selected=true>{"All"}</option>
+                        {
+                            ["Products", "Deliveries", "Neighborhoods", "Distribution Points", "Users", "Closeout"]
+                                .iter()
+                                .map(|v| html! { <option value={*v}>{*v}</option> })
+                                .collect::<Html>()
+                        }
+                    </select>
+                    <label for="formAuditSectionFilter">{"Section"}</label>
+                </div>
+                <table class="table table-sm table-striped">
+                    <thead>
+                        <tr>
+                            <th>{"Changed On"}</th>
+                            <th>{"Admin"}</th>
+                            <th>{"Section"}</th>
+                            <th>{"Item"}</th>
+                            <th>{"Before"}</th>
+                            <th>{"After"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                    {
+                        events
+                            .iter()
+                            .filter(|event| section_filter.is_empty() || event.section == *section_filter)
+                            .flat_map(|event| {
+                                let changed_by = get_username_from_id(&event.changed_by)
+                                    .unwrap_or(event.changed_by.clone());
+                                let change_time = event.get_change_time_str();
+                                event.changes.iter().map(move |change| html! {
+                                    <tr>
+                                        <td>{change_time.clone()}</td>
+                                        <td>{changed_by.clone()}</td>
+                                        <td>{event.section.clone()}</td>
+                                        <td>{change.item.clone()}</td>
+                                        <td>{change.from.clone()}</td>
+                                        <td>{change.to.clone()}</td>
+                                    </tr>
+                                })
+                            })
+                            .collect::<Html>()
+                    }
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    }
+}
@@ -1,10 +1,13 @@
+use crate::components::admin_config_delivery_reschedule::*;
 use data_model::*;
 use js::bootstrap;
 use std::cell::RefCell;
 use std::rc::Rc;
 use tracing::info;
 use wasm_bindgen::JsCast;
-use web_sys::{Element, HtmlButtonElement, HtmlElement, HtmlInputElement, MouseEvent};
+use web_sys::{
+    Element, Event, HtmlButtonElement, HtmlElement, HtmlInputElement, HtmlSelectElement, MouseEvent,
+};
 use yew::prelude::*;
 
 #[derive(PartialEq, Clone, Debug, Default)]
@@ -12,6 +15,8 @@ struct SelectedDeliveryInfo {
     delivery_id_str: String,
     delivery_date_str: String,
     cutoff_date_str: String,
+    max_bags_str: String,
+    is_waitlist_enabled: bool,
 }
 
 thread_local! {
@@ -20,7 +25,7 @@ thread_local! {
 
 /////////////////////////////////////////////////
 /////////////////////////////////////////////////
-type DeliveryDlgAddOrUpdateCb = (u32, String, String);
+type DeliveryDlgAddOrUpdateCb = (u32, String, String, Option<u32>, bool);
 /////////////////////////////////////////////////
 //
 #[derive(Properties, PartialEq, Clone, Debug)]
@@ -30,7 +35,7 @@ struct DeliveryAddEditDlgProps {
 
 #[component(DeliveryAddEditDlg)]
 fn delivery_add_or_edit_dlg(props: &DeliveryAddEditDlgProps) -> Html {
-    //Tuple of Devlivery ID, Delivery Data, Cutoff Date
+    //Tuple of Devlivery ID, Delivery Data, Cutoff Date, Max Bags, Is Waitlist Enabled
     let delivery_info = use_state_eq(SelectedDeliveryInfo::default);
     // let delivery_date_str = use_state_eq(|| "".to_string());
     // let cutoff_date_str = use_state_eq(|| "".to_string());
@@ -58,10 +63,24 @@ fn delivery_add_or_edit_dlg(props: &DeliveryAddEditDlgProps) -> Html {
                 .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
                 .unwrap()
                 .value();
+            let max_bags = document
+                .get_element_by_id("formDeliveryMaxBags")
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                .unwrap()
+                .value()
+                .parse::<u32>()
+                .ok();
+            let is_waitlist_enabled = document
+                .get_element_by_id("formDeliveryWaitlist")
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                .unwrap()
+                .checked();
             onaddorupdate.emit((
                 delivery_info.delivery_id_str.parse::<u32>().unwrap(),
                 delivery_date,
                 order_cutoff_date,
+                max_bags,
+                is_waitlist_enabled,
             ));
         }
     };
@@ -104,6 +123,23 @@ fn delivery_add_or_edit_dlg(props: &DeliveryAddEditDlgProps) -> Html {
                                     </div>
                                 </div>
                             </div>
+                            <div class="row mt-2">
+                                <div class="col-md">
+                                    <div class="form-floating">
+                                        <input class="form-control" type="number" min="0" id="formDeliveryMaxBags"
+                                            placeholder="No Limit"
+                                            value={delivery_info.max_bags_str.clone()} />
+                                        <label for="formDeliveryMaxBags">{"Max Bags (blank for no limit)"}</label>
+                                    </div>
+                                </div>
+                                <div class="col-md d-flex align-items-center">
+                                    <div class="form-check">
+                                        <input class="form-check-input" type="checkbox" id="formDeliveryWaitlist"
+                                            checked={delivery_info.is_waitlist_enabled} />
+                                        <label class="form-check-label" for="formDeliveryWaitlist">{"Waitlist When Full"}</label>
+                                    </div>
+                                </div>
+                            </div>
                         </div>
                     </div>
                     <div class="modal-footer">
@@ -124,8 +160,14 @@ struct DeliveryLiProps {
     deliveryid: u32,
     deliverydate: String,
     newordercutoff: String,
+    maxbags: Option<u32>,
+    iswaitlistenabled: bool,
+    islocked: bool,
     onedit: Callback<MouseEvent>,
     ondelete: Callback<MouseEvent>,
+    onreschedule: Callback<MouseEvent>,
+    onwaitlist: Callback<MouseEvent>,
+    onlocktoggle: Callback<MouseEvent>,
 }
 
 /////////////////////////////////////////////////
@@ -135,10 +177,23 @@ fn delivery_item(props: &DeliveryLiProps) -> Html {
         <li class="list-group-item d-flex justify-content-between">
             <div>
                 <div class="d-flex justify-content-between">
-                    <div class="mb-1">{format!("Delivery Date: {}", &props.deliverydate)}</div>
+                    <div class="mb-1">
+                        {format!("Delivery Date: {}", &props.deliverydate)}
+                        if props.islocked {
+                            <span class="badge bg-danger mx-1">{"Locked"}</span>
+                        }
+                    </div>
                     <small class="text-muted mx-2">{props.deliveryid.to_string()}</small>
                 </div>
                 <small class="text-muted">{format!("New Order Cutoff: {}", &props.newordercutoff)}</small>
+                if let Some(max_bags) = props.maxbags {
+                    <small class="text-muted mx-2">
+                        {format!("Max Bags: {max_bags}")}
+                        if props.iswaitlistenabled {
+                            {" (Waitlist)"}
+                        }
+                    </small>
+                }
             </div>
             <div class="float-end">
                 <button class="btn btn-outline-danger mx-1 float-end order-del-btn"
@@ -149,6 +204,24 @@ fn delivery_item(props: &DeliveryLiProps) -> Html {
                     data-deliveryid={props.deliveryid.to_string()} onclick={props.onedit.clone()}>
                     <i class="bi bi-pencil" fill="currentColor"></i>
                 </button>
+                <button class="btn btn-outline-secondary mx-1 float-end order-lock-btn"
+                    data-deliveryid={props.deliveryid.to_string()} onclick={props.onlocktoggle.clone()}>
+                    if props.islocked {
+                        <i class="bi bi-unlock" fill="currentColor"></i>
+                    } else {
+                        <i class="bi bi-lock" fill="currentColor"></i>
+                    }
+                </button>
+                <button class="btn btn-outline-warning mx-1 float-end order-rsch-btn"
+                    data-deliveryid={props.deliveryid.to_string()} onclick={props.onreschedule.clone()}>
+                    <i class="bi bi-calendar-range" fill="currentColor"></i>
+                </button>
+                if props.iswaitlistenabled {
+                    <button class="btn btn-outline-secondary float-end order-wait-btn"
+                        data-deliveryid={props.deliveryid.to_string()} onclick={props.onwaitlist.clone()}>
+                        <i class="bi bi-hourglass-split" fill="currentColor"></i>
+                    </button>
+                }
             </div>
         </li>
     }
@@ -198,16 +271,27 @@ fn disable_save_button(document: &web_sys::Document, value: bool, with_spinner:
 #[component(DeliveryUl)]
 pub(crate) fn delivery_list() -> Html {
     let deliveries = use_state(|| (*get_deliveries()).clone());
+    let timezone = use_state_eq(get_fundraiser_timezone);
     let is_dirty = use_state_eq(|| false);
 
     let on_add_or_update_dlg_submit = {
         let is_dirty = is_dirty.clone();
         let deliveries = deliveries.clone();
+        let timezone = timezone.clone();
         move |vals: DeliveryDlgAddOrUpdateCb| {
-            let (delivery_id, delivery_date, cutoff_date) = vals.to_owned();
+            let (delivery_id, delivery_date, cutoff_date, max_bags, is_waitlist_enabled) =
+                vals.to_owned();
             info!("Add/Updating Delivery {delivery_id} - {delivery_date} - {cutoff_date}");
-            let delivery_info = DeliveryInfo::new_from_admin(delivery_date, cutoff_date);
+            let mut delivery_info =
+                DeliveryInfo::new_from_admin(delivery_date, cutoff_date, &timezone);
+            delivery_info.set_capacity(max_bags, is_waitlist_enabled);
             let mut delivery_map = (*deliveries).clone();
+            // Editing the dates shouldn't unlock the delivery
+            delivery_info.set_locked(
+                delivery_map
+                    .get(&delivery_id)
+                    .is_some_and(|v| v.is_locked()),
+            );
             delivery_map.insert(delivery_id, delivery_info);
             deliveries.set(delivery_map);
             is_dirty.set(true);
@@ -253,18 +337,80 @@ pub(crate) fn delivery_list() -> Html {
                 let di = deliveries.get(&delivery_id).unwrap();
                 let delivery_date_str = di.get_delivery_date_str();
                 let cutoff_date_str = di.get_new_order_cutoff_date_str();
+                let max_bags_str = di.get_max_bags().map_or("".to_string(), |v| v.to_string());
                 let selected_delivery = selected_delivery_rc.borrow().as_ref().unwrap().clone();
                 let delivery_id_str = delivery_id.to_string();
                 selected_delivery.set(SelectedDeliveryInfo {
                     delivery_id_str,
                     delivery_date_str,
                     cutoff_date_str,
+                    max_bags_str,
+                    is_waitlist_enabled: di.is_waitlist_enabled(),
                 });
             });
             bootstrap::modal_op("deliveryAddOrEditDlg", "toggle");
         }
     };
 
+    let on_lock_toggle = {
+        let deliveries = deliveries.clone();
+        let is_dirty = is_dirty.clone();
+        move |evt: MouseEvent| {
+            let delivery_id = get_delivery_id(evt);
+            let mut delivery_map = (*deliveries).clone();
+            if let Some(delivery_info) = delivery_map.get_mut(&delivery_id) {
+                info!(
+                    "Setting lock for delivery {delivery_id} to {}",
+                    !delivery_info.is_locked()
+                );
+                delivery_info.set_locked(!delivery_info.is_locked());
+            }
+            deliveries.set(delivery_map);
+            is_dirty.set(true);
+        }
+    };
+
+    let on_reschedule = {
+        let is_dirty = is_dirty.clone();
+        move |evt: MouseEvent| {
+            let delivery_id = get_delivery_id(evt);
+            if *is_dirty {
+                gloo::dialogs::alert("Save the delivery dates before rescheduling orders");
+                return;
+            }
+            show_delivery_reschedule_dlg(delivery_id, false);
+        }
+    };
+
+    let on_waitlist = {
+        let is_dirty = is_dirty.clone();
+        move |evt: MouseEvent| {
+            let delivery_id = get_delivery_id(evt);
+            if *is_dirty {
+                gloo::dialogs::alert("Save the delivery dates before promoting waitlisted orders");
+                return;
+            }
+            show_delivery_reschedule_dlg(delivery_id, true);
+        }
+    };
+
+    let on_timezone_change = {
+        let deliveries = deliveries.clone();
+        let timezone = timezone.clone();
+        let is_dirty = is_dirty.clone();
+        move |evt: Event| {
+            let value = evt.target_unchecked_into::<HtmlSelectElement>().value();
+            info!("Setting fundraiser timezone to: {value}");
+            let mut delivery_map = (*deliveries).clone();
+            for delivery_info in delivery_map.values_mut() {
+                delivery_info.set_timezone(&value);
+            }
+            deliveries.set(delivery_map);
+            timezone.set(value);
+            is_dirty.set(true);
+        }
+    };
+
     let on_save_deliveries = {
         let deliveries = deliveries.clone();
         let is_dirty = is_dirty.clone();
@@ -302,14 +448,33 @@ pub(crate) fn delivery_list() -> Html {
                         }
                     </h5>
 
+                    <div class="form-floating mb-2">
+                        <select class="form-select" id="formFundraiserTimezone" onchange={on_timezone_change}>
+                        {
+                            FUNDRAISER_TIMEZONES.iter().map(|v| html! {
+                                <option value={*v} selected={*v == timezone.as_str()}>{*v}</option>
+                            }).collect::<Html>()
+                        }
+                        if !FUNDRAISER_TIMEZONES.contains(&timezone.as_str()) {
+                            <option value={(*timezone).clone()} selected=true>{(*timezone).clone()}</option>
+                        }
+                        </select>
+                        <label for="formFundraiserTimezone">{"Fundraiser Timezone (cutoffs are at the end of the day here)"}</label>
+                    </div>
 
                     <ul class="list-group">
                     {
                         (*deliveries).iter().map(|(id,delivery_info)| {
                             html!{<DeliveryLi deliveryid={id}
                                 deliverydate={delivery_info.get_delivery_date_str()}
-                                newordercutoff={delivery_info.get_new_order_cutoff_date_str()}
+                                newordercutoff={delivery_info.get_new_order_cutoff_display_str()}
+                                maxbags={delivery_info.get_max_bags()}
+                                iswaitlistenabled={delivery_info.is_waitlist_enabled()}
+                                islocked={delivery_info.is_locked()}
                                 ondelete={on_delete.clone()}
+                                onreschedule={on_reschedule.clone()}
+                                onwaitlist={on_waitlist.clone()}
+                                onlocktoggle={on_lock_toggle.clone()}
                                 onedit={on_edit.clone()} />}
                         }).collect::<Html>()
                     }
@@ -317,6 +482,7 @@ pub(crate) fn delivery_list() -> Html {
                 </div>
             </div>
             <DeliveryAddEditDlg onaddorupdate={on_add_or_update_dlg_submit}/>
+            <DeliveryRescheduleDlg/>
         </div>
     }
 }
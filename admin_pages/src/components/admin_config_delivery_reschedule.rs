@@ -0,0 +1,389 @@
+use data_model::*;
+use js::bootstrap;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::rc::Rc;
+use tracing::{error, info};
+use wasm_bindgen::JsCast;
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement, InputEvent, MouseEvent};
+use yew::prelude::*;
+
+#[derive(PartialEq, Clone, Debug)]
+enum RescheduleState {
+    Loading,
+    Choosing(Vec<DeliveryOrderInfo>),
+    Submitting,
+    Reviewing(Vec<DeliveryOrderInfo>),
+}
+
+thread_local! {
+    static RESCHEDULE_FROM: Rc<RefCell<Option<UseStateHandle<Option<u32>>>>> = Rc::new(RefCell::new(None));
+    static IS_WAITLIST: Rc<RefCell<Option<UseStateHandle<bool>>>> = Rc::new(RefCell::new(None));
+    static DLG_STATE: Rc<RefCell<Option<UseStateHandle<RescheduleState>>>> = Rc::new(RefCell::new(None));
+}
+
+/////////////////////////////////////////////////
+/// Shows the dialog for moving orders off of a delivery. When is_waitlist is set only the
+/// waitlisted orders are shown and moving them takes them off of the waitlist.
+pub(crate) fn show_delivery_reschedule_dlg(from_delivery_id: u32, is_waitlist: bool) {
+    info!("Rescheduling orders from delivery id: {from_delivery_id} waitlist: {is_waitlist}");
+    RESCHEDULE_FROM.with(|v| {
+        let from_state = v.borrow().as_ref().unwrap().clone();
+        from_state.set(Some(from_delivery_id));
+    });
+    IS_WAITLIST.with(|v| {
+        let is_waitlist_state = v.borrow().as_ref().unwrap().clone();
+        is_waitlist_state.set(is_waitlist);
+    });
+    DLG_STATE.with(|v| {
+        let dlg_state = v.borrow().as_ref().unwrap().clone();
+        dlg_state.set(RescheduleState::Loading);
+    });
+    bootstrap::modal_op("deliveryRescheduleDlg", "show");
+}
+
+/////////////////////////////////////////////////
+fn gen_address_str(customer: &CustomerInfo) -> String {
+    let mut addr = customer.addr1.clone();
+    if let Some(addr2) = customer.addr2.as_ref().filter(|v| !v.is_empty()) {
+        addr.push_str(&format!(" {addr2}"));
+    }
+    if let Some(city) = customer.city.as_ref() {
+        addr.push_str(&format!(", {city}"));
+    }
+    if let Some(zipcode) = customer.zipcode.as_ref() {
+        addr.push_str(&format!(" {zipcode}"));
+    }
+    addr
+}
+
+/////////////////////////////////////////////////
+#[component(DeliveryRescheduleDlg)]
+pub(crate) fn delivery_reschedule_dlg() -> Html {
+    let from_delivery_id = use_state_eq(|| None::<u32>);
+    let is_waitlist = use_state_eq(|| false);
+    let dlg_state = use_state(|| RescheduleState::Loading);
+    let to_delivery_id = use_state_eq(|| None::<u32>);
+    let hood_filter = use_state_eq(|| None::<String>);
+    let selected_orders = use_state_eq(BTreeSet::<String>::new);
+
+    {
+        let from_delivery_id = from_delivery_id.clone();
+        let dlg_state = dlg_state.clone();
+        let is_waitlist = is_waitlist.clone();
+        RESCHEDULE_FROM.with(|v| {
+            *v.borrow_mut() = Some(from_delivery_id);
+        });
+        IS_WAITLIST.with(|v| {
+            *v.borrow_mut() = Some(is_waitlist);
+        });
+        DLG_STATE.with(|v| {
+            *v.borrow_mut() = Some(dlg_state);
+        });
+    }
+
+    {
+        let dlg_state = dlg_state.clone();
+        let from_delivery_id = from_delivery_id.clone();
+        let to_delivery_id = to_delivery_id.clone();
+        let hood_filter = hood_filter.clone();
+        let selected_orders = selected_orders.clone();
+        let is_waitlist = *is_waitlist;
+        use_effect(move || {
+            if let (RescheduleState::Loading, Some(delivery_id)) = (&*dlg_state, *from_delivery_id)
+            {
+                wasm_bindgen_futures::spawn_local(async move {
+                    let orders = if is_waitlist {
+                        // The counts are needed to show how much room the other dates have
+                        if let Err(err) = load_delivery_bag_counts().await {
+                            error!("Failed to load delivery bag counts: {err:#?}");
+                        }
+                        get_waitlisted_orders_for_delivery(delivery_id).await
+                    } else {
+                        get_orders_for_delivery(delivery_id).await
+                    };
+                    match orders {
+                        Ok(orders) => {
+                            info!(
+                                "Found {} orders for delivery id: {delivery_id}",
+                                orders.len()
+                            );
+                            to_delivery_id.set(None);
+                            hood_filter.set(None);
+                            selected_orders
+                                .set(orders.iter().map(|v| v.order_id.clone()).collect());
+                            dlg_state.set(RescheduleState::Choosing(orders));
+                        }
+                        Err(err) => {
+                            gloo::dialogs::alert(&format!(
+                                "Failed to retrieve orders for delivery: {err:#?}"
+                            ));
+                            bootstrap::modal_op("deliveryRescheduleDlg", "hide");
+                        }
+                    }
+                });
+            }
+            || {}
+        });
+    }
+
+    let on_to_delivery_change = {
+        let to_delivery_id = to_delivery_id.clone();
+        Callback::from(move |evt: InputEvent| {
+            let value = evt
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+                .unwrap()
+                .value();
+            to_delivery_id.set(value.parse::<u32>().ok());
+        })
+    };
+
+    let on_hood_filter_change = {
+        let hood_filter = hood_filter.clone();
+        Callback::from(move |evt: InputEvent| {
+            let value = evt
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+                .unwrap()
+                .value();
+            if value.is_empty() {
+                hood_filter.set(None);
+            } else {
+                hood_filter.set(Some(value));
+            }
+        })
+    };
+
+    let on_order_selected = {
+        let selected_orders = selected_orders.clone();
+        Callback::from(move |evt: Event| {
+            let target_elm = evt
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                .unwrap();
+            let order_id = target_elm.dataset().get("orderid").unwrap();
+            let mut orders = (*selected_orders).clone();
+            if target_elm.checked() {
+                orders.insert(order_id);
+            } else {
+                orders.remove(&order_id);
+            }
+            selected_orders.set(orders);
+        })
+    };
+
+    let is_order_visible = {
+        let hood_filter = (*hood_filter).clone();
+        move |order: &DeliveryOrderInfo| -> bool {
+            hood_filter
+                .as_ref()
+                .is_none_or(|hood| order.customer.neighborhood.as_ref() == Some(hood))
+        }
+    };
+
+    let orders_to_move: Vec<DeliveryOrderInfo> = match &*dlg_state {
+        RescheduleState::Choosing(orders) => orders
+            .iter()
+            .filter(|v| is_order_visible(v) && selected_orders.contains(&v.order_id))
+            .cloned()
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let on_submit = {
+        let dlg_state = dlg_state.clone();
+        let to_delivery_id = to_delivery_id.clone();
+        let orders_to_move = orders_to_move.clone();
+        let is_waitlist = *is_waitlist;
+        Callback::from(move |_evt: MouseEvent| {
+            let Some(new_delivery_id) = *to_delivery_id else {
+                gloo::dialogs::alert("A delivery date to move the orders to must be selected");
+                return;
+            };
+            let orders_to_move = orders_to_move.clone();
+            let dlg_state = dlg_state.clone();
+            dlg_state.set(RescheduleState::Submitting);
+            wasm_bindgen_futures::spawn_local(async move {
+                let rslt = if is_waitlist {
+                    promote_waitlisted_orders(&orders_to_move, new_delivery_id).await
+                } else {
+                    reschedule_orders(&orders_to_move, new_delivery_id).await
+                };
+                match rslt {
+                    Ok(_) => dlg_state.set(RescheduleState::Reviewing(orders_to_move)),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to reschedule orders: {err:#?}"));
+                        bootstrap::modal_op("deliveryRescheduleDlg", "hide");
+                    }
+                }
+            });
+        })
+    };
+
+    let from_delivery_str = from_delivery_id
+        .map(|v| get_delivery_date(&v))
+        .unwrap_or_default();
+
+    let body_html = match &*dlg_state {
+        RescheduleState::Loading | RescheduleState::Submitting => html! {
+            <div class="d-flex justify-content-center">
+                <span class="spinner-border" role="status" aria-hidden="true"/>
+            </div>
+        },
+        RescheduleState::Choosing(orders) => {
+            let hoods = orders
+                .iter()
+                .filter_map(|v| v.customer.neighborhood.clone())
+                .collect::<BTreeSet<String>>();
+            html! {
+                <div class="container-sm">
+                    <div class="row mb-2">
+                        <div class="col-md">
+                            <div class="form-floating">
+                                <select class="form-select" id="formRescheduleToDelivery"
+                                    oninput={on_to_delivery_change}>
+                                    <option value="" selected={to_delivery_id.is_none()}>{"Select delivery date"}</option>
+                                    {
+                                        get_deliveries().iter()
+                                            // Waitlisted orders can be promoted onto their own date once there is room
+                                            .filter(|(id, _)| *is_waitlist || Some(**id) != *from_delivery_id)
+                                            .map(|(id, delivery)| html! {
+                                                <option value={id.to_string()} selected={Some(*id) == *to_delivery_id}>
+                                                    {
+                                                        match get_delivery_bags_remaining(*id).filter(|_| *is_waitlist) {
+                                                            Some(remaining) => format!(
+                                                                "{} ({} bags left)", delivery.get_delivery_date_str(), remaining.max(0)),
+                                                            None => delivery.get_delivery_date_str(),
+                                                        }
+                                                    }
+                                                </option>
+                                            }).collect::<Html>()
+                                    }
+                                </select>
+                                <label for="formRescheduleToDelivery">{"Move To Delivery Date"}</label>
+                            </div>
+                        </div>
+                        <div class="col-md">
+                            <div class="form-floating">
+                                <select class="form-select" id="formRescheduleHoodFilter"
+                                    oninput={on_hood_filter_change}>
+                                    <option value="" selected={hood_filter.is_none()}>{"All Neighborhoods"}</option>
+                                    {
+                                        hoods.iter().map(|hood| html! {
+                                            <option value={hood.clone()} selected={Some(hood) == hood_filter.as_ref()}>
+                                                {hood.clone()}
+                                            </option>
+                                        }).collect::<Html>()
+                                    }
+                                </select>
+                                <label for="formRescheduleHoodFilter">{"Neighborhood"}</label>
+                            </div>
+                        </div>
+                    </div>
+                    if orders.is_empty() && *is_waitlist {
+                        <div>{"There are no waitlisted orders for this delivery date"}</div>
+                    } else if orders.is_empty() {
+                        <div>{"There are no orders for this delivery date"}</div>
+                    } else {
+                        <ul class="list-group overflow-auto" style="max-height: 50vh;">
+                        {
+                            orders.iter().filter(|v| is_order_visible(v)).map(|order| {
+                                let has_spreaders = order.spreaders.as_ref().is_some_and(|v| !v.is_empty());
+                                html! {
+                                    <li class="list-group-item">
+                                        <input class="form-check-input me-1" type="checkbox"
+                                            data-orderid={order.order_id.clone()}
+                                            checked={selected_orders.contains(&order.order_id)}
+                                            onchange={on_order_selected.clone()}/>
+                                        {order.customer.name.clone()}
+                                        <small class="text-muted mx-2">{gen_address_str(&order.customer)}</small>
+                                        if has_spreaders {
+                                            <span class="badge bg-secondary">{"Spread"}</span>
+                                        }
+                                    </li>
+                                }
+                            }).collect::<Html>()
+                        }
+                        </ul>
+                    }
+                </div>
+            }
+        }
+        RescheduleState::Reviewing(orders) => html! {
+            <div class="container-sm">
+                <div class="mb-2">
+                    {format!(
+                        "{} orders were moved to {}. These customers should be notified:",
+                        orders.len(),
+                        to_delivery_id.map(|v| get_delivery_date(&v)).unwrap_or_default()
+                    )}
+                </div>
+                <table class="table table-sm table-responsive" id="rescheduledCustomersTable">
+                    <thead>
+                        <tr>
+                            <th>{"Name"}</th>
+                            <th>{"Phone"}</th>
+                            <th>{"Email"}</th>
+                            <th>{"Address"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                    {
+                        orders.iter().map(|order| html! {
+                            <tr>
+                                <td>{order.customer.name.clone()}</td>
+                                <td>{order.customer.phone.clone()}</td>
+                                <td>{order.customer.email.clone().unwrap_or_default()}</td>
+                                <td>{gen_address_str(&order.customer)}</td>
+                            </tr>
+                        }).collect::<Html>()
+                    }
+                    </tbody>
+                </table>
+            </div>
+        },
+    };
+
+    let is_reviewing = matches!(*dlg_state, RescheduleState::Reviewing(_));
+    let is_submit_disabled = !matches!(*dlg_state, RescheduleState::Choosing(_))
+        || orders_to_move.is_empty()
+        || to_delivery_id.is_none();
+
+    html! {
+        <div class="modal fade" id="deliveryRescheduleDlg"
+             tabIndex="-1" role="dialog" aria-labelledby="deliveryRescheduleDlgTitle" aria-hidden="true">
+            <div class="modal-dialog modal-lg modal-dialog-centered" role="document">
+                <div class="modal-content">
+                    <div class="modal-header">
+                        <h5 class="modal-title" id="deliveryRescheduleDlgLongTitle">
+                           if *is_waitlist {
+                               {format!("Promote Waitlisted Orders From: {from_delivery_str}")}
+                           } else {
+                               {format!("Reschedule Orders From: {from_delivery_str}")}
+                           }
+                        </h5>
+                    </div>
+                    <div class="modal-body">
+                        {body_html}
+                    </div>
+                    <div class="modal-footer">
+                        if is_reviewing {
+                            <button type="button" class="btn btn-primary" data-bs-dismiss="modal">{"Close"}</button>
+                        } else {
+                            <button type="button" class="btn btn-secondary" data-bs-dismiss="modal">{"Cancel"}</button>
+                            <button type="button" class="btn btn-primary float-end"
+                                disabled={is_submit_disabled} onclick={on_submit}>
+                                if *is_waitlist {
+                                    {format!("Promote {} Orders", orders_to_move.len())}
+                                } else {
+                                    {format!("Move {} Orders", orders_to_move.len())}
+                                }
+                            </button>
+                        }
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,447 @@
+use data_model::*;
+use js::bootstrap;
+use std::cell::RefCell;
+use std::rc::Rc;
+use tracing::info;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlButtonElement, HtmlElement, HtmlInputElement, MouseEvent};
+use yew::prelude::*;
+
+thread_local! {
+    static SELECTED_DIST_POINT: Rc<RefCell<Option<UseStateHandle<DistributionPoint>>>> = Rc::new(RefCell::new(None));
+}
+
+/////////////////////////////////////////////////
+fn set_is_invalid(id: &str, document: &web_sys::Document, is_invalid: bool) {
+    let class_list = get_element::<Element>(id, document).class_list();
+    let _ = if is_invalid {
+        class_list.add_1("is-invalid")
+    } else {
+        class_list.remove_1("is-invalid")
+    };
+}
+
+/////////////////////////////////////////////////
+// Older neighborhoods only have the distribution point name so those count too
+fn get_num_hoods_using(dist_point: &DistributionPoint, neighborhoods: &[Neighborhood]) -> usize {
+    neighborhoods
+        .iter()
+        .filter(|v| match v.distribution_point_id.as_ref() {
+            Some(id) => *id == dist_point.id,
+            None => v.distribution_point == dist_point.name,
+        })
+        .count()
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[derive(Properties, PartialEq, Clone, Debug)]
+struct DistPointAddEditDlgProps {
+    distpoints: Vec<DistributionPoint>,
+    onaddorupdate: Callback<DistributionPoint>,
+}
+
+#[component(DistPointAddEditDlg)]
+fn dist_point_add_or_edit_dlg(props: &DistPointAddEditDlgProps) -> Html {
+    let dist_point = use_state_eq(DistributionPoint::default);
+    let is_locating = use_state_eq(|| false);
+    {
+        // This adds the use_state handler so it can be accessed externally
+        let dist_point = dist_point.clone();
+        SELECTED_DIST_POINT.with(|rc| {
+            *rc.borrow_mut() = Some(dist_point);
+        });
+    }
+
+    let on_locate = {
+        let is_locating = is_locating.clone();
+        Callback::from(move |_evt: MouseEvent| {
+            let document = gloo::utils::document();
+            let Some(address) = get_html_input_value("frmDlgDistPtAddr", &document) else {
+                set_is_invalid("frmDlgDistPtAddr", &document, true);
+                return;
+            };
+            is_locating.set(true);
+            let is_locating = is_locating.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match geocode_address(&address).await {
+                    Some(pt) => {
+                        get_element::<HtmlInputElement>("frmDlgDistPtLat", &document)
+                            .set_value(&pt.lat.to_string());
+                        get_element::<HtmlInputElement>("frmDlgDistPtLng", &document)
+                            .set_value(&pt.lng.to_string());
+                    }
+                    None => gloo::dialogs::alert("Couldn't find the location for that address"),
+                }
+                is_locating.set(false);
+            });
+        })
+    };
+
+    let on_form_submission = {
+        let onaddorupdate = props.onaddorupdate.clone();
+        let dist_points = props.distpoints.clone();
+        let dist_point = dist_point.clone();
+
+        Callback::from(move |evt: SubmitEvent| {
+            evt.prevent_default();
+            evt.stop_propagation();
+
+            let document = gloo::utils::document();
+
+            // Neighborhoods and inventory find points by name so they have to be unique
+            let name = get_html_input_value("frmDlgDistPtName", &document).filter(|name| {
+                !dist_points
+                    .iter()
+                    .any(|v| v.id != dist_point.id && v.name.eq_ignore_ascii_case(name))
+            });
+            set_is_invalid("frmDlgDistPtName", &document, name.is_none());
+            let address = get_html_input_value("frmDlgDistPtAddr", &document);
+            set_is_invalid("frmDlgDistPtAddr", &document, address.is_none());
+
+            let latitude = get_html_input_value("frmDlgDistPtLat", &document)
+                .and_then(|v| v.parse::<f64>().ok());
+            let longitude = get_html_input_value("frmDlgDistPtLng", &document)
+                .and_then(|v| v.parse::<f64>().ok());
+            // Coordinates are optional but one without the other isn't useful
+            let is_location_valid = latitude.is_some() == longitude.is_some();
+            set_is_invalid("frmDlgDistPtLat", &document, !is_location_valid);
+
+            let capacity = get_html_input_value("frmDlgDistPtCapacity", &document);
+            let is_capacity_valid = capacity
+                .as_ref()
+                .is_none_or(|v| v.parse::<u32>().is_ok_and(|v| v > 0));
+            set_is_invalid("frmDlgDistPtCapacity", &document, !is_capacity_valid);
+
+            let (Some(name), Some(address)) = (name, address) else {
+                return;
+            };
+            if !is_location_valid || !is_capacity_valid {
+                return;
+            }
+
+            let id = if dist_point.id.is_empty() {
+                uuid::Uuid::new_v4().to_string()
+            } else {
+                dist_point.id.clone()
+            };
+            onaddorupdate.emit(DistributionPoint {
+                id,
+                name,
+                address,
+                latitude,
+                longitude,
+                contact_name: get_html_input_value("frmDlgDistPtContact", &document),
+                contact_phone: get_html_input_value("frmDlgDistPtPhone", &document),
+                capacity: capacity.and_then(|v| v.parse::<u32>().ok()),
+            });
+        })
+    };
+
+    html! {
+        <div class="modal fade" id="distPointAddOrEditDlg"
+             tabIndex="-1" role="dialog" aria-labelledby="distPointAddOrEditDlgTitle" aria-hidden="true">
+            <div class="modal-dialog modal-dialog-centered" role="document">
+                <div class="modal-content">
+                    <div class="modal-header">
+                        <h5 class="modal-title" id="distPointAddOrEditLongTitle">
+                           {"Add/Edit Distribution Point"}
+                        </h5>
+                    </div>
+                    <div class="modal-body">
+                        <div class="container-sm">
+                            <form class="needs-validation" novalidate=true onsubmit={on_form_submission}>
+                                <div class="row mb-1">
+                                    <div class="form-floating col-md">
+                                        <input class="form-control" type="text" autocomplete="fr-new-distpt" id="frmDlgDistPtName"
+                                            required=true
+                                            value={dist_point.name.clone()} />
+                                        <label for="frmDlgDistPtName">{"Name"}</label>
+                                        <div class="invalid-feedback">
+                                            {"* A name not used by another distribution point is required"}
+                                        </div>
+                                    </div>
+                                </div>
+                                <div class="row mb-1">
+                                    <div class="form-floating col-md">
+                                        <input class="form-control" type="text" autocomplete="fr-new-distpt-addr" id="frmDlgDistPtAddr"
+                                            required=true
+                                            value={dist_point.address.clone()} />
+                                        <label for="frmDlgDistPtAddr">{"Address"}</label>
+                                        <div class="invalid-feedback">
+                                            {"* Address is required"}
+                                        </div>
+                                    </div>
+                                </div>
+                                <div class="row mb-1">
+                                    <div class="form-floating col-md-5">
+                                        <input class="form-control" type="number" step="any" id="frmDlgDistPtLat"
+                                            value={dist_point.latitude.map_or("".to_string(), |v| v.to_string())} />
+                                        <label for="frmDlgDistPtLat">{"Latitude"}</label>
+                                        <div class="invalid-feedback">
+                                            {"* Both latitude and longitude are needed"}
+                                        </div>
+                                    </div>
+                                    <div class="form-floating col-md-5">
+                                        <input class="form-control" type="number" step="any" id="frmDlgDistPtLng"
+                                            value={dist_point.longitude.map_or("".to_string(), |v| v.to_string())} />
+                                        <label for="frmDlgDistPtLng">{"Longitude"}</label>
+                                    </div>
+                                    <div class="col-md-2 d-flex align-items-center">
+                                        <button type="button" class="btn btn-outline-secondary" onclick={on_locate}
+                                            disabled={*is_locating} title="Look up the coordinates from the address">
+                                            if *is_locating {
+                                                <span class="spinner-border spinner-border-sm" role="status" aria-hidden="true"/>
+                                            } else {
+                                                <i class="bi bi-geo-alt" fill="currentColor"></i>
+                                            }
+                                        </button>
+                                    </div>
+                                </div>
+                                <div class="row mb-1">
+                                    <div class="form-floating col-md-6">
+                                        <input class="form-control" type="text" id="frmDlgDistPtContact"
+                                            value={dist_point.contact_name.clone().unwrap_or_default()} />
+                                        <label for="frmDlgDistPtContact">{"Contact Name"}</label>
+                                    </div>
+                                    <div class="form-floating col-md-6">
+                                        <input class="form-control" type="tel" id="frmDlgDistPtPhone"
+                                            value={dist_point.contact_phone.clone().unwrap_or_default()} />
+                                        <label for="frmDlgDistPtPhone">{"Contact Phone"}</label>
+                                    </div>
+                                </div>
+                                <div class="row mb-1">
+                                    <div class="form-floating col-md">
+                                        <input class="form-control" type="number" min="1" id="frmDlgDistPtCapacity"
+                                            value={dist_point.capacity.map_or("".to_string(), |v| v.to_string())} />
+                                        <label for="frmDlgDistPtCapacity">{"Bag Capacity Per Delivery"}</label>
+                                        <div class="invalid-feedback">
+                                            {"* Capacity must be a positive number of bags"}
+                                        </div>
+                                    </div>
+                                </div>
+                                <div class="row">
+                                    <div class="col-md">
+                                        <button type="button" class="btn btn-secondary" data-bs-dismiss="modal">{"Cancel"}</button>
+                                        <button type="submit" class="btn btn-primary float-end">
+                                            {"Submit"}
+                                        </button>
+                                    </div>
+                                </div>
+                            </form>
+                        </div>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[derive(Properties, PartialEq, Clone, Debug)]
+struct DistPointLiProps {
+    distpoint: DistributionPoint,
+    numhoods: usize,
+    onedit: Callback<MouseEvent>,
+    ondelete: Callback<MouseEvent>,
+}
+
+#[component(DistPointLi)]
+fn dist_point_item(props: &DistPointLiProps) -> Html {
+    let dist_point = &props.distpoint;
+    html! {
+        <li class="list-group-item d-flex justify-content-between">
+            <div class="container">
+                <div class="row mb-1">{dist_point.name.clone()}</div>
+                <small class="text-muted row">{format!("Address: {}", &dist_point.address)}</small>
+                if let Some(pt) = dist_point.get_location() {
+                    <small class="text-muted row">{format!("Location: {:.6}, {:.6}", pt.lat, pt.lng)}</small>
+                }
+                if dist_point.contact_name.is_some() || dist_point.contact_phone.is_some() {
+                    <small class="text-muted row">
+                        {format!("Contact: {} {}",
+                            dist_point.contact_name.as_deref().unwrap_or(""),
+                            dist_point.contact_phone.as_deref().unwrap_or(""))}
+                    </small>
+                }
+                if let Some(capacity) = dist_point.capacity {
+                    <small class="text-muted row">{format!("Capacity: {capacity} bags")}</small>
+                }
+                <small class="text-muted row">{format!("Neighborhoods: {}", props.numhoods)}</small>
+            </div>
+            <div class="float-end d-flex">
+                <button class="btn btn-outline-info me-1 order-edt-btn"
+                    data-distpointid={dist_point.id.clone()} onclick={props.onedit.clone()}>
+                    <i class="bi bi-pencil" fill="currentColor"></i>
+                </button>
+                <button class="btn btn-outline-danger order-edt-btn" disabled={props.numhoods != 0}
+                    data-distpointid={dist_point.id.clone()} onclick={props.ondelete.clone()}>
+                    <i class="bi bi-trash" fill="currentColor"></i>
+                </button>
+            </div>
+        </li>
+    }
+}
+
+/////////////////////////////////////////////////
+fn get_selected_dist_point(evt: MouseEvent) -> String {
+    evt.target()
+        .and_then(|t| t.dyn_into::<Element>().ok())
+        .and_then(|t| {
+            if t.node_name() == "I" {
+                t.parent_element()
+            } else {
+                Some(t)
+            }
+        })
+        .and_then(|t| t.dyn_into::<HtmlElement>().ok())
+        .and_then(|t| t.dataset().get("distpointid"))
+        .unwrap()
+}
+
+/////////////////////////////////////////////////
+fn disable_save_button(document: &web_sys::Document, value: bool) {
+    if let Some(btn) = document
+        .get_element_by_id("btnSaveUpdatedDistPoints")
+        .and_then(|t| t.dyn_into::<HtmlButtonElement>().ok())
+    {
+        btn.set_disabled(value);
+        let spinner_display = if value { "inline-block" } else { "none" };
+        let _ = document
+            .get_element_by_id("saveDistPointsConfigSpinner")
+            .and_then(|t| t.dyn_into::<HtmlElement>().ok())
+            .unwrap()
+            .style()
+            .set_property("display", spinner_display);
+    }
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(DistributionPointsUl)]
+pub(crate) fn distribution_points_list() -> Html {
+    let dist_points = use_state(|| (*get_distribution_points()).clone());
+    let is_dirty = use_state_eq(|| false);
+
+    let on_add_or_update_dlg_submit = {
+        let is_dirty = is_dirty.clone();
+        let dist_points = dist_points.clone();
+        move |dist_point: DistributionPoint| {
+            info!("Add/Updating Distribution Point: {:#?}", &dist_point);
+            bootstrap::modal_op("distPointAddOrEditDlg", "hide");
+
+            let mut updated = (*dist_points).clone();
+            match updated.iter_mut().find(|v| v.id == dist_point.id) {
+                Some(existing) => *existing = dist_point,
+                None => updated.push(dist_point),
+            }
+            dist_points.set(updated);
+            is_dirty.set(true);
+        }
+    };
+
+    let on_add_dist_point = {
+        move |_evt: MouseEvent| {
+            SELECTED_DIST_POINT.with(|rc| {
+                let selected = rc.borrow().as_ref().unwrap().clone();
+                selected.set(DistributionPoint::default());
+            });
+            bootstrap::modal_op("distPointAddOrEditDlg", "show");
+        }
+    };
+
+    let on_edit = {
+        let dist_points = dist_points.clone();
+        move |evt: MouseEvent| {
+            let id = get_selected_dist_point(evt);
+            let Some(dist_point) = dist_points.iter().find(|v| v.id == id) else {
+                return;
+            };
+            SELECTED_DIST_POINT.with(|rc| {
+                let selected = rc.borrow().as_ref().unwrap().clone();
+                selected.set(dist_point.clone());
+            });
+            bootstrap::modal_op("distPointAddOrEditDlg", "show");
+        }
+    };
+
+    let on_delete = {
+        let dist_points = dist_points.clone();
+        let is_dirty = is_dirty.clone();
+        move |evt: MouseEvent| {
+            let id = get_selected_dist_point(evt);
+            let Some(dist_point) = dist_points.iter().find(|v| v.id == id) else {
+                return;
+            };
+            let num_hoods = get_num_hoods_using(dist_point, &get_neighborhoods());
+            if 0 != num_hoods {
+                gloo::dialogs::alert(&format!(
+                    "{} is still used by {num_hoods} neighborhood(s). Move them to another distribution point first.",
+                    &dist_point.name
+                ));
+                return;
+            }
+            let mut updated = (*dist_points).clone();
+            updated.retain(|v| v.id != id);
+            dist_points.set(updated);
+            is_dirty.set(true);
+        }
+    };
+
+    let on_save_dist_points = {
+        let dist_points = dist_points.clone();
+        let is_dirty = is_dirty.clone();
+        move |_evt: MouseEvent| {
+            let document = gloo::utils::document();
+            disable_save_button(&document, true);
+            let updated = (*dist_points).clone();
+            let is_dirty = is_dirty.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Saving Distribution Points: {updated:#?}");
+                match set_distribution_points(updated).await {
+                    Ok(_) => is_dirty.set(false),
+                    Err(err) => gloo::dialogs::alert(&format!(
+                        "Failed updating distribution points:\n{err:#?}"
+                    )),
+                }
+                disable_save_button(&document, false);
+            });
+        }
+    };
+
+    let neighborhoods = get_neighborhoods();
+    html! {
+        <div>
+            <div class="card">
+                <div class="card-body">
+                    <h5 class="card-title">
+                        {"Distribution Points"}
+                        <button class="btn btn-outline-info float-end order-edt-btn" onclick={on_add_dist_point}>
+                            <i class="bi bi-plus-square" fill="currentColor"></i>
+                        </button>
+                        if *is_dirty {
+                            <button class="btn btn-primary" onclick={on_save_dist_points} id="btnSaveUpdatedDistPoints">
+                                <span class="spinner-border spinner-border-sm me-1" role="status"
+                                aria-hidden="true" id="saveDistPointsConfigSpinner" style="display: none;" />
+                                {"Save"}
+                            </button>
+                        }
+                    </h5>
+                    <ul class="list-group">
+                    {
+                        dist_points.iter().map(|dist_point| {
+                            let num_hoods = get_num_hoods_using(dist_point, &neighborhoods);
+                            html!{
+                                <DistPointLi distpoint={dist_point.clone()} numhoods={num_hoods}
+                                    onedit={on_edit.clone()} ondelete={on_delete.clone()} />
+                            }
+                        }).collect::<Html>()
+                    }
+                    </ul>
+                </div>
+            </div>
+            <DistPointAddEditDlg distpoints={(*dist_points).clone()} onaddorupdate={on_add_or_update_dlg_submit}/>
+        </div>
+    }
+}
@@ -4,7 +4,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use tracing::info;
 use wasm_bindgen::JsCast;
-use web_sys::{Element, HtmlButtonElement, HtmlElement, MouseEvent};
+use web_sys::{Element, HtmlButtonElement, HtmlElement, HtmlSelectElement, MouseEvent};
 use yew::prelude::*;
 
 thread_local! {
@@ -27,6 +27,7 @@ fn neighborhood_add_or_edit_dlg(props: &NeighborhoodAddEditDlgProps) -> Html {
         city: None,
         is_visible: false,
         distribution_point: "".to_string(),
+        distribution_point_id: None,
     });
     {
         // This adds the use_state handler so it can be accessed externally
@@ -64,7 +65,9 @@ fn neighborhood_add_or_edit_dlg(props: &NeighborhoodAddEditDlgProps) -> Html {
                 }
             };
 
-            let distribution_point = match get_html_input_value("frmDlgHoodDistPt", &document) {
+            let dist_point_id =
+                get_element::<HtmlSelectElement>("frmDlgHoodDistPt", &document).value();
+            let distribution_point = match get_distribution_point(&dist_point_id) {
                 Some(distribution_point) => {
                     let _ = get_element::<Element>("frmDlgHoodDistPt", &document)
                         .class_list()
@@ -76,7 +79,7 @@ fn neighborhood_add_or_edit_dlg(props: &NeighborhoodAddEditDlgProps) -> Html {
                     let _ = get_element::<Element>("frmDlgHoodDistPt", &document)
                         .class_list()
                         .add_1("is-invalid");
-                    "".to_string()
+                    DistributionPoint::default()
                 }
             };
 
@@ -121,7 +124,8 @@ fn neighborhood_add_or_edit_dlg(props: &NeighborhoodAddEditDlgProps) -> Html {
             if is_valid {
                 let hood = Neighborhood {
                     name,
-                    distribution_point,
+                    distribution_point: distribution_point.name,
+                    distribution_point_id: Some(distribution_point.id),
                     city,
                     zipcode,
                     is_visible: get_html_checked_input_value("frmDlgHoodIsVisible", &document),
@@ -132,6 +136,12 @@ fn neighborhood_add_or_edit_dlg(props: &NeighborhoodAddEditDlgProps) -> Html {
         })
     };
 
+    // Neighborhoods saved before distribution points were configured only have the name
+    let selected_dist_point_id = neighborhood
+        .distribution_point_id
+        .clone()
+        .or_else(|| get_distribution_point_by_name(&neighborhood.distribution_point).map(|v| v.id));
+
     html! {
         <div class="modal fade" id="neighborhoodAddOrEditDlg"
              tabIndex="-1" role="dialog" aria-labelledby="neighborhoodAddOrEditDlgTitle" aria-hidden="true">
@@ -173,12 +183,22 @@ fn neighborhood_add_or_edit_dlg(props: &NeighborhoodAddEditDlgProps) -> Html {
                                 <div class="row mb-1">
                                     <div class="col-md">
                                         <div class="form-floating col-md">
-                                            <input class="form-control" type="edit" autocomplete="fr-new-distpt" id="frmDlgHoodDistPt"
-                                                required=true
-                                                value={neighborhood.distribution_point.clone()} />
+                                            <select class="form-select" id="frmDlgHoodDistPt" required=true>
+                                                <option value="" selected={selected_dist_point_id.is_none()}>
+                                                    {"Select a distribution point"}
+                                                </option>
+                                                {
+                                                    get_distribution_points().iter().map(|v| html! {
+                                                        <option value={v.id.clone()}
+                                                            selected={selected_dist_point_id.as_ref() == Some(&v.id)}>
+                                                            {v.name.clone()}
+                                                        </option>
+                                                    }).collect::<Html>()
+                                                }
+                                            </select>
                                             <label for="frmDlgHoodDistPt">{"Distribution Point"}</label>
                                             <div class="invalid-feedback">
-                                                {"* Distribution point is required"}
+                                                {"* Distribution point is required. They are added on the Distribution Points tab."}
                                             </div>
                                         </div>
                                     </div>
@@ -245,7 +265,7 @@ fn neighborhood_item(props: &NeighborhoodLiProps) -> Html {
         <li class={liclass}>
             <div class="container">
                 <div class="row mb-1">{props.hood.name.clone()}</div>
-                <small class="text-muted row">{format!("Distribution Point: {}", &props.hood.distribution_point)}</small>
+                <small class="text-muted row">{format!("Distribution Point: {}", props.hood.get_distribution_point_name())}</small>
                 if props.hood.city.is_some() {
                     <small class="text-muted row">{format!("City: {}", &props.hood.city.as_ref().unwrap())}</small>
                 }
@@ -340,6 +360,7 @@ pub(crate) fn neighborhood_list() -> Html {
                     city: None,
                     is_visible: false,
                     distribution_point: "".to_string(),
+                    distribution_point_id: None,
                 });
             });
 
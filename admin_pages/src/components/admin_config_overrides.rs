@@ -0,0 +1,251 @@
+use data_model::*;
+use tracing::info;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, Event, HtmlElement, HtmlInputElement, HtmlSelectElement, MouseEvent};
+use yew::prelude::*;
+
+/////////////////////////////////////////////////
+fn get_override_target_str(cutoff_override: &CutoffOverride) -> String {
+    match cutoff_override.kind {
+        CutoffOverrideKind::SellerExtension => get_username_from_id(&cutoff_override.target_id)
+            .map_or(cutoff_override.target_id.clone(), |v| {
+                format!("{v}[{}]", &cutoff_override.target_id)
+            }),
+        CutoffOverrideKind::OrderReopen => cutoff_override.target_id.clone(),
+    }
+}
+
+/////////////////////////////////////////////////
+fn get_override_id(evt: MouseEvent) -> String {
+    evt.target()
+        .and_then(|t| t.dyn_into::<Element>().ok())
+        .and_then(|t| {
+            if t.node_name() == "I" {
+                t.parent_element()
+            } else {
+                Some(t)
+            }
+        })
+        .and_then(|t| t.dyn_into::<HtmlElement>().ok())
+        .and_then(|t| t.dataset().get("overrideid"))
+        .unwrap()
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(CutoffOverridesUl)]
+pub(crate) fn cutoff_overrides_list() -> Html {
+    let overrides: UseStateHandle<Option<Vec<CutoffOverride>>> = use_state_eq(|| None);
+    let kind = use_state_eq(CutoffOverrideKind::default);
+    let is_granting = use_state_eq(|| false);
+
+    {
+        let overrides = overrides.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Loading cutoff overrides");
+                match get_all_cutoff_overrides().await {
+                    Ok(v) => overrides.set(Some(v)),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to load overrides: {err:#?}"));
+                        overrides.set(Some(Vec::new()));
+                    }
+                }
+            });
+            || {}
+        });
+    }
+
+    let on_kind_change = {
+        let kind = kind.clone();
+        Callback::from(move |evt: Event| {
+            let value = evt.target_unchecked_into::<HtmlSelectElement>().value();
+            kind.set(value.parse::<CutoffOverrideKind>().unwrap_or_default());
+        })
+    };
+
+    let on_grant = {
+        let overrides = overrides.clone();
+        let kind = kind.clone();
+        let is_granting = is_granting.clone();
+        Callback::from(move |_evt: MouseEvent| {
+            let document = gloo::utils::document();
+            let target_id = match *kind {
+                CutoffOverrideKind::SellerExtension => {
+                    get_element::<HtmlSelectElement>("formOverrideSeller", &document).value()
+                }
+                CutoffOverrideKind::OrderReopen => {
+                    get_element::<HtmlInputElement>("formOverrideOrderId", &document)
+                        .value()
+                        .trim()
+                        .to_string()
+                }
+            };
+            let reason = get_element::<HtmlInputElement>("formOverrideReason", &document)
+                .value()
+                .trim()
+                .to_string();
+            let expires_at = parse_fundraiser_datetime(
+                &get_element::<HtmlInputElement>("formOverrideExpires", &document).value(),
+            );
+
+            if target_id.is_empty() || reason.is_empty() {
+                gloo::dialogs::alert("Who or what the override is for and why are required");
+                return;
+            }
+            let Some(expires_at) = expires_at.filter(|v| *v > chrono::Utc::now()) else {
+                gloo::dialogs::alert("The override must expire sometime in the future");
+                return;
+            };
+
+            is_granting.set(true);
+            let overrides = overrides.clone();
+            let is_granting = is_granting.clone();
+            let kind = *kind;
+            wasm_bindgen_futures::spawn_local(async move {
+                match add_cutoff_override(kind, &target_id, &reason, expires_at).await {
+                    Ok(cutoff_override) => {
+                        let mut updated = vec![cutoff_override];
+                        updated.extend((*overrides).clone().unwrap_or_default());
+                        overrides.set(Some(updated));
+                    }
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to grant override: {err:#?}"));
+                    }
+                }
+                is_granting.set(false);
+            });
+        })
+    };
+
+    let on_revoke = {
+        let overrides = overrides.clone();
+        Callback::from(move |evt: MouseEvent| {
+            let override_id = get_override_id(evt);
+            if !gloo::dialogs::confirm("Revoke this override now?") {
+                return;
+            }
+            let overrides = overrides.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match revoke_cutoff_override(&override_id).await {
+                    Ok(_) => {
+                        let mut updated = (*overrides).clone().unwrap_or_default();
+                        updated
+                            .iter_mut()
+                            .filter(|v| v.id == override_id)
+                            .for_each(|v| v.is_revoked = Some(true));
+                        overrides.set(Some(updated));
+                    }
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to revoke override: {err:#?}"));
+                    }
+                }
+            });
+        })
+    };
+
+    let Some(overrides) = &*overrides else {
+        return html! {
+            <div class="spinner-border" role="status">
+                <span class="visually-hidden">{"Loading..."}</span>
+            </div>
+        };
+    };
+
+    html! {
+        <div class="card">
+            <div class="card-body">
+                <h5 class="card-title">
+                    {"Cutoff Overrides"}
+                </h5>
+                <div class="row g-2 mb-2">
+                    <div class="form-floating col-md-3">
+                        <select class="form-select" id="formOverrideKind" onchange={on_kind_change}>
+                        {
+                            [CutoffOverrideKind::SellerExtension, CutoffOverrideKind::OrderReopen]
+                                .iter()
+                                .map(|v| html! {
+                                    <option value={v.to_string()} selected={*v == *kind}>{v.to_string()}</option>
+                                })
+                                .collect::<Html>()
+                        }
+                        </select>
+                        <label for="formOverrideKind">{"Override"}</label>
+                    </div>
+                    <div class="form-floating col-md-3">
+                        if CutoffOverrideKind::SellerExtension == *kind {
+                            <select class="form-select" id="formOverrideSeller">
+                            {
+                                get_users().iter().map(|(uid, user)| html! {
+                                    <option value={uid.clone()}>{format!("{}[{uid}]", &user.name)}</option>
+                                }).collect::<Html>()
+                            }
+                            </select>
+                            <label for="formOverrideSeller">{"Seller"}</label>
+                        } else {
+                            <input class="form-control" type="text" id="formOverrideOrderId"/>
+                            <label for="formOverrideOrderId">{"Order Id"}</label>
+                        }
+                    </div>
+                    <div class="form-floating col-md-3">
+                        <input class="form-control" type="text" id="formOverrideReason"/>
+                        <label for="formOverrideReason">{"Reason"}</label>
+                    </div>
+                    <div class="form-floating col-md-3">
+                        <input class="form-control" type="datetime-local" id="formOverrideExpires"/>
+                        <label for="formOverrideExpires">{format!("Expires ({})", get_fundraiser_timezone())}</label>
+                    </div>
+                </div>
+                <button class="btn btn-primary mb-2" onclick={on_grant} disabled={*is_granting}>
+                    if *is_granting {
+                        <span class="spinner-border spinner-border-sm me-1" role="status" aria-hidden="true"/>
+                    }
+                    {"Grant"}
+                </button>
+                <table class="table table-sm table-striped">
+                    <thead>
+                        <tr>
+                            <th>{"Override"}</th>
+                            <th>{"For"}</th>
+                            <th>{"Reason"}</th>
+                            <th>{"Granted By"}</th>
+                            <th>{"Granted On"}</th>
+                            <th>{"Expires"}</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                    {
+                        overrides.iter().map(|v| {
+                            let granted_by = get_username_from_id(&v.granted_by)
+                                .unwrap_or(v.granted_by.clone());
+                            html! {
+                                <tr>
+                                    <td>{v.kind.to_string()}</td>
+                                    <td>{get_override_target_str(v)}</td>
+                                    <td>{v.reason.clone()}</td>
+                                    <td>{granted_by}</td>
+                                    <td>{v.get_grant_time_str()}</td>
+                                    <td>{v.get_expires_at_str()}</td>
+                                    <td>
+                                        if v.is_active() {
+                                            <button class="btn btn-outline-danger btn-sm"
+                                                data-overrideid={v.id.clone()} onclick={on_revoke.clone()}>
+                                                <i class="bi bi-x-circle" fill="currentColor"></i>
+                                            </button>
+                                        } else if v.is_revoked.unwrap_or(false) {
+                                            <span class="badge bg-secondary">{"Revoked"}</span>
+                                        } else {
+                                            <span class="badge bg-secondary">{"Expired"}</span>
+                                        }
+                                    </td>
+                                </tr>
+                            }
+                        }).collect::<Html>()
+                    }
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    }
+}
@@ -1,6 +1,3 @@
-use calamine::{Ods, RangeDeserializerBuilder, Reader, Xlsx, open_workbook_from_rs};
-use std::io::Cursor;
-
 use data_model::*;
 use gloo::file::File;
 use js::bootstrap;
@@ -10,7 +7,9 @@ use std::collections::BTreeMap;
 use std::rc::Rc;
 use tracing::info;
 use wasm_bindgen::JsCast;
-use web_sys::{Element, FileList, HtmlButtonElement, HtmlElement, HtmlInputElement, MouseEvent};
+use web_sys::{
+    Element, Event, FileList, HtmlButtonElement, HtmlElement, HtmlInputElement, MouseEvent,
+};
 use yew::prelude::*;
 
 #[derive(PartialEq, Clone, Default, Debug)]
@@ -98,94 +97,6 @@ fn process_user_file_rec(
     potential_new_users.insert(new_id, record);
 }
 
-/////////////////////////////////////////////////
-fn process_csv_records(
-    data: Vec<u8>,
-    potential_new_users: &mut BTreeMap<String, UserFileRec>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let mut rdr = csv::Reader::from_reader(&data[..]);
-    for result in rdr.deserialize() {
-        let record: UserFileRec = result?;
-        process_user_file_rec(record, potential_new_users);
-    }
-    Ok(())
-}
-
-/////////////////////////////////////////////////
-fn process_spreadsheet_records<T>(
-    mut wb: T,
-    potential_new_users: &mut BTreeMap<String, UserFileRec>,
-) -> Result<(), Box<dyn std::error::Error>>
-where
-    T: Reader<Cursor<Vec<u8>>>,
-{
-    let range = wb
-        .worksheet_range_at(0)
-        .unwrap()
-        .map_err(|_| calamine::Error::Msg("Cannot find Sheet1"))?;
-
-    let iter_records = RangeDeserializerBuilder::new()
-        .has_headers(true)
-        .from_range(&range)?;
-
-    for result in iter_records {
-        let record: UserFileRec = result?;
-        process_user_file_rec(record, potential_new_users);
-    }
-
-    Ok(())
-}
-
-/////////////////////////////////////////////////
-fn process_xlsx_records(
-    data: Vec<u8>,
-    potential_new_users: &mut BTreeMap<String, UserFileRec>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let c = Cursor::new(data);
-    let wb: Xlsx<_> = open_workbook_from_rs(c)?;
-
-    process_spreadsheet_records(wb, potential_new_users)
-}
-
-/////////////////////////////////////////////////
-fn process_ods_records(
-    data: Vec<u8>,
-    potential_new_users: &mut BTreeMap<String, UserFileRec>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let c = Cursor::new(data);
-    let wb: Ods<_> = open_workbook_from_rs(c)?;
-
-    process_spreadsheet_records(wb, potential_new_users)
-}
-
-/////////////////////////////////////////////////
-fn process_uploaded_file(
-    filename: String,
-    mimetype: String,
-    data: Vec<u8>,
-    potential_new_users: &mut BTreeMap<String, UserFileRec>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    // 2ndBatch.xlsx type: application/vnd.openxmlformats-officedocument.spreadsheetml.sheet
-    // 2ndBatch.ods type: application/vnd.oasis.opendocument.spreadsheet
-
-    match true {
-        _ if filename.ends_with(".csv") || mimetype.eq("text/csv") => {
-            process_csv_records(data, potential_new_users)
-        }
-        _ if filename.to_ascii_lowercase().ends_with(".xlsx")
-            || mimetype.eq("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet") =>
-        {
-            process_xlsx_records(data, potential_new_users)
-        }
-        _ if filename.to_ascii_lowercase().ends_with(".ods")
-            || mimetype.eq("application/vnd.oasis.opendocument.spreadsheet") =>
-        {
-            process_ods_records(data, potential_new_users)
-        }
-        _ => panic!(),
-    }
-}
-
 #[component(UploadUsersDlg)]
 fn upload_users_dlg(props: &UploadUsersDlgProps) -> Html {
     let users = use_state_eq(Vec::<UserAdminConfig>::new);
@@ -272,15 +183,18 @@ fn upload_users_dlg(props: &UploadUsersDlgProps) -> Html {
                         }
                     };
 
-                    if let Err(err) =
-                        process_uploaded_file(file_name, file_type, data, &mut potential_new_users)
-                    {
-                        gloo::dialogs::alert(&format!(
-                            "Error in users file make sure proper headers are in place:\n{err:#?}"
-                        ));
-                        input.set_value("");
-                        potential_new_users.clear();
-                        break;
+                    match read_uploaded_file_records::<UserFileRec>(&file_name, &file_type, data) {
+                        Ok(records) => records
+                            .into_iter()
+                            .for_each(|v| process_user_file_rec(v, &mut potential_new_users)),
+                        Err(err) => {
+                            gloo::dialogs::alert(&format!(
+                                "Error in users file make sure proper headers are in place:\n{err:#?}"
+                            ));
+                            input.set_value("");
+                            potential_new_users.clear();
+                            break;
+                        }
                     }
                 }
 
@@ -555,6 +469,133 @@ fn user_item(props: &UserLiProps) -> Html {
     }
 }
 
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[derive(Properties, PartialEq, Clone, Debug)]
+struct GroupAttributesCardProps {
+    groups: Vec<String>,
+}
+
+#[component(GroupAttributesCard)]
+fn group_attributes_card(props: &GroupAttributesCardProps) -> Html {
+    let group_attributes = use_state_eq(|| {
+        get_group_attributes()
+            .iter()
+            .map(|v| (v.group.clone(), v.clone()))
+            .collect::<BTreeMap<String, GroupAttributes>>()
+    });
+    let is_dirty = use_state_eq(|| false);
+    let is_saving = use_state_eq(|| false);
+
+    let on_attribute_change = {
+        let group_attributes = group_attributes.clone();
+        let is_dirty = is_dirty.clone();
+        Callback::from(move |evt: Event| {
+            let input = evt.target_unchecked_into::<HtmlInputElement>();
+            let (Some(group), Some(attr)) =
+                (input.dataset().get("group"), input.dataset().get("attr"))
+            else {
+                return;
+            };
+            let mut attributes_map = (*group_attributes).clone();
+            let attributes = attributes_map
+                .entry(group.clone())
+                .or_insert_with(|| GroupAttributes::new(&group));
+            match attr.as_str() {
+                "timecard" => attributes.is_timecard_eligible = input.checked(),
+                "allocation" => attributes.is_allocation_eligible = input.checked(),
+                "topsellers" => attributes.is_hidden_from_top_sellers = input.checked(),
+                _ => return,
+            }
+            group_attributes.set(attributes_map);
+            is_dirty.set(true);
+        })
+    };
+
+    let on_save = {
+        let group_attributes = group_attributes.clone();
+        let is_dirty = is_dirty.clone();
+        let is_saving = is_saving.clone();
+        Callback::from(move |_evt: MouseEvent| {
+            let group_attributes = (*group_attributes).clone().into_values().collect();
+            let is_dirty = is_dirty.clone();
+            let is_saving = is_saving.clone();
+            is_saving.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Saving group attributes");
+                match set_group_attributes(group_attributes).await {
+                    Ok(_) => is_dirty.set(false),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed saving group attributes:\n{err:#?}"))
+                    }
+                }
+                is_saving.set(false);
+            });
+        })
+    };
+
+    let mut groups = props.groups.clone();
+    groups.extend(group_attributes.keys().cloned());
+    groups.sort();
+    groups.dedup();
+
+    html! {
+        <div class="card mt-2">
+            <div class="card-body">
+                <h5 class="card-title">
+                    {"Groups"}
+                    if *is_dirty {
+                        <button class="btn btn-primary ms-2" onclick={on_save} disabled={*is_saving}>
+                            if *is_saving {
+                                <span class="spinner-border spinner-border-sm me-1" role="status" aria-hidden="true"/>
+                            }
+                            {"Save"}
+                        </button>
+                    }
+                </h5>
+                <table class="table table-sm">
+                    <thead>
+                        <tr>
+                            <th>{"Group"}</th>
+                            <th>{"Timecards"}</th>
+                            <th>{"Allocations"}</th>
+                            <th>{"Hidden From Top Sellers"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                    {
+                        groups.iter().map(|group| {
+                            let attributes = group_attributes
+                                .get(group)
+                                .cloned()
+                                .unwrap_or_else(|| GroupAttributes::new(group));
+                            html! {
+                                <tr>
+                                    <td>{group.clone()}</td>
+                                    {
+                                        [
+                                            ("timecard", attributes.is_timecard_eligible),
+                                            ("allocation", attributes.is_allocation_eligible),
+                                            ("topsellers", attributes.is_hidden_from_top_sellers),
+                                        ].iter().map(|(attr, is_checked)| html! {
+                                            <td>
+                                                <input class="form-check-input" type="checkbox"
+                                                    data-group={group.clone()} data-attr={*attr}
+                                                    checked={*is_checked} onchange={on_attribute_change.clone()}/>
+                                            </td>
+                                        }).collect::<Html>()
+                                    }
+                                </tr>
+                            }
+                        }).collect::<Html>()
+                    }
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    }
+}
+
 /////////////////////////////////////////////////
 fn get_selected_user(evt: MouseEvent) -> String {
     let btn_elm = evt
@@ -736,6 +777,9 @@ pub(crate) fn user_list() -> Html {
                     </ul>
                 </div>
             </div>
+            <GroupAttributesCard groups={
+                users.values().map(|v| v.group.clone()).filter(|v| !v.is_empty()).collect::<Vec<String>>()
+            }/>
             <EditUserDlg onupdate={on_edit_user_group_dlg_submit}/>
             <UploadUsersDlg onadd={on_upload_users_dlg_submit} knownusers={(*users).clone()}/>
         </div>
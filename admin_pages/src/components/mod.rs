@@ -1,4 +1,8 @@
+pub(crate) mod admin_config_audit_log;
 pub(crate) mod admin_config_deliveries;
+pub(crate) mod admin_config_delivery_reschedule;
+pub(crate) mod admin_config_distribution_points;
 pub(crate) mod admin_config_neighborhoods;
+pub(crate) mod admin_config_overrides;
 pub(crate) mod admin_config_product_costs;
 pub(crate) mod admin_config_users;
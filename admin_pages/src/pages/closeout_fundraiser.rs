@@ -1,6 +1,9 @@
 use rust_decimal::prelude::*;
+use std::collections::BTreeMap;
 use wasm_bindgen::JsCast;
-use web_sys::{FocusEvent, HtmlAnchorElement, HtmlInputElement, InputEvent, MouseEvent, Url};
+use web_sys::{
+    Event, FocusEvent, HtmlAnchorElement, HtmlInputElement, InputEvent, MouseEvent, Url,
+};
 use yew::prelude::*;
 
 use data_model::*;
@@ -19,17 +22,20 @@ fn calculate_new_dvars(
     let svars = svar_map.get("TROOP_TOTALS").unwrap();
 
     info!(
-        "BD: {}, MS: {}, SP: {} DN: {}",
+        "BD: {}, MS: {}, SP: {} DN: {} RF: {}",
         &dvars.bank_deposited,
         &dvars.mulch_cost,
         &svars.amount_from_bags_to_spread_sales,
-        &svars.amount_from_donations
+        &svars.amount_from_donations,
+        &svars.amount_refunded
     );
+    // Refunds were paid back out of the deposited funds
     dvars.mulch_sales_gross = dvars
         .bank_deposited
         .checked_sub(svars.amount_from_bags_to_spread_sales)
         .and_then(|v| v.checked_sub(dvars.mulch_cost))
         .and_then(|v| v.checked_sub(svars.amount_from_donations))
+        .and_then(|v| v.checked_sub(svars.amount_refunded))
         .unwrap();
     dvars.money_pool_for_troop = dvars
         .mulch_sales_gross
@@ -59,12 +65,17 @@ fn calculate_new_dvars(
         .amount_from_bags_sales
         .checked_sub(dvars.mulch_cost)
         .unwrap();
-    let delivery_time_in_minutes =
-        Decimal::from_f64(svars.delivery_time_total.as_secs_f64() / 60.0).unwrap();
-    dvars.delivery_earnings_per_minute = dvars
-        .money_pool_for_scout_delivery
-        .checked_div(delivery_time_in_minutes)
-        .unwrap();
+    // Each activity's minutes are weighted so a minute loading trucks can be worth more or less
+    let delivery_time_in_minutes = svars.get_weighted_delivery_minutes();
+    // No delivery time yet means there is nothing to split the delivery pool by
+    dvars.delivery_earnings_per_minute = if delivery_time_in_minutes.is_zero() {
+        Decimal::ZERO
+    } else {
+        dvars
+            .money_pool_for_scout_delivery
+            .checked_div(delivery_time_in_minutes)
+            .unwrap()
+    };
     Some(dvars)
 }
 ////////////////////////////////////////////////////////
@@ -90,6 +101,11 @@ fn calculate_per_scout_report(
     let mut scout_vals = svar_map
         .iter()
         .filter(|(uid, _)| uid.as_str() != "TROOP_TOTALS")
+        .filter(|(uid, _)| {
+            !uid_2_name_map
+                .get(uid.as_str())
+                .is_some_and(|v| is_allocation_excluded_group(&v.group))
+        })
         .map(|(uid, data)| {
             total_calc_donations = total_calc_donations
                 .checked_add(data.amount_from_donations)
@@ -135,7 +151,7 @@ fn calculate_per_scout_report(
                 Decimal::from_f64(data.delivery_time_total.as_secs_f64() / 60.0).unwrap();
             let allocations_from_delivery = dvars
                 .delivery_earnings_per_minute
-                .checked_mul(total_delivery_minutes)
+                .checked_mul(data.get_weighted_delivery_minutes())
                 .unwrap();
             calc_allocations_from_delivery = calc_allocations_from_delivery
                 .checked_add(allocations_from_delivery)
@@ -359,12 +375,17 @@ fn sales_table(props: &SalesTableProps) -> Html {
                             <td></td>
                             <td>{decimal_to_money_string(&svars.amount_from_donations)}</td>
                         </tr>
+                        <tr>
+                            <td>{"Refunds"}</td>
+                            <td></td>
+                            <td>{decimal_to_money_string(&(-svars.amount_refunded))}</td>
+                        </tr>
                     </tbody>
                     <tfoot>
                         <tr>
                             <td>{"Total Collected"}</td>
                             <td></td>
-                            <td>{decimal_to_money_string(&svars.amount_total_collected)}</td>
+                            <td>{decimal_to_money_string(&svars.amount_total_collected.checked_sub(svars.amount_refunded).unwrap())}</td>
                         </tr>
                     </tfoot>
                 </table>
@@ -451,6 +472,21 @@ fn allocations_table(props: &AllocationsTableProps) -> Html {
                                     .to_string()
                             }</td>
                         </tr>
+                        {
+                            svars.activity_time_totals.iter()
+                                .filter(|_| svars.activity_time_totals.len() > 1)
+                                .map(|(activity, dur)| html! {
+                                    <tr>
+                                        <td class="ps-4">{format!("{activity} Minutes")}</td>
+                                        <td>{Decimal::from_f64(dur.as_secs_f64()/60.0).unwrap().round_dp(2).to_string()}</td>
+                                    </tr>
+                                })
+                                .collect::<Html>()
+                        }
+                        <tr>
+                            <td>{"Weighted Delivery Minutes"}</td>
+                            <td>{svars.get_weighted_delivery_minutes().round_dp(2).to_string()}</td>
+                        </tr>
                         <tr>
                             <td>{"Allocation Per Delivery Minute"}</td>
                             <td>{decimal_to_money_string(&props.dvars.delivery_earnings_per_minute)}</td>
@@ -514,10 +550,15 @@ fn currency_widget(props: &CurrencyWidgetProps) -> Html {
 struct AllocationsFormProps {
     dvars: FrCloseoutDynamicVars,
     svarsmap: FrClosureStaticData,
+    numbagsordered: Option<u64>,
     oninput: Callback<InputEvent>,
 }
 #[component(AllocationsForm)]
 fn allocations_form(props: &AllocationsFormProps) -> Html {
+    let num_bags_sold = props
+        .svarsmap
+        .get("TROOP_TOTALS")
+        .map_or(0, |v| v.num_bags_sold);
     html! {
         <form>
             <div class="row m-1">
@@ -533,6 +574,19 @@ fn allocations_form(props: &AllocationsFormProps) -> Html {
                                 label="Amount Paid for Mulch"
                                 oninput={props.oninput.clone()}
                 />
+                if let Some(num_bags_ordered) = props.numbagsordered.filter(|v| *v != 0) {
+                    <small class="text-muted">
+                        {format!("Ordered {num_bags_ordered} bags from the supplier for {num_bags_sold} sold")}
+                        if Decimal::ZERO != props.dvars.mulch_cost {
+                            {format!(" ({} per bag ordered)",
+                                to_money_str(Some(props.dvars.mulch_cost
+                                    .checked_div(num_bags_ordered.into())
+                                    .unwrap_or_default()
+                                    .round_dp(2)
+                                    .to_string())))}
+                        }
+                    </small>
+                }
             </div>
 
             <div class="container-fluid d-flex flex-row flex-wrap" id="fundsReleaseTables">
@@ -545,6 +599,76 @@ fn allocations_form(props: &AllocationsFormProps) -> Html {
     }
 }
 
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[derive(Properties, PartialEq)]
+struct ActivityWeightsFormProps {
+    weights: BTreeMap<TimecardActivity, Decimal>,
+    onchange: Callback<BTreeMap<TimecardActivity, Decimal>>,
+}
+#[component(ActivityWeightsForm)]
+fn activity_weights_form(props: &ActivityWeightsFormProps) -> Html {
+    let get_weight =
+        |activity: &TimecardActivity| props.weights.get(activity).copied().unwrap_or(Decimal::ONE);
+
+    let on_weight_change = {
+        let weights = props.weights.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |evt: Event| {
+            let input_elm = evt.target_unchecked_into::<HtmlInputElement>();
+            let Some(activity) = input_elm
+                .dataset()
+                .get("activity")
+                .and_then(|v| TimecardActivity::from_str(&v).ok())
+            else {
+                return;
+            };
+            match Decimal::from_str(input_elm.value().trim()) {
+                Ok(weight) if weight > Decimal::ZERO => {
+                    let mut weights = weights.clone();
+                    weights.insert(activity, weight);
+                    onchange.emit(weights);
+                }
+                _ => {
+                    gloo::dialogs::alert("Weights must be a number greater than 0");
+                    input_elm.set_value(
+                        &weights
+                            .get(&activity)
+                            .copied()
+                            .unwrap_or(Decimal::ONE)
+                            .to_string(),
+                    );
+                }
+            }
+        })
+    };
+
+    html! {
+        <div class="row m-1 g-2">
+            <div class="col-12">
+                <small class="text-muted">
+                    {"Timecard activity weights. A minute of an activity earns its weight times the allocation per delivery minute."}
+                </small>
+            </div>
+            {
+                TimecardActivity::all().iter().map(|activity| {
+                    let id = format!("formActivityWeight{}", activity.as_api_str());
+                    html! {
+                        <div class="form-floating col-md-4">
+                            <input type="number" min="0.05" step="0.05" class="form-control" id={id.clone()}
+                                   data-activity={activity.as_api_str()}
+                                   value={get_weight(activity).to_string()}
+                                   disabled={is_fundraiser_finalized()}
+                                   onchange={on_weight_change.clone()}/>
+                            <label class="ms-2" for={id}>{format!("{activity} Weight")}</label>
+                        </div>
+                    }
+                }).collect::<Html>()
+            }
+        </div>
+    }
+}
+
 /////////////////////////////////////////////////
 /////////////////////////////////////////////////
 #[component(StaticDataLoadingSpinny)]
@@ -588,6 +712,62 @@ pub fn closeout_fundraiser_page() -> Html {
     });
     let scout_report_list: UseStateHandle<Vec<FrCloseoutAllocationVals>> = use_state_eq(Vec::new);
     let fr_closure_static_data: UseStateHandle<Option<FrClosureStaticData>> = use_state_eq(|| None);
+    let num_bags_ordered: UseStateHandle<Option<u64>> = use_state_eq(|| None);
+    let num_timecard_anomalies: UseStateHandle<usize> = use_state_eq(|| 0);
+    let num_open_clock_ins: UseStateHandle<usize> = use_state_eq(|| 0);
+    let activity_weights = use_state_eq(|| get_fr_config().timecard_activity_weights.clone());
+
+    let on_activity_weights_change = {
+        let activity_weights = activity_weights.clone();
+        Callback::from(move |weights: BTreeMap<TimecardActivity, Decimal>| {
+            let activity_weights = activity_weights.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match set_timecard_activity_weights(weights.clone()).await {
+                    // The allocations get recalculated with the new weights on the next render
+                    Ok(_) => activity_weights.set(weights),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to save activity weights: {err:#?}"));
+                    }
+                }
+            });
+        })
+    };
+
+    {
+        let num_timecard_anomalies = num_timecard_anomalies.clone();
+        let num_open_clock_ins = num_open_clock_ins.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                match get_timecard_anomalies().await {
+                    Ok(v) => {
+                        num_open_clock_ins.set(
+                            v.iter()
+                                .filter(|v| TimecardAnomalyKind::MissingTimeOut == v.kind)
+                                .count(),
+                        );
+                        num_timecard_anomalies.set(v.len());
+                    }
+                    Err(err) => error!("Failed to check the timecards: {err:#?}"),
+                }
+            });
+            || {}
+        });
+    }
+
+    {
+        let num_bags_ordered = num_bags_ordered.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                match get_total_ordered_from_supplier("bags").await {
+                    Ok(v) => num_bags_ordered.set(Some(v)),
+                    Err(err) => {
+                        error!("Failed to get the bags ordered from the supplier: {err:#?}")
+                    }
+                }
+            });
+            || {}
+        });
+    }
 
     let on_download_summary = {
         let dvars = dvars.clone();
@@ -626,8 +806,12 @@ pub fn closeout_fundraiser_page() -> Html {
             let scout_report_list = scout_report_list.clone();
             wasm_bindgen_futures::spawn_local(async move {
                 info!("on_release_funds_form_submission");
-                set_fr_closeout_data(&dvars.clone(), &scout_report_list).await;
-                gloo::dialogs::alert("Submitted");
+                match set_fr_closeout_data(&dvars.clone(), &scout_report_list).await {
+                    Ok(_) => gloo::dialogs::alert("Submitted"),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to submit closeout data: {err:#?}"))
+                    }
+                }
             });
         })
     };
@@ -745,6 +929,21 @@ pub fn closeout_fundraiser_page() -> Html {
                 <div class="container-fluid vh-100 d-flex flex-column align-items-center" style="max-width: 95%;">
                     <h4>{"Funds Release Page"}</h4>
 
+                    if 0 != *num_timecard_anomalies {
+                        <div class="alert alert-warning my-1" role="alert">
+                            {format!(
+                                "There are {} unresolved timecard anomalies. Delivery time allocations may be off until they are fixed. See the Timecard Anomalies report.",
+                                *num_timecard_anomalies)}
+                        </div>
+                    }
+                    if 0 != *num_open_clock_ins {
+                        <div class="alert alert-warning my-1" role="alert">
+                            {format!(
+                                "{} timecards are still clocked in. Their time isn't counted until a time out is entered on the Timecards page.",
+                                *num_open_clock_ins)}
+                        </div>
+                    }
+
                     <div class="card my-1">
                         <h5 class="card-header">
                             {"Allocation Calculations"}
@@ -759,6 +958,11 @@ pub fn closeout_fundraiser_page() -> Html {
                                 oninput={on_allocation_form_inputs_change.clone()}
                                 dvars={(*dvars).clone()}
                                 svarsmap={(*fr_closure_static_data).as_ref().unwrap().clone()}
+                                numbagsordered={*num_bags_ordered}
+                            />
+                            <ActivityWeightsForm
+                                weights={(*activity_weights).clone()}
+                                onchange={on_activity_weights_change}
                             />
                         </div>
                     </div> // End of Card
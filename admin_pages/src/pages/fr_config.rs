@@ -1,5 +1,8 @@
+use crate::components::admin_config_audit_log::*;
 use crate::components::admin_config_deliveries::*;
+use crate::components::admin_config_distribution_points::*;
 use crate::components::admin_config_neighborhoods::*;
+use crate::components::admin_config_overrides::*;
 use crate::components::admin_config_product_costs::*;
 use crate::components::admin_config_users::*;
 use tracing::info;
@@ -136,6 +139,18 @@ pub fn fr_config() -> Html {
                                     {"Neighborhoods"}
                             </button>
                         </li>
+                        <li class="nav-item" role="presentation">
+                            <button
+                                class="nav-link"
+                                id="dist-points-tab"
+                                data-bs-toggle="tab"
+                                data-bs-target="#dist-points-tab-pane"
+                                type="button" role="tab"
+                                aria-controls="dist-points-tab-pane"
+                                aria-selected="false">
+                                    {"Distribution Points"}
+                            </button>
+                        </li>
                         <li class="nav-item" role="presentation">
                             <button
                                 class="nav-link"
@@ -148,6 +163,30 @@ pub fn fr_config() -> Html {
                                     {"Users"}
                             </button>
                         </li>
+                        <li class="nav-item" role="presentation">
+                            <button
+                                class="nav-link"
+                                id="overrides-tab"
+                                data-bs-toggle="tab"
+                                data-bs-target="#overrides-tab-pane"
+                                type="button" role="tab"
+                                aria-controls="overrides-tab-pane"
+                                aria-selected="false">
+                                    {"Overrides"}
+                            </button>
+                        </li>
+                        <li class="nav-item" role="presentation">
+                            <button
+                                class="nav-link"
+                                id="audit-tab"
+                                data-bs-toggle="tab"
+                                data-bs-target="#audit-tab-pane"
+                                type="button" role="tab"
+                                aria-controls="audit-tab-pane"
+                                aria-selected="false">
+                                    {"Audit Log"}
+                            </button>
+                        </li>
                         <li class="nav-item" role="presentation">
                             <button
                                 class="nav-link"
@@ -183,6 +222,13 @@ pub fn fr_config() -> Html {
                                 </div>
                             </div>
                         </div>
+                        <div class="tab-pane fade" id="dist-points-tab-pane" role="tabpanel" aria-labelledby="dist-points-tab" tabindex="0">
+                            <div class="row mt-2">
+                                <div class="col-xs-1 d-flex justify-content-center">
+                                    <DistributionPointsUl/>
+                                </div>
+                            </div>
+                        </div>
                         <div class="tab-pane fade" id="users-tab-pane" role="tabpanel" aria-labelledby="users-tab" tabindex="0">
                             <div class="row mt-2">
                                 <div class="col-xs-1 d-flex justify-content-center">
@@ -190,6 +236,20 @@ pub fn fr_config() -> Html {
                                 </div>
                             </div>
                         </div>
+                        <div class="tab-pane fade" id="overrides-tab-pane" role="tabpanel" aria-labelledby="overrides-tab" tabindex="0">
+                            <div class="row mt-2">
+                                <div class="col-xs-1 d-flex justify-content-center">
+                                    <CutoffOverridesUl/>
+                                </div>
+                            </div>
+                        </div>
+                        <div class="tab-pane fade" id="audit-tab-pane" role="tabpanel" aria-labelledby="audit-tab" tabindex="0">
+                            <div class="row mt-2">
+                                <div class="col-xs-1 d-flex justify-content-center">
+                                    <ConfigAuditLog/>
+                                </div>
+                            </div>
+                        </div>
                         <div class="tab-pane fade" id="reset-tab-pane" role="tabpanel" aria-labelledby="reset-tab" tabindex="0">
                             <div class="row mt-2">
                                 <div class="col-xs-1 d-flex justify-content-center">
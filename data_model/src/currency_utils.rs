@@ -43,12 +43,9 @@ pub fn parse_money_str_as_decimal(input: &str) -> Option<Decimal> {
     if input.is_empty() {
         return Some(Decimal::ZERO);
     }
-    Some(
-        Money::from_str(input, iso::USD)
-            .unwrap()
-            .amount()
-            .to_owned(),
-    )
+    Money::from_str(input, iso::USD)
+        .ok()
+        .map(|v| v.amount().to_owned())
 }
 
 pub fn on_money_input_filter(input: Option<&String>) -> String {
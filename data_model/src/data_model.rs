@@ -1,8 +1,10 @@
 use super::{
+    GeoPoint, SpreaderBags, TimecardActivity, does_active_user_have_cutoff_extension,
     get_active_user,
     gql_utils::{GraphQlReq, make_gql_request},
 };
-use chrono::prelude::*;
+use chrono::{Days, prelude::*};
+use chrono_tz::Tz;
 use gloo::storage::{LocalStorage, Storage};
 use rust_decimal::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -27,12 +29,36 @@ static CONFIG_GQL: &str = r#"
     kind
     isLocked
     lastModifiedTime
+    supplierOveragePercent
+    maxSpreadingJobClaims
+    truckCapacity
+    timecardActivityWeights {
+      activity
+      weight
+    }
     neighborhoods {
       name
       city
       zipcode
       isVisible
       distributionPoint
+      distributionPointId
+    }
+    distributionPoints {
+      id
+      name
+      address
+      latitude
+      longitude
+      contactName
+      contactPhone
+      capacity
+    }
+    groupAttributes {
+      group
+      isTimecardEligible
+      isAllocationEligible
+      isHiddenFromTopSellers
     }
     mulchDeliveryConfigs {
       id
@@ -40,7 +66,9 @@ static CONFIG_GQL: &str = r#"
       date
       dateAsEpoch
       newOrderCutoffDate
-      newOrderCutoffDateAsEpoch
+      maxBags
+      isWaitlistEnabled
+      isLocked
     }
     products {
       id
@@ -75,7 +103,7 @@ static CONFIG_GQL: &str = r#"
 
 // Internal Schema version for stored config data.  This gives me a way
 //   to force update reload of config even if last_modified_time hasn't changed
-static LOCAL_STORE_SCHEMA_VER: u32 = 20501;
+static LOCAL_STORE_SCHEMA_VER: u32 = 20509;
 
 pub type UserMapType = BTreeMap<String, UserInfo>;
 type ProductMapType = BTreeMap<String, ProductInfo>;
@@ -83,10 +111,16 @@ type DeliveryMapType = BTreeMap<u32, DeliveryInfo>;
 
 static NEIGHBORHOODS: LazyLock<RwLock<Option<Arc<Vec<Neighborhood>>>>> =
     LazyLock::new(|| RwLock::new(None));
+static DISTRIBUTION_POINTS: LazyLock<RwLock<Arc<Vec<DistributionPoint>>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(Vec::new())));
+static GROUP_ATTRIBUTES: LazyLock<RwLock<Arc<Vec<GroupAttributes>>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(Vec::new())));
 static PRODUCTS: LazyLock<RwLock<Option<Arc<ProductMapType>>>> =
     LazyLock::new(|| RwLock::new(None));
 static DELIVERIES: LazyLock<RwLock<Option<Arc<DeliveryMapType>>>> =
     LazyLock::new(|| RwLock::new(None));
+static DELIVERY_BAG_COUNTS: LazyLock<RwLock<BTreeMap<u32, u32>>> =
+    LazyLock::new(|| RwLock::new(BTreeMap::new()));
 static FRCONFIG: LazyLock<RwLock<Option<Arc<FrConfig>>>> = LazyLock::new(|| RwLock::new(None));
 // map<uid,(name, group)>
 static USER_MAP: LazyLock<RwLock<Arc<UserMapType>>> =
@@ -102,12 +136,31 @@ pub struct UserInfo {
 }
 
 ////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
 pub struct FrConfig {
     pub kind: String,
     pub description: String,
     // pub last_modified_time: String,
     pub is_locked: bool,
     pub is_finalized: bool,
+    // Extra added on top of what was sold when ordering from the supplier
+    pub supplier_overage_percent: u32,
+    // How much a minute of each activity counts towards delivery earnings at closeout
+    pub timecard_activity_weights: BTreeMap<TimecardActivity, Decimal>,
+    // How many spreading jobs a scout can have claimed on the job board at once
+    pub max_spreading_job_claims: u32,
+    // How many bags fit on a truck when planning delivery routes
+    pub truck_capacity: u64,
+}
+
+impl FrConfig {
+    /// Activities without a weight count the same as delivery time
+    pub fn get_activity_weight(&self, activity: &TimecardActivity) -> Decimal {
+        self.timecard_activity_weights
+            .get(activity)
+            .copied()
+            .unwrap_or(Decimal::ONE)
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////
@@ -118,12 +171,19 @@ pub struct DeliveryInfo {
     delivery_date_str: String,
     new_order_cutoff_date_str: String,
     new_order_cutoff_date: Option<DateTime<Utc>>,
+    // The fundraiser's timezone. Cutoffs are in this zone no matter where the browser is
+    timezone: Tz,
+    // Max number of bags the supplier can deliver on this date. None means no limit
+    max_bags: Option<u32>,
+    is_waitlist_enabled: bool,
+    // Locked deliveries can only have their orders changed by an admin
+    is_locked: bool,
 }
 impl DeliveryInfo {
     pub fn new(
         delivery_date_raw_api_str: String,
         new_order_cutoff_raw_api_str: String,
-        new_order_cutoff_epoch: u32,
+        timezone: &str,
     ) -> DeliveryInfo {
         let formatted_delivery_date_str = {
             let nd = NaiveDate::parse_from_str(&delivery_date_raw_api_str, "%m/%d/%Y").unwrap();
@@ -131,45 +191,48 @@ impl DeliveryInfo {
             //    .unwrap()
             nd.format("%Y-%m-%d").to_string()
         };
-        let formatted_cutoff_date_str = {
-            let nd = NaiveDate::parse_from_str(&new_order_cutoff_raw_api_str, "%m/%d/%Y").unwrap();
-            // let Utc.with_ymd_and_hms(nd.year(), nd.month(), nd.day(), 0, 0, 0)
-            //     .unwrap()
-            nd.format("%Y-%m-%d").to_string()
-        };
-
-        let cutoff_date = Utc.timestamp_opt(new_order_cutoff_epoch as i64, 0).unwrap();
+        let cutoff_nd =
+            NaiveDate::parse_from_str(&new_order_cutoff_raw_api_str, "%m/%d/%Y").unwrap();
+        let formatted_cutoff_date_str = cutoff_nd.format("%Y-%m-%d").to_string();
+        let timezone = parse_timezone(timezone);
 
         DeliveryInfo {
             delivery_date_api_str: delivery_date_raw_api_str,
             new_order_cutoff_date_api_str: new_order_cutoff_raw_api_str,
             delivery_date_str: formatted_delivery_date_str,
             new_order_cutoff_date_str: formatted_cutoff_date_str,
-            new_order_cutoff_date: Some(cutoff_date),
+            new_order_cutoff_date: gen_cutoff_date(&cutoff_nd, &timezone),
+            timezone,
+            max_bags: None,
+            is_waitlist_enabled: false,
+            is_locked: false,
         }
     }
 
     pub fn new_from_admin(
         delivery_date_raw_str: String,
         order_cutoff_raw_str: String,
+        timezone: &str,
     ) -> DeliveryInfo {
         let formatted_delivery_date_api_str = {
             let nd = NaiveDate::parse_from_str(&delivery_date_raw_str, "%Y-%m-%d").unwrap();
             // Utc.with_ymd_and_hms(nd.year(), nd.month(), nd.day(), 0, 0, 0).unwrap()
             nd.format("%m/%d/%Y").to_string()
         };
-        let formatted_new_order_cutoff_date_api_str = {
-            let nd = NaiveDate::parse_from_str(&order_cutoff_raw_str, "%Y-%m-%d").unwrap();
-            // Utc.with_ymd_and_hms(nd.year(), nd.month(), nd.day(), 0, 0, 0).unwrap()
-            nd.format("%m/%d/%Y").to_string()
-        };
+        let cutoff_nd = NaiveDate::parse_from_str(&order_cutoff_raw_str, "%Y-%m-%d").unwrap();
+        let formatted_new_order_cutoff_date_api_str = cutoff_nd.format("%m/%d/%Y").to_string();
+        let timezone = parse_timezone(timezone);
 
         DeliveryInfo {
             delivery_date_api_str: formatted_delivery_date_api_str,
             new_order_cutoff_date_api_str: formatted_new_order_cutoff_date_api_str,
             delivery_date_str: delivery_date_raw_str,
             new_order_cutoff_date_str: order_cutoff_raw_str,
-            new_order_cutoff_date: None,
+            new_order_cutoff_date: gen_cutoff_date(&cutoff_nd, &timezone),
+            timezone,
+            max_bags: None,
+            is_waitlist_enabled: false,
+            is_locked: false,
         }
     }
 
@@ -193,13 +256,165 @@ impl DeliveryInfo {
         self.new_order_cutoff_date_api_str.clone()
     }
 
+    /// The cutoff shown in the fundraiser's timezone instead of the browser's
+    pub fn get_new_order_cutoff_display_str(&self) -> String {
+        self.new_order_cutoff_date
+            .map_or(self.new_order_cutoff_date_str.clone(), |v| {
+                v.with_timezone(&self.timezone)
+                    .format("%m/%d/%Y %I:%M %p %Z")
+                    .to_string()
+            })
+    }
+
+    pub fn get_timezone_name(&self) -> &'static str {
+        self.timezone.name()
+    }
+
+    pub fn set_timezone(&mut self, timezone: &str) {
+        self.timezone = parse_timezone(timezone);
+        if let Ok(cutoff_nd) =
+            NaiveDate::parse_from_str(&self.new_order_cutoff_date_api_str, "%m/%d/%Y")
+        {
+            self.new_order_cutoff_date = gen_cutoff_date(&cutoff_nd, &self.timezone);
+        }
+    }
+
     pub fn can_take_orders(&self) -> bool {
         self.new_order_cutoff_date.is_none_or(|v| v.ge(&Utc::now()))
     }
+
+    pub fn set_capacity(&mut self, max_bags: Option<u32>, is_waitlist_enabled: bool) {
+        self.max_bags = max_bags;
+        self.is_waitlist_enabled = is_waitlist_enabled;
+    }
+
+    pub fn get_max_bags(&self) -> Option<u32> {
+        self.max_bags
+    }
+
+    pub fn is_waitlist_enabled(&self) -> bool {
+        self.is_waitlist_enabled
+    }
+
+    pub fn set_locked(&mut self, is_locked: bool) {
+        self.is_locked = is_locked;
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.is_locked
+    }
+
+    /// The current time in the fundraiser's timezone so clock ins are recorded in local time
+    pub fn get_local_now(&self) -> DateTime<Tz> {
+        Utc::now().with_timezone(&self.timezone)
+    }
+
+    pub fn has_delivery_date_passed(&self) -> bool {
+        NaiveDate::parse_from_str(&self.delivery_date_str, "%Y-%m-%d")
+            .is_ok_and(|v| v < self.get_local_now().date_naive())
+    }
+
+    pub fn is_delivery_today(&self) -> bool {
+        NaiveDate::parse_from_str(&self.delivery_date_str, "%Y-%m-%d")
+            .is_ok_and(|v| v == self.get_local_now().date_naive())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+// Used when the config doesn't say how much extra to order from the supplier
+pub static DEFAULT_SUPPLIER_OVERAGE_PERCENT: u32 = 5;
+
+////////////////////////////////////////////////////////////////////////////
+// Used when the config doesn't limit how many spreading jobs a scout can claim
+pub static DEFAULT_MAX_SPREADING_JOB_CLAIMS: u32 = 3;
+
+////////////////////////////////////////////////////////////////////////////
+// Used when the config doesn't say how many bags fit on a delivery truck
+pub static DEFAULT_TRUCK_CAPACITY: u64 = 150;
+
+////////////////////////////////////////////////////////////////////////////
+// Used when the config doesn't have a timezone or has one that isn't recognized
+pub static DEFAULT_TIMEZONE: &str = "America/Chicago";
+
+// Zones offered to the admin when picking the fundraiser's timezone
+pub static FUNDRAISER_TIMEZONES: &[&str] = &[
+    "America/New_York",
+    "America/Chicago",
+    "America/Denver",
+    "America/Phoenix",
+    "America/Los_Angeles",
+    "America/Anchorage",
+    "Pacific/Honolulu",
+];
+
+////////////////////////////////////////////////////////////////////////////
+fn parse_timezone(timezone: &str) -> Tz {
+    timezone.parse::<Tz>().unwrap_or_else(|_| {
+        warn!("Unrecognized timezone: {timezone} using {DEFAULT_TIMEZONE}");
+        DEFAULT_TIMEZONE.parse::<Tz>().unwrap()
+    })
+}
+
+////////////////////////////////////////////////////////////////////////////
+// Orders are allowed through the end of the cutoff day in the fundraiser's timezone
+fn gen_cutoff_date(cutoff_date: &NaiveDate, timezone: &Tz) -> Option<DateTime<Utc>> {
+    cutoff_date
+        .and_hms_opt(23, 59, 59)
+        .and_then(|v| timezone.from_local_datetime(&v).latest())
+        .map(|v| v.with_timezone(&Utc))
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// The timezone the fundraiser is run in. All deliveries share the same zone.
+pub fn get_fundraiser_timezone() -> String {
+    get_deliveries()
+        .values()
+        .next()
+        .map_or(DEFAULT_TIMEZONE.to_string(), |v| {
+            v.get_timezone_name().to_string()
+        })
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Today's date where the fundraiser is run no matter where the browser is
+pub fn get_fundraiser_today() -> NaiveDate {
+    Utc::now()
+        .with_timezone(&parse_timezone(&get_fundraiser_timezone()))
+        .date_naive()
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Parses a date/time entered by an admin (as from a datetime-local input) as being in the
+/// fundraiser's timezone
+pub fn parse_fundraiser_datetime(value: &str) -> Option<DateTime<Utc>> {
+    let timezone = parse_timezone(&get_fundraiser_timezone());
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M")
+        .ok()
+        .and_then(|v| timezone.from_local_datetime(&v).latest())
+        .map(|v| v.with_timezone(&Utc))
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Formats an rfc3339 time in the fundraiser's timezone
+pub fn to_fundraiser_datetime_str(value: &str) -> String {
+    format_fundraiser_datetime(value, "%m/%d/%Y %I:%M %p %Z")
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Formats a stored rfc3339 timestamp in the fundraiser's timezone so everyone sees the
+/// same time no matter where their browser is
+pub fn format_fundraiser_datetime(value: &str, fmt: &str) -> String {
+    let timezone = parse_timezone(&get_fundraiser_timezone());
+    DateTime::parse_from_rfc3339(value).map_or(value.to_string(), |v| {
+        v.with_timezone(&timezone).format(fmt).to_string()
+    })
 }
 
 ////////////////////////////////////////////////////////////////////////////
 pub fn are_sales_still_allowed() -> bool {
+    if does_active_user_have_cutoff_extension() {
+        return true;
+    }
     let deliveries = get_deliveries();
     let mut are_any_still_active = false;
     for delivery_info in deliveries.values() {
@@ -224,8 +439,49 @@ pub struct Neighborhood {
     pub city: Option<String>,
     #[serde(alias = "isVisible")]
     pub is_visible: bool,
+    // Name of the distribution point. Kept in sync with the id for anything still reading it
     #[serde(alias = "distributionPoint")]
     pub distribution_point: String,
+    #[serde(alias = "distributionPointId")]
+    pub distribution_point_id: Option<String>,
+}
+
+impl Neighborhood {
+    pub fn get_distribution_point(&self) -> Option<DistributionPoint> {
+        self.distribution_point_id
+            .as_ref()
+            .and_then(|id| get_distribution_point(id))
+    }
+
+    /// Neighborhoods from before distribution points were configured only have the name
+    pub fn get_distribution_point_name(&self) -> String {
+        self.get_distribution_point()
+            .map_or(self.distribution_point.clone(), |v| v.name)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+pub struct DistributionPoint {
+    pub id: String,
+    pub name: String,
+    pub address: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    #[serde(alias = "contactName")]
+    pub contact_name: Option<String>,
+    #[serde(alias = "contactPhone")]
+    pub contact_phone: Option<String>,
+    // Max number of bags the point can hold for a single delivery
+    pub capacity: Option<u32>,
+}
+
+impl DistributionPoint {
+    pub fn get_location(&self) -> Option<GeoPoint> {
+        self.latitude
+            .zip(self.longitude)
+            .map(|(lat, lng)| GeoPoint { lat, lng })
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////
@@ -261,7 +517,19 @@ struct FrConfigApi {
     last_modified_time: String,
     #[serde(alias = "isLocked")]
     is_locked: bool,
+    #[serde(alias = "supplierOveragePercent")]
+    supplier_overage_percent: Option<u32>,
+    #[serde(alias = "maxSpreadingJobClaims")]
+    max_spreading_job_claims: Option<u32>,
+    #[serde(alias = "truckCapacity")]
+    truck_capacity: Option<u64>,
+    #[serde(alias = "timecardActivityWeights", default)]
+    timecard_activity_weights: Vec<TimecardActivityWeightApi>,
     neighborhoods: Vec<Neighborhood>,
+    #[serde(alias = "distributionPoints", default)]
+    distribution_points: Vec<DistributionPoint>,
+    #[serde(alias = "groupAttributes")]
+    group_attributes: Option<Vec<GroupAttributes>>,
     products: Vec<ProductsApi>,
     #[serde(alias = "mulchDeliveryConfigs")]
     mulch_delivery_configs: Vec<MulchDeliveryConfigApi>,
@@ -270,6 +538,13 @@ struct FrConfigApi {
     users: Vec<UsersConfigApi>,
 }
 
+////////////////////////////////////////////////////////////////////////////
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct TimecardActivityWeightApi {
+    activity: TimecardActivity,
+    weight: String,
+}
+
 ////////////////////////////////////////////////////////////////////////////
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct UsersConfigApi {
@@ -330,8 +605,12 @@ struct MulchDeliveryConfigApi {
     delivery_date_as_epoch: u32,
     #[serde(rename = "newOrderCutoffDate")]
     new_order_cutoff_date: String,
-    #[serde(rename = "newOrderCutoffDateAsEpoch")]
-    new_order_cutoff_date_as_epoch: u32,
+    #[serde(rename = "maxBags")]
+    max_bags: Option<u32>,
+    #[serde(rename = "isWaitlistEnabled")]
+    is_waitlist_enabled: Option<bool>,
+    #[serde(rename = "isLocked")]
+    is_locked: Option<bool>,
 }
 
 ////////////////////////////////////////////////////////////////////////////
@@ -352,16 +631,42 @@ fn process_config_data(config: FrConfigApi) {
         // last_modified_time: config.last_modified_time,
         is_locked: config.is_locked,
         is_finalized: is_config_finalized,
+        supplier_overage_percent: config
+            .supplier_overage_percent
+            .unwrap_or(DEFAULT_SUPPLIER_OVERAGE_PERCENT),
+        timecard_activity_weights: config
+            .timecard_activity_weights
+            .iter()
+            .filter_map(|v| Decimal::from_str(&v.weight).ok().map(|w| (v.activity, w)))
+            .collect(),
+        max_spreading_job_claims: config
+            .max_spreading_job_claims
+            .unwrap_or(DEFAULT_MAX_SPREADING_JOB_CLAIMS),
+        truck_capacity: config
+            .truck_capacity
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_TRUCK_CAPACITY),
     }));
     *NEIGHBORHOODS.write().unwrap() = Some(Arc::new(config.neighborhoods));
+    *DISTRIBUTION_POINTS.write().unwrap() = Arc::new(config.distribution_points);
+    *GROUP_ATTRIBUTES.write().unwrap() = Arc::new(
+        config
+            .group_attributes
+            .unwrap_or_else(get_legacy_group_attributes),
+    );
 
     let mut deliveries = BTreeMap::new();
     for delivery in config.mulch_delivery_configs {
-        let delivery_info = DeliveryInfo::new(
+        let mut delivery_info = DeliveryInfo::new(
             delivery.delivery_date.clone(),
             delivery.new_order_cutoff_date.clone(),
-            delivery.new_order_cutoff_date_as_epoch,
+            &delivery.timezone,
+        );
+        delivery_info.set_capacity(
+            delivery.max_bags,
+            delivery.is_waitlist_enabled.unwrap_or(false),
         );
+        delivery_info.set_locked(delivery.is_locked.unwrap_or(false));
         deliveries.insert(delivery.id, delivery_info);
     }
     *DELIVERIES.write().unwrap() = Some(Arc::new(deliveries));
@@ -526,9 +831,15 @@ pub async fn set_products(
 
     info!("Set Product Mutation:\n{}", &query);
     let req = GraphQlReq::new(query);
-    make_gql_request::<serde_json::Value>(&req).await.map(|_| {
-        *PRODUCTS.write().unwrap() = Some(Arc::new(products));
-    })
+    make_gql_request::<serde_json::Value>(&req).await?;
+
+    let changes = gen_config_changes(
+        &to_products_audit_map(&get_products()),
+        &to_products_audit_map(&products),
+    );
+    *PRODUCTS.write().unwrap() = Some(Arc::new(products));
+    record_config_audit_event("Products", changes).await;
+    Ok(())
 }
 
 ////////////////////////////////////////////////////////////////////////////
@@ -549,14 +860,18 @@ pub async fn set_deliveries(
         .iter()
         .map(|(k, v)| {
             format!(
-                "\t\t{{\n{}\n{}\n{}\n{}\n\t\t}}",
+                "\t\t{{\n{}\n{}\n{}\n{}\n{}{}\n{}\n\t\t}}",
                 format_args!("\t\t\tid: {},", k),
-                "\t\t\ttimezone: \"America/Chicago\",", //Hardcoded for now
+                format_args!("\t\t\ttimezone: \"{}\",", v.get_timezone_name()),
                 format_args!("\t\t\tdate: \"{}\",", v.get_api_delivery_date_str()),
                 format_args!(
-                    "\t\t\tnewOrderCutoffDate: \"{}\"",
+                    "\t\t\tnewOrderCutoffDate: \"{}\",",
                     v.get_api_new_order_cutoff_date_str()
-                )
+                ),
+                v.get_max_bags()
+                    .map_or("".to_string(), |v| format!("\t\t\tmaxBags: {v},\n")),
+                format_args!("\t\t\tisWaitlistEnabled: {},", v.is_waitlist_enabled()),
+                format_args!("\t\t\tisLocked: {}", v.is_locked())
             )
         })
         .collect::<Vec<String>>()
@@ -566,9 +881,15 @@ pub async fn set_deliveries(
 
     // info!("Set Delivery Mutation:\n{}", &query);
     let req = GraphQlReq::new(query);
-    make_gql_request::<serde_json::Value>(&req).await.map(|_| {
-        *DELIVERIES.write().unwrap() = Some(Arc::new(deliveries));
-    })
+    make_gql_request::<serde_json::Value>(&req).await?;
+
+    let changes = gen_config_changes(
+        &to_deliveries_audit_map(&get_deliveries()),
+        &to_deliveries_audit_map(&deliveries),
+    );
+    *DELIVERIES.write().unwrap() = Some(Arc::new(deliveries));
+    record_config_audit_event("Deliveries", changes).await;
+    Ok(())
 }
 
 ////////////////////////////////////////////////////////////////////////////
@@ -580,6 +901,59 @@ pub fn is_valid_delivery_id(id: u32) -> bool {
     get_deliveries().contains_key(&id)
 }
 
+////////////////////////////////////////////////////////////////////////////
+pub fn is_delivery_locked(id: u32) -> bool {
+    get_deliveries().get(&id).is_some_and(|v| v.is_locked())
+}
+
+////////////////////////////////////////////////////////////////////////////
+static DELIVERY_BAG_COUNTS_GQL: &str = r"
+{
+  mulchDeliveryBagCounts {
+    deliveryId
+    numBags
+  }
+}";
+
+////////////////////////////////////////////////////////////////////////////
+/// Loads the number of bags already ordered (not counting the waitlist) for each delivery
+pub async fn load_delivery_bag_counts() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(Deserialize)]
+    struct DeliveryBagCount {
+        #[serde(rename = "deliveryId")]
+        delivery_id: u32,
+        #[serde(rename = "numBags")]
+        num_bags: u32,
+    }
+    #[derive(Deserialize)]
+    struct RespBagCounts {
+        #[serde(rename = "mulchDeliveryBagCounts")]
+        bag_counts: Vec<DeliveryBagCount>,
+    }
+
+    let req = GraphQlReq::new(DELIVERY_BAG_COUNTS_GQL);
+    let resp = make_gql_request::<RespBagCounts>(&req).await?;
+    *DELIVERY_BAG_COUNTS.write().unwrap() = resp
+        .bag_counts
+        .into_iter()
+        .map(|v| (v.delivery_id, v.num_bags))
+        .collect();
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Returns how many more bags can go on a delivery or None if the delivery has no limit
+pub fn get_delivery_bags_remaining(delivery_id: u32) -> Option<i64> {
+    let max_bags = get_deliveries().get(&delivery_id)?.get_max_bags()?;
+    let num_ordered = DELIVERY_BAG_COUNTS
+        .read()
+        .unwrap()
+        .get(&delivery_id)
+        .copied()
+        .unwrap_or(0);
+    Some(max_bags as i64 - num_ordered as i64)
+}
+
 ////////////////////////////////////////////////////////////////////////////
 pub fn get_delivery_date(delivery_id: &u32) -> String {
     get_deliveries()
@@ -604,9 +978,17 @@ pub async fn update_neighborhoods(
         .iter()
         .map(|v| {
             format!(
-                "\t\t{{\n{},\n{},{}{}\n{}\n\t\t}}",
+                "\t\t{{\n{},\n{},{}{}{}\n{}\n\t\t}}",
                 format_args!("\t\t\tname: \"{}\"", v.name),
-                format_args!("\t\t\tdistributionPoint: \"{}\"", v.distribution_point),
+                format_args!(
+                    "\t\t\tdistributionPoint: \"{}\"",
+                    v.get_distribution_point_name()
+                ),
+                v.distribution_point_id
+                    .as_ref()
+                    .map_or("".to_string(), |v| format!(
+                        "\n\t\t\tdistributionPointId: \"{v}\","
+                    )),
                 "***CITY***",
                 "***ZIP***",
                 format_args!("\t\t\tisVisible: {}", v.is_visible)
@@ -637,6 +1019,19 @@ pub async fn update_neighborhoods(
         .await
         .map(|_| ())?;
 
+    let changes = {
+        let old_hoods = get_neighborhoods();
+        let old_hoods = old_hoods
+            .iter()
+            .filter(|v| hoods.iter().any(|h| h.name == v.name))
+            .cloned()
+            .collect::<Vec<Neighborhood>>();
+        gen_config_changes(
+            &to_neighborhoods_audit_map(&old_hoods),
+            &to_neighborhoods_audit_map(&hoods),
+        )
+    };
+
     // I don't know if there is any better way. Making DB Query costs money
     // Trying to merge in place would also take multiple passes through the neighborhood list
     // so converting it into a map and then replacing the list with values
@@ -654,6 +1049,7 @@ pub async fn update_neighborhoods(
     *NEIGHBORHOODS.write().unwrap() = Some(Arc::new(
         merged_hoods.into_values().collect::<Vec<Neighborhood>>(),
     ));
+    record_config_audit_event("Neighborhoods", changes).await;
     Ok(())
 }
 
@@ -671,6 +1067,88 @@ pub fn get_neighborhood<T: AsRef<str>>(hood: T) -> Option<Neighborhood> {
         .and_then(|v| v.iter().find(|&v| v.name == hood.as_ref()).cloned())
 }
 
+////////////////////////////////////////////////////////////////////////////
+pub fn get_distribution_points() -> Arc<Vec<DistributionPoint>> {
+    DISTRIBUTION_POINTS.read().unwrap().clone()
+}
+
+////////////////////////////////////////////////////////////////////////////
+pub fn get_distribution_point(id: &str) -> Option<DistributionPoint> {
+    DISTRIBUTION_POINTS
+        .read()
+        .unwrap()
+        .iter()
+        .find(|v| v.id == id)
+        .cloned()
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Reports group by distribution point name so this gets back to the configured point
+pub fn get_distribution_point_by_name(name: &str) -> Option<DistributionPoint> {
+    DISTRIBUTION_POINTS
+        .read()
+        .unwrap()
+        .iter()
+        .find(|v| v.name == name)
+        .cloned()
+}
+
+////////////////////////////////////////////////////////////////////////////
+static SET_DISTRIBUTION_POINTS_GQL: &str = r#"
+mutation {
+  updateConfig(config: {
+    distributionPoints: [
+        ***DIST_POINTS_PARAMS***
+    ]
+  })
+}"#;
+
+////////////////////////////////////////////////////////////////////////////
+pub async fn set_distribution_points(
+    dist_points: Vec<DistributionPoint>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let escape = |v: &str| v.replace("\"", "\\\"").replace("\n", r"\n");
+    let dist_points_str = dist_points
+        .iter()
+        .map(|v| {
+            let mut fields = vec![
+                format!("\t\t\tid: \"{}\"", v.id),
+                format!("\t\t\tname: \"{}\"", escape(&v.name)),
+                format!("\t\t\taddress: \"{}\"", escape(&v.address)),
+            ];
+            if let Some((lat, lng)) = v.latitude.zip(v.longitude) {
+                fields.push(format!("\t\t\tlatitude: {lat}"));
+                fields.push(format!("\t\t\tlongitude: {lng}"));
+            }
+            if let Some(contact_name) = v.contact_name.as_ref() {
+                fields.push(format!("\t\t\tcontactName: \"{}\"", escape(contact_name)));
+            }
+            if let Some(contact_phone) = v.contact_phone.as_ref() {
+                fields.push(format!("\t\t\tcontactPhone: \"{}\"", escape(contact_phone)));
+            }
+            if let Some(capacity) = v.capacity {
+                fields.push(format!("\t\t\tcapacity: {capacity}"));
+            }
+            format!("\t\t{{\n{}\n\t\t}}", fields.join(",\n"))
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let query = SET_DISTRIBUTION_POINTS_GQL.replace("***DIST_POINTS_PARAMS***", &dist_points_str);
+
+    // info!("Set Distribution Points Mutation:\n{}", &query);
+    let req = GraphQlReq::new(query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+
+    let changes = gen_config_changes(
+        &to_distribution_points_audit_map(&get_distribution_points()),
+        &to_distribution_points_audit_map(&dist_points),
+    );
+    *DISTRIBUTION_POINTS.write().unwrap() = Arc::new(dist_points);
+    record_config_audit_event("Distribution Points", changes).await;
+    Ok(())
+}
+
 ////////////////////////////////////////////////////////////////////////////
 pub fn get_city_and_zip_from_neighborhood<T: AsRef<str>>(hood: T) -> Option<(String, u32)> {
     get_neighborhood(hood).and_then(|v| v.city.zip(v.zipcode))
@@ -686,6 +1164,151 @@ pub fn get_fr_config() -> Arc<FrConfig> {
     FRCONFIG.read().unwrap().as_ref().unwrap().clone()
 }
 
+////////////////////////////////////////////////////////////////////////////
+/// Updates the cached config after a setting has been saved to the cloud
+fn update_fr_config(update: impl FnOnce(&mut FrConfig)) {
+    let mut config = (*get_fr_config()).clone();
+    update(&mut config);
+    *FRCONFIG.write().unwrap() = Some(Arc::new(config));
+}
+
+////////////////////////////////////////////////////////////////////////////
+static SET_SUPPLIER_OVERAGE_GQL: &str = r"
+mutation {
+  updateConfig(config: {
+    supplierOveragePercent: ***OVERAGE_PARAM***
+  })
+}";
+
+////////////////////////////////////////////////////////////////////////////
+pub async fn set_supplier_overage_percent(percent: u32) -> Result<(), Box<dyn std::error::Error>> {
+    let query = SET_SUPPLIER_OVERAGE_GQL.replace("***OVERAGE_PARAM***", &percent.to_string());
+    info!("Set Supplier Overage Mutation:\n{}", &query);
+    let req = GraphQlReq::new(query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+
+    let config = get_fr_config();
+    let changes = gen_config_changes(
+        &BTreeMap::from([(
+            "supplierOveragePercent".to_string(),
+            config.supplier_overage_percent.to_string(),
+        )]),
+        &BTreeMap::from([("supplierOveragePercent".to_string(), percent.to_string())]),
+    );
+    update_fr_config(|v| v.supplier_overage_percent = percent);
+    record_config_audit_event("Supplier Orders", changes).await;
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////
+static SET_TIMECARD_ACTIVITY_WEIGHTS_GQL: &str = r"
+mutation {
+  updateConfig(config: {
+    timecardActivityWeights: [
+***WEIGHTS_PARAM***
+    ]
+  })
+}";
+
+////////////////////////////////////////////////////////////////////////////
+fn to_activity_weights_audit_map(
+    weights: &BTreeMap<TimecardActivity, Decimal>,
+) -> BTreeMap<String, String> {
+    weights
+        .iter()
+        .map(|(activity, weight)| (format!("{activity} Weight"), weight.to_string()))
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////
+pub async fn set_timecard_activity_weights(
+    weights: BTreeMap<TimecardActivity, Decimal>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let weights_param = weights
+        .iter()
+        .map(|(activity, weight)| {
+            format!(
+                "\t\t{{ activity: \"{}\", weight: \"{}\" }}",
+                activity.as_api_str(),
+                weight
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",\n");
+    let query = SET_TIMECARD_ACTIVITY_WEIGHTS_GQL.replace("***WEIGHTS_PARAM***", &weights_param);
+    info!("Set Timecard Activity Weights Mutation:\n{}", &query);
+    let req = GraphQlReq::new(query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+
+    let config = get_fr_config();
+    let changes = gen_config_changes(
+        &to_activity_weights_audit_map(&config.timecard_activity_weights),
+        &to_activity_weights_audit_map(&weights),
+    );
+    update_fr_config(|v| v.timecard_activity_weights = weights);
+    record_config_audit_event("Closeout", changes).await;
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////
+static SET_MAX_SPREADING_JOB_CLAIMS_GQL: &str = r"
+mutation {
+  updateConfig(config: {
+    maxSpreadingJobClaims: ***MAX_CLAIMS_PARAM***
+  })
+}";
+
+////////////////////////////////////////////////////////////////////////////
+pub async fn set_max_spreading_job_claims(
+    max_claims: u32,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let query =
+        SET_MAX_SPREADING_JOB_CLAIMS_GQL.replace("***MAX_CLAIMS_PARAM***", &max_claims.to_string());
+    info!("Set Max Spreading Job Claims Mutation:\n{}", &query);
+    let req = GraphQlReq::new(query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+
+    let config = get_fr_config();
+    let changes = gen_config_changes(
+        &BTreeMap::from([(
+            "maxSpreadingJobClaims".to_string(),
+            config.max_spreading_job_claims.to_string(),
+        )]),
+        &BTreeMap::from([("maxSpreadingJobClaims".to_string(), max_claims.to_string())]),
+    );
+    update_fr_config(|v| v.max_spreading_job_claims = max_claims);
+    record_config_audit_event("Spreading Jobs", changes).await;
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////
+static SET_TRUCK_CAPACITY_GQL: &str = r"
+mutation {
+  updateConfig(config: {
+    truckCapacity: ***CAPACITY_PARAM***
+  })
+}";
+
+////////////////////////////////////////////////////////////////////////////
+pub async fn set_truck_capacity(capacity: u64) -> Result<(), Box<dyn std::error::Error>> {
+    let query = SET_TRUCK_CAPACITY_GQL.replace("***CAPACITY_PARAM***", &capacity.to_string());
+    info!("Set Truck Capacity Mutation:\n{}", &query);
+    let req = GraphQlReq::new(query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+
+    let config = get_fr_config();
+    let changes = gen_config_changes(
+        &BTreeMap::from([(
+            "truckCapacity".to_string(),
+            config.truck_capacity.to_string(),
+        )]),
+        &BTreeMap::from([("truckCapacity".to_string(), capacity.to_string())]),
+    );
+    update_fr_config(|v| v.truck_capacity = capacity);
+    record_config_audit_event("Delivery Routes", changes).await;
+    Ok(())
+}
+
 ////////////////////////////////////////////////////////////////////////////
 pub fn get_purchase_cost_for(product_id: &str, num_sold: u32) -> String {
     if 0 == num_sold {
@@ -726,6 +1349,7 @@ pub fn is_fundraiser_finalized() -> bool {
 
 ////////////////////////////////////////////////////////////////////////////
 pub fn is_fundraiser_editable() -> bool {
+    // Cutoff extensions only get a seller past the order cutoff, the lock always applies
     let is_fr_readonly = is_fundraiser_locked() || is_fundraiser_finalized();
     !is_fr_readonly || (get_active_user().get_id() == "fradmin")
 }
@@ -763,33 +1387,153 @@ static GET_TIMECARDS_GRAPHQL: &str = r"
   mulchTimecards(***GET_TIMECARDS_PARAMS***){
     id,
     deliveryId
+    spanId
+    activity
     timeIn
     timeOut
     timeTotal
+    clockInLocation {
+      lat
+      lng
+    }
+    clockOutLocation {
+      lat
+      lng
+    }
   }
 }
 ";
 
-////////////////////////////////////////////////////////////////////////////
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-pub struct TimeCard {
-    #[serde(rename = "id")]
-    pub uid: String,
-    #[serde(rename = "deliveryId")]
-    pub delivery_id: u32,
-    #[serde(rename = "timeIn")]
-    pub time_in: String,
-    #[serde(rename = "timeOut")]
-    pub time_out: String,
-    #[serde(rename = "timeTotal")]
-    pub time_total: String,
+////////////////////////////////////////////////////////////////////////////
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct TimeCard {
+    #[serde(rename = "id")]
+    pub uid: String,
+    #[serde(rename = "deliveryId")]
+    pub delivery_id: u32,
+    // Scouts can have several spans for a delivery. Timecards from before spans were
+    // supported have an empty span id.
+    #[serde(rename = "spanId", default)]
+    pub span_id: String,
+    #[serde(default)]
+    pub activity: TimecardActivity,
+    #[serde(rename = "timeIn")]
+    pub time_in: String,
+    #[serde(rename = "timeOut")]
+    pub time_out: String,
+    #[serde(rename = "timeTotal")]
+    pub time_total: String,
+    // Where the time clock was when the scout was clocked in/out
+    #[serde(rename = "clockInLocation", default)]
+    pub clock_in_location: Option<GeoPoint>,
+    #[serde(rename = "clockOutLocation", default)]
+    pub clock_out_location: Option<GeoPoint>,
+}
+
+impl TimeCard {
+    /// Seconds are ignored the same way closeout ignores them
+    pub fn get_time_total(&self) -> Duration {
+        time_val_str_to_duration(&self.time_total).unwrap_or_default()
+    }
+
+    /// A new empty span for the scout to fill in
+    pub fn new_span(uid: &str, delivery_id: u32, activity: TimecardActivity) -> Self {
+        TimeCard {
+            uid: uid.to_string(),
+            delivery_id,
+            span_id: uuid::Uuid::new_v4().to_string(),
+            activity,
+            ..Default::default()
+        }
+    }
+
+    /// Starts a timecard from the time clock using the current time for the delivery.
+    /// Only works on the day of the delivery since the time in has no date.
+    pub fn new_clock_in(
+        uid: &str,
+        delivery_id: u32,
+        activity: TimecardActivity,
+        location: Option<GeoPoint>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let deliveries = get_deliveries();
+        let delivery = deliveries.get(&delivery_id).ok_or("Unknown delivery")?;
+        if !delivery.is_delivery_today() {
+            return Err("Clocking in is only allowed on the day of the delivery".into());
+        }
+        let now = delivery.get_local_now();
+        Ok(TimeCard {
+            time_in: now.format("%H:%M:%S").to_string(),
+            clock_in_location: location,
+            ..TimeCard::new_span(uid, delivery_id, activity)
+        })
+    }
+
+    /// Finishes a time clock timecard using the current time for the delivery
+    pub fn clock_out(
+        &mut self,
+        location: Option<GeoPoint>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let deliveries = get_deliveries();
+        let delivery = deliveries
+            .get(&self.delivery_id)
+            .ok_or("Unknown delivery")?;
+        let delivery_date =
+            NaiveDate::parse_from_str(&delivery.get_delivery_date_str(), "%Y-%m-%d")?;
+        let now = delivery.get_local_now().naive_local();
+        if now.date() > delivery_date + Days::new(1) {
+            return Err(
+                "Clock outs more than a day late are corrected on the Timecards page".into(),
+            );
+        }
+        // Spans can't cross midnight so clocking out after it ends the span with the delivery day
+        let time_out = if now.date() > delivery_date {
+            NaiveTime::from_hms_opt(23, 59, 59).ok_or("Invalid end of day")?
+        } else {
+            now.time()
+        };
+        let time_in = NaiveTime::parse_from_str(&self.time_in, "%H:%M:%S")?;
+        if time_out <= time_in {
+            return Err("Clock out must be after clock in".into());
+        }
+        let total_secs = (time_out - time_in).num_seconds();
+        self.time_out = time_out.format("%H:%M:%S").to_string();
+        self.time_total = format!(
+            "{:02}:{:02}:{:02}",
+            total_secs / 3600,
+            (total_secs % 3600) / 60,
+            total_secs % 60
+        );
+        self.clock_out_location = location;
+        Ok(())
+    }
+
+    pub fn is_clocked_in(&self) -> bool {
+        !self.time_in.is_empty() && self.time_out.is_empty()
+    }
+
+    /// The time clock can still clock out a scout that stayed past midnight
+    pub fn can_clock_out(&self) -> bool {
+        self.is_clocked_in()
+            && get_deliveries().get(&self.delivery_id).is_some_and(|v| {
+                NaiveDate::parse_from_str(&v.get_delivery_date_str(), "%Y-%m-%d")
+                    .is_ok_and(|d| v.get_local_now().date_naive() <= d + Days::new(1))
+            })
+    }
+
+    /// Scouts still clocked in after the delivery day is over forgot to clock out
+    pub fn is_missing_clock_out(&self) -> bool {
+        self.is_clocked_in()
+            && get_deliveries()
+                .get(&self.delivery_id)
+                .is_some_and(|v| v.has_delivery_date_passed())
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////
-pub async fn get_timecards_data(
+/// Gets the timecards as stored. Without a delivery id a scout will have one per delivery.
+pub async fn get_timecards(
     delivery_id: Option<u32>,
-    _uid: Option<String>,
-) -> Result<Vec<(String, String, Option<TimeCard>)>, Box<dyn std::error::Error>> {
+) -> Result<Vec<TimeCard>, Box<dyn std::error::Error>> {
     let query = if let Some(delivery_id) = delivery_id {
         GET_TIMECARDS_GRAPHQL.replace(
             "***GET_TIMECARDS_PARAMS***",
@@ -800,32 +1544,45 @@ pub async fn get_timecards_data(
     };
     info!("Running Query: {}", &query);
 
-    let mut timecard_map = {
-        #[derive(Serialize, Deserialize, Debug)]
-        struct GqlResp {
-            #[serde(alias = "mulchTimecards")]
-            mulch_timecards: Vec<TimeCard>,
-        }
+    #[derive(Serialize, Deserialize, Debug)]
+    struct GqlResp {
+        #[serde(alias = "mulchTimecards")]
+        mulch_timecards: Vec<TimeCard>,
+    }
 
-        let req = GraphQlReq::new(query);
-        let resp = make_gql_request::<GqlResp>(&req).await?;
-        let timecard_map: BTreeMap<_, _> = resp
-            .mulch_timecards
-            .into_iter()
-            .map(|v| (v.uid.clone(), v))
-            .collect();
-        timecard_map
-    };
+    let req = GraphQlReq::new(query);
+    Ok(make_gql_request::<GqlResp>(&req).await?.mulch_timecards)
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Users in these groups don't work deliveries so they aren't on the timesheet
+pub fn is_timecard_excluded_group(group: &str) -> bool {
+    !get_group_attributes_for(group).is_timecard_eligible
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Gets each user on the timesheet with their spans in the order they were worked
+pub async fn get_timecards_data(
+    delivery_id: Option<u32>,
+    _uid: Option<String>,
+) -> Result<Vec<(String, String, Vec<TimeCard>)>, Box<dyn std::error::Error>> {
+    let mut timecard_map: BTreeMap<String, Vec<TimeCard>> = BTreeMap::new();
+    for tc in get_timecards(delivery_id).await? {
+        timecard_map.entry(tc.uid.clone()).or_default().push(tc);
+    }
 
     let timecard_data = (*get_users())
         .clone()
         .into_iter()
-        .filter(|(_, user_info)| "Bear" != user_info.group && "Bogus" != user_info.group)
+        .filter(|(_, user_info)| !is_timecard_excluded_group(&user_info.group))
         .map(|(uid, user_info)| {
-            let tc = timecard_map.remove(&uid);
-            (uid, user_info.name, tc)
+            let mut tcs = timecard_map.remove(&uid).unwrap_or_default();
+            tcs.sort_by(|a, b| {
+                (a.time_in.is_empty(), &a.time_in).cmp(&(b.time_in.is_empty(), &b.time_in))
+            });
+            (uid, user_info.name, tcs)
         })
-        .collect::<Vec<(String, String, Option<TimeCard>)>>();
+        .collect::<Vec<(String, String, Vec<TimeCard>)>>();
     //timecard_data.sort_by_key(|k| k.1.clone());  //Shouldn't need this since btree is sorted
     Ok(timecard_data)
 }
@@ -838,6 +1595,13 @@ mutation {
   ])
 }";
 
+////////////////////////////////////////////////////////////////////////////
+fn gen_timecard_location_param(field: &str, location: &Option<GeoPoint>) -> String {
+    location.as_ref().map_or("".to_string(), |v| {
+        format!("\n\t\t\t{field}: {{ lat: {}, lng: {} }}", v.lat, v.lng)
+    })
+}
+
 ////////////////////////////////////////////////////////////////////////////
 pub async fn save_timecards_data(
     timecards: Vec<TimeCard>,
@@ -850,12 +1614,16 @@ pub async fn save_timecards_data(
         .iter()
         .map(|v| {
             format!(
-                "\t\t{{\n{}\n{}\n{}\n{}\n{}\n\t\t}}",
+                "\t\t{{\n{}\n{}\n{}\n{}\n{}\n{}\n{}{}{}\n\t\t}}",
                 format_args!("\t\t\tid: \"{}\",", &v.uid),
                 format_args!("\t\t\tdeliveryId: {},", v.delivery_id),
+                format_args!("\t\t\tspanId: \"{}\",", &v.span_id),
+                format_args!("\t\t\tactivity: \"{}\",", v.activity.as_api_str()),
                 format_args!("\t\t\ttimeIn: \"{}\",", &v.time_in),
                 format_args!("\t\t\ttimeOut: \"{}\",", &v.time_out),
-                format_args!("\t\t\ttimeTotal: \"{}\"", &v.time_total)
+                format_args!("\t\t\ttimeTotal: \"{}\"", &v.time_total),
+                gen_timecard_location_param("clockInLocation", &v.clock_in_location),
+                gen_timecard_location_param("clockOutLocation", &v.clock_out_location)
             )
         })
         .collect::<Vec<String>>()
@@ -874,6 +1642,7 @@ static GET_FR_CLOSURE_DATA_GRAPHQL: &str = r"
 {
   mulchTimecards{
     id,
+    activity
     timeTotal
   }
   mulchOrders {
@@ -885,7 +1654,17 @@ static GET_FR_CLOSURE_DATA_GRAPHQL: &str = r"
     }
     amountFromDonations
     amountTotalCollected
+    amountRefunded
     spreaders
+    spreaderBags {
+        uid
+        numBags
+    }
+  }
+  cancelledOrders: mulchOrders(doGetCancelledOrdersOnly: true) {
+    ownerId
+    amountTotalCollected
+    amountRefunded
   }
 }
 ";
@@ -894,15 +1673,34 @@ static GET_FR_CLOSURE_DATA_GRAPHQL: &str = r"
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct FrClosureMapData {
     pub delivery_time_total: Duration,
+    // Breakdown of delivery_time_total by what was being done
+    pub activity_time_totals: BTreeMap<TimecardActivity, Duration>,
     pub num_bags_sold: u64,
     pub amount_from_bags_sales: Decimal,
     pub num_bags_to_spread_sold: u64,
     pub amount_from_bags_to_spread_sales: Decimal,
     pub amount_from_donations: Decimal,
     pub amount_total_collected: Decimal,
+    pub amount_refunded: Decimal,
     pub num_bags_spread: Decimal,
 }
 
+impl FrClosureMapData {
+    /// Minutes worked with each activity scaled by its configured weight
+    pub fn get_weighted_delivery_minutes(&self) -> Decimal {
+        let config = get_fr_config();
+        self.activity_time_totals
+            .iter()
+            .map(|(activity, dur)| {
+                Decimal::from_f64(dur.as_secs_f64() / 60.0)
+                    .unwrap_or_default()
+                    .checked_mul(config.get_activity_weight(activity))
+                    .unwrap_or_default()
+            })
+            .sum()
+    }
+}
+
 /////////////////////////////////////////////////
 pub fn time_val_str_to_duration(time_val_str: &str) -> Option<Duration> {
     let mut time_val_str = time_val_str
@@ -944,6 +1742,42 @@ pub fn get_calculated_bags_spread_per_user(spreaders: &[String], num_bags: usize
     }
 }
 
+/// Bags spread by each spreader using the recorded split when there is one for every
+/// spreader and it adds up to the bags on the order, otherwise splits them evenly.
+pub fn get_bags_spread_per_user(
+    spreaders: &[String],
+    spreader_bags: &[SpreaderBags],
+    num_bags: usize,
+) -> Vec<(String, Decimal)> {
+    let recorded_bags = spreaders
+        .iter()
+        .map(|uid| {
+            spreader_bags
+                .iter()
+                .find(|v| &v.uid == uid)
+                .map(|v| (uid.clone(), Decimal::from(v.num_bags)))
+        })
+        .collect::<Option<Vec<(String, Decimal)>>>();
+
+    match recorded_bags {
+        Some(recorded_bags)
+            if recorded_bags.iter().map(|v| v.1).sum::<Decimal>() == Decimal::from(num_bags) =>
+        {
+            recorded_bags
+        }
+        _ => {
+            if !spreader_bags.is_empty() {
+                warn!("Recorded spreader bags don't match the order so splitting evenly");
+            }
+            let bags_per_user = get_calculated_bags_spread_per_user(spreaders, num_bags);
+            spreaders
+                .iter()
+                .map(|uid| (uid.clone(), bags_per_user))
+                .collect()
+        }
+    }
+}
+
 pub type FrClosureStaticData = Arc<BTreeMap<String, FrClosureMapData>>;
 ////////////////////////////////////////////////////////////////////////////
 pub async fn get_fundraiser_closure_static_data()
@@ -958,6 +1792,8 @@ pub async fn get_fundraiser_closure_static_data()
     struct TimecardClosureData {
         #[serde(rename = "id")]
         uid: String,
+        #[serde(default)]
+        activity: TimecardActivity,
         #[serde(rename = "timeTotal")]
         time_total: String,
     }
@@ -978,8 +1814,21 @@ pub async fn get_fundraiser_closure_static_data()
         amount_from_donations: Option<String>,
         #[serde(rename = "amountTotalCollected")]
         amount_total_collected: Option<String>,
+        #[serde(rename = "amountRefunded")]
+        amount_refunded: Option<String>,
         purchases: Vec<PurchasedItemsClosureData>,
         spreaders: Vec<String>,
+        #[serde(rename = "spreaderBags", default)]
+        spreader_bags: Option<Vec<SpreaderBags>>,
+    }
+    #[derive(Deserialize, Debug)]
+    struct CancelledOrdersClosureData {
+        #[serde(rename = "ownerId")]
+        uid: String,
+        #[serde(rename = "amountTotalCollected")]
+        amount_total_collected: Option<String>,
+        #[serde(rename = "amountRefunded")]
+        amount_refunded: Option<String>,
     }
     #[derive(Deserialize, Debug)]
     struct RespClosureData {
@@ -987,6 +1836,8 @@ pub async fn get_fundraiser_closure_static_data()
         time_cards: Vec<TimecardClosureData>,
         #[serde(rename = "mulchOrders")]
         orders: Vec<OrdersClosureData>,
+        #[serde(rename = "cancelledOrders", default)]
+        cancelled_orders: Vec<CancelledOrdersClosureData>,
     }
 
     let resp = {
@@ -996,8 +1847,9 @@ pub async fn get_fundraiser_closure_static_data()
         make_gql_request::<RespClosureData>(&req).await?
     };
 
-    fn add_tc(cd: &mut FrClosureMapData, add_dur: Duration) {
-        cd.delivery_time_total = cd.delivery_time_total.checked_add(add_dur).unwrap();
+    fn add_tc(cd: &mut FrClosureMapData, activity: TimecardActivity, add_dur: Duration) {
+        cd.delivery_time_total = cd.delivery_time_total.saturating_add(add_dur);
+        *cd.activity_time_totals.entry(activity).or_default() += add_dur;
     }
 
     fn add_order_data(cd: &mut FrClosureMapData, new_data: &FrClosureMapData) {
@@ -1009,6 +1861,10 @@ pub async fn get_fundraiser_closure_static_data()
             .amount_total_collected
             .checked_add(new_data.amount_total_collected)
             .unwrap();
+        cd.amount_refunded = cd
+            .amount_refunded
+            .checked_add(new_data.amount_refunded)
+            .unwrap();
         cd.num_bags_sold += new_data.num_bags_sold;
         cd.amount_from_bags_sales = cd
             .amount_from_bags_sales
@@ -1024,6 +1880,7 @@ pub async fn get_fundraiser_closure_static_data()
     fn register_spreaders(
         closure_data: &mut BTreeMap<String, FrClosureMapData>,
         mut spreaders: Vec<String>,
+        spreader_bags: Vec<SpreaderBags>,
         num_bags: u64,
     ) {
         //Due to bug, there can be empty spreaders.
@@ -1038,10 +1895,9 @@ pub async fn get_fundraiser_closure_static_data()
             return;
         }
 
-        let num_bags_to_record_as_spread_per_user: Decimal =
-            get_calculated_bags_spread_per_user(&spreaders, num_bags as usize);
-
-        for uid in spreaders {
+        for (uid, num_bags_to_record_as_spread_per_user) in
+            get_bags_spread_per_user(&spreaders, &spreader_bags, num_bags as usize)
+        {
             if !closure_data.contains_key(&uid) {
                 closure_data.insert(uid.clone(), FrClosureMapData::default());
             }
@@ -1063,13 +1919,29 @@ pub async fn get_fundraiser_closure_static_data()
     closure_data.insert("TROOP_TOTALS".to_string(), FrClosureMapData::default());
 
     // convert time and total and assign to user
+    let users = get_users();
     for tc in resp.time_cards {
         if !closure_data.contains_key(&tc.uid) {
             closure_data.insert(tc.uid.clone(), FrClosureMapData::default());
         }
-        let dur = time_val_str_to_duration(tc.time_total.as_str()).unwrap();
-        add_tc(closure_data.get_mut(&tc.uid).unwrap(), dur);
-        add_tc(closure_data.get_mut("TROOP_TOTALS").unwrap(), dur);
+        // Scouts still clocked in don't have a total yet
+        let Some(dur) = time_val_str_to_duration(tc.time_total.as_str()) else {
+            continue;
+        };
+        add_tc(closure_data.get_mut(&tc.uid).unwrap(), tc.activity, dur);
+        // Hours are still tracked for users not getting allocations but they don't
+        // dilute the delivery earnings per minute
+        if users
+            .get(&tc.uid)
+            .is_some_and(|v| is_allocation_excluded_group(&v.group))
+        {
+            continue;
+        }
+        add_tc(
+            closure_data.get_mut("TROOP_TOTALS").unwrap(),
+            tc.activity,
+            dur,
+        );
     }
 
     for order in resp.orders {
@@ -1087,6 +1959,9 @@ pub async fn get_fundraiser_closure_static_data()
                 amount_total_collected: order
                     .amount_total_collected
                     .map_or(Decimal::ZERO, |v| Decimal::from_str(v.as_str()).unwrap()),
+                amount_refunded: order
+                    .amount_refunded
+                    .map_or(Decimal::ZERO, |v| Decimal::from_str(v.as_str()).unwrap()),
                 ..Default::default()
             };
 
@@ -1112,10 +1987,32 @@ pub async fn get_fundraiser_closure_static_data()
         register_spreaders(
             &mut closure_data,
             order.spreaders,
+            order.spreader_bags.unwrap_or_default(),
             new_data.num_bags_to_spread_sold,
         );
     }
 
+    // Money collected on a cancelled order was still deposited and any refund of it was
+    // paid back out so both count even though nothing was sold
+    for order in resp.cancelled_orders {
+        if !closure_data.contains_key(&order.uid) {
+            closure_data.insert(order.uid.clone(), FrClosureMapData::default());
+        }
+
+        let new_data = FrClosureMapData {
+            amount_total_collected: order
+                .amount_total_collected
+                .map_or(Decimal::ZERO, |v| Decimal::from_str(v.as_str()).unwrap()),
+            amount_refunded: order
+                .amount_refunded
+                .map_or(Decimal::ZERO, |v| Decimal::from_str(v.as_str()).unwrap()),
+            ..Default::default()
+        };
+
+        add_order_data(closure_data.get_mut(&order.uid).unwrap(), &new_data);
+        add_order_data(closure_data.get_mut("TROOP_TOTALS").unwrap(), &new_data);
+    }
+
     if let Ok(mut arc_map) = FR_CLOSURE_DATA.write() {
         Arc::get_mut(&mut *arc_map)
             .unwrap()
@@ -1164,7 +2061,7 @@ pub fn set_fundraiser_closure_dynamic_data(data: FrClosureDynamicData) {
 }
 
 ////////////////////////////////////////////////////////
-#[derive(Serialize, Default, Debug, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq, Clone)]
 pub struct FrCloseoutDynamicVars {
     pub bank_deposited: Decimal,
     pub mulch_cost: Decimal,
@@ -1246,11 +2143,52 @@ static SET_FR_CLOSEOUT_ALLOC_DATUM_GRAPHQL: &str = r#"
             allocationsTotal:
         }
 "#;
+////////////////////////////////////////////////////////////////////////////
+static GET_FR_CLOSEOUT_CONFIG_DATA_GRAPHQL: &str = r"
+{
+  config {
+    finalizationData {
+      bankDeposited
+      mulchCost
+      perBagCost
+      profitsFromBags
+      mulchSalesGross
+      moneyPoolForTroop
+      moneyPoolForScoutsSubPools
+      moneyPoolForScoutsSales
+      moneyPoolForScoutsDelivery
+      perBagAvgEarnings
+      deliveryEarningsPerMinute
+    }
+  }
+}";
+
+////////////////////////////////////////////////////////////////////////////
+/// What the server has for the closeout right now. The locally stored config can be
+/// stale or missing so the audit needs to compare against this instead.
+async fn get_fr_closeout_config_data_from_server() -> Option<FinalizationDataConfigApi> {
+    #[derive(Deserialize, Debug)]
+    struct FinalizationConfigApi {
+        #[serde(alias = "finalizationData")]
+        finalization_data: Option<FinalizationDataConfigApi>,
+    }
+    #[derive(Deserialize, Debug)]
+    struct RespApi {
+        config: FinalizationConfigApi,
+    }
+    let req = GraphQlReq::new(GET_FR_CLOSEOUT_CONFIG_DATA_GRAPHQL);
+    make_gql_request::<RespApi>(&req)
+        .await
+        .inspect_err(|err| warn!("Failed to get the current closeout data: {err:#?}"))
+        .ok()
+        .and_then(|v| v.config.finalization_data)
+}
+
 ////////////////////////////////////////////////////////////////////////////
 pub async fn set_fr_closeout_data(
     dynamic_vars: &FrCloseoutDynamicVars,
     allocation_list: &[FrCloseoutAllocationVals],
-) {
+) -> Result<(), Box<dyn std::error::Error>> {
     // Set Config closeout data
     let query = SET_FR_CLOSEOUT_CONFIG_DATA_GRAPHQL
         .replace(
@@ -1325,8 +2263,25 @@ pub async fn set_fr_closeout_data(
             ),
         );
 
+    let prev_finalization_data = get_fr_closeout_config_data_from_server().await;
     let req = GraphQlReq::new(query);
-    let _ = make_gql_request::<serde_json::Value>(&req).await.unwrap();
+    let _ = make_gql_request::<serde_json::Value>(&req).await?;
+
+    // Both sides go through the same type so only real value changes show up in the audit
+    let to_audit_map = |v: &FrCloseoutDynamicVars| {
+        to_decimal_audit_map(serde_json::to_value(v).unwrap_or_default())
+    };
+    let prev_dynamic_vars = prev_finalization_data
+        .and_then(|v| serde_json::to_value(v).ok())
+        .and_then(|v| serde_json::from_value::<FrCloseoutDynamicVars>(v).ok());
+    let changes = gen_config_changes(
+        &prev_dynamic_vars
+            .as_ref()
+            .map(to_audit_map)
+            .unwrap_or_default(),
+        &to_audit_map(dynamic_vars),
+    );
+    record_config_audit_event("Closeout", changes).await;
 
     let query = SET_FR_CLOSEOUT_ALLOC_DATA_GRAPHQL.replace(
         "***ALLOCATIONS***",
@@ -1406,7 +2361,8 @@ pub async fn set_fr_closeout_data(
 
     info!("Allocation Mutation:\n{}", &query);
     let req = GraphQlReq::new(query);
-    let _ = make_gql_request::<serde_json::Value>(&req).await.unwrap();
+    let _ = make_gql_request::<serde_json::Value>(&req).await?;
+    Ok(())
 }
 
 ////////////////////////////////////////////////////////////////////////////
@@ -1540,6 +2496,31 @@ pub async fn add_or_update_users_for_admin_config(
         .await
         .map(|_| ())?;
 
+    let changes = {
+        let old_users = get_users();
+        let to_audit_str = |name: &str, group: &str| format!("{name} ({group})");
+        gen_config_changes(
+            &users
+                .iter()
+                .filter_map(|v| {
+                    old_users
+                        .get(&v.id)
+                        .map(|u| (v.id.clone(), to_audit_str(&u.name, &u.group)))
+                })
+                .collect(),
+            &users
+                .iter()
+                .map(|v| {
+                    (
+                        v.id.clone(),
+                        to_audit_str(&format!("{} {}", v.first_name, v.last_name), &v.group),
+                    )
+                })
+                .collect(),
+        )
+    };
+    record_config_audit_event("Users", changes).await;
+
     // I don't know if there is any better way. Making DB Query costs money
     // Trying to merge in place would also take multiple passes through the neighborhood list
     // so converting it into a map and then replacing list with values
@@ -1561,3 +2542,348 @@ pub async fn add_or_update_users_for_admin_config(
 
     Ok(())
 }
+
+////////////////////////////////////////////////////////////////////////////
+/// What users in a group take part in. Groups without any are treated like scouts.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GroupAttributes {
+    pub group: String,
+    #[serde(alias = "isTimecardEligible")]
+    pub is_timecard_eligible: bool,
+    // Adults can have their hours tracked without taking a share of the scout allocations
+    #[serde(alias = "isAllocationEligible")]
+    pub is_allocation_eligible: bool,
+    #[serde(alias = "isHiddenFromTopSellers")]
+    pub is_hidden_from_top_sellers: bool,
+}
+
+impl GroupAttributes {
+    pub fn new(group: &str) -> Self {
+        GroupAttributes {
+            group: group.to_string(),
+            is_timecard_eligible: true,
+            is_allocation_eligible: true,
+            is_hidden_from_top_sellers: false,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+// These groups were always left off the timesheet before the attributes were configurable
+fn get_legacy_group_attributes() -> Vec<GroupAttributes> {
+    ["Bear", "Bogus"]
+        .iter()
+        .map(|group| GroupAttributes {
+            is_timecard_eligible: false,
+            ..GroupAttributes::new(group)
+        })
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////
+pub fn get_group_attributes() -> Arc<Vec<GroupAttributes>> {
+    GROUP_ATTRIBUTES.read().unwrap().clone()
+}
+
+////////////////////////////////////////////////////////////////////////////
+pub fn get_group_attributes_for(group: &str) -> GroupAttributes {
+    GROUP_ATTRIBUTES
+        .read()
+        .unwrap()
+        .iter()
+        .find(|v| v.group == group)
+        .cloned()
+        .unwrap_or_else(|| GroupAttributes::new(group))
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Users in these groups don't get a share of the scout allocations at closeout
+pub fn is_allocation_excluded_group(group: &str) -> bool {
+    !get_group_attributes_for(group).is_allocation_eligible
+}
+
+////////////////////////////////////////////////////////////////////////////
+static SET_GROUP_ATTRIBUTES_GQL: &str = r"
+mutation {
+  updateConfig(config: {
+    groupAttributes: [
+***GROUP_ATTRIBUTES_PARAM***
+    ]
+  })
+}";
+
+////////////////////////////////////////////////////////////////////////////
+pub async fn set_group_attributes(
+    group_attributes: Vec<GroupAttributes>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let escape = |v: &str| v.replace("\"", "\\\"");
+    let group_attributes_str = group_attributes
+        .iter()
+        .map(|v| {
+            format!(
+                "\t\t{{ group: \"{}\", isTimecardEligible: {}, isAllocationEligible: {}, isHiddenFromTopSellers: {} }}",
+                escape(&v.group),
+                v.is_timecard_eligible,
+                v.is_allocation_eligible,
+                v.is_hidden_from_top_sellers
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",\n");
+    let query =
+        SET_GROUP_ATTRIBUTES_GQL.replace("***GROUP_ATTRIBUTES_PARAM***", &group_attributes_str);
+    info!("Set Group Attributes Mutation:\n{}", &query);
+    let req = GraphQlReq::new(query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+
+    let changes = gen_config_changes(
+        &to_group_attributes_audit_map(&get_group_attributes()),
+        &to_group_attributes_audit_map(&group_attributes),
+    );
+    *GROUP_ATTRIBUTES.write().unwrap() = Arc::new(group_attributes);
+    record_config_audit_event("Groups", changes).await;
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////
+// Config Audit Log
+////////////////////////////////////////////////////////////////////////////
+#[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ConfigFieldChange {
+    pub item: String,
+    pub from: String,
+    pub to: String,
+}
+
+////////////////////////////////////////////////////////////////////////////
+#[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ConfigAuditEvent {
+    pub section: String,
+    #[serde(alias = "changedBy")]
+    pub changed_by: String,
+    #[serde(alias = "changeTime")]
+    pub change_time: String,
+    pub changes: Vec<ConfigFieldChange>,
+}
+
+impl ConfigAuditEvent {
+    pub fn get_change_time_str(&self) -> String {
+        format_fundraiser_datetime(&self.change_time, "%m/%d/%Y %I:%M %p")
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+fn to_products_audit_map(products: &BTreeMap<String, ProductInfo>) -> BTreeMap<String, String> {
+    products
+        .iter()
+        .map(|(id, v)| {
+            let price_breaks = v
+                .price_breaks
+                .iter()
+                .map(|pb| format!(">{}: {}", pb.gt, pb.unit_price))
+                .collect::<Vec<String>>()
+                .join(", ");
+            (
+                id.clone(),
+                format!(
+                    "unitPrice: {}, minUnits: {}, priceBreaks: [{}]",
+                    v.unit_price, v.min_units, price_breaks
+                ),
+            )
+        })
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////
+fn to_deliveries_audit_map(deliveries: &BTreeMap<u32, DeliveryInfo>) -> BTreeMap<String, String> {
+    deliveries
+        .iter()
+        .map(|(id, v)| {
+            (
+                id.to_string(),
+                format!(
+                    "date: {}, newOrderCutoff: {}, timezone: {}, maxBags: {}, isWaitlistEnabled: {}, isLocked: {}",
+                    v.get_api_delivery_date_str(),
+                    v.get_api_new_order_cutoff_date_str(),
+                    v.get_timezone_name(),
+                    v.get_max_bags().map_or("".to_string(), |v| v.to_string()),
+                    v.is_waitlist_enabled(),
+                    v.is_locked()
+                ),
+            )
+        })
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////
+fn to_neighborhoods_audit_map(hoods: &[Neighborhood]) -> BTreeMap<String, String> {
+    hoods
+        .iter()
+        .map(|v| {
+            (
+                v.name.clone(),
+                format!(
+                    "distributionPoint: {}, city: {}, zipcode: {}, isVisible: {}",
+                    v.get_distribution_point_name(),
+                    v.city.as_deref().unwrap_or(""),
+                    v.zipcode.map_or("".to_string(), |v| v.to_string()),
+                    v.is_visible
+                ),
+            )
+        })
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////
+fn to_distribution_points_audit_map(dist_points: &[DistributionPoint]) -> BTreeMap<String, String> {
+    dist_points
+        .iter()
+        .map(|v| {
+            (
+                v.name.clone(),
+                format!(
+                    "address: {}, location: {}, contact: {} {}, capacity: {}",
+                    v.address,
+                    v.get_location()
+                        .map_or("".to_string(), |v| format!("{},{}", v.lat, v.lng)),
+                    v.contact_name.as_deref().unwrap_or(""),
+                    v.contact_phone.as_deref().unwrap_or(""),
+                    v.capacity.map_or("".to_string(), |v| v.to_string())
+                ),
+            )
+        })
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////
+fn to_group_attributes_audit_map(group_attributes: &[GroupAttributes]) -> BTreeMap<String, String> {
+    group_attributes
+        .iter()
+        .map(|v| {
+            (
+                v.group.clone(),
+                format!(
+                    "isTimecardEligible: {}, isAllocationEligible: {}, isHiddenFromTopSellers: {}",
+                    v.is_timecard_eligible, v.is_allocation_eligible, v.is_hidden_from_top_sellers
+                ),
+            )
+        })
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////
+// Normalizes the decimal strings so "1.5000" and "1.5" aren't reported as a change
+fn to_decimal_audit_map(values: serde_json::Value) -> BTreeMap<String, String> {
+    values
+        .as_object()
+        .map(|v| {
+            v.iter()
+                .map(|(k, v)| {
+                    let v = v.as_str().map_or(v.to_string(), |v| {
+                        Decimal::from_str(v)
+                            .map_or(v.to_string(), |v| v.round_dp(4).normalize().to_string())
+                    });
+                    (k.clone(), v)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+////////////////////////////////////////////////////////////////////////////
+fn gen_config_changes(
+    before: &BTreeMap<String, String>,
+    after: &BTreeMap<String, String>,
+) -> Vec<ConfigFieldChange> {
+    let mut items = before.keys().chain(after.keys()).collect::<Vec<&String>>();
+    items.sort();
+    items.dedup();
+
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let from = before.get(item).cloned().unwrap_or_default();
+            let to = after.get(item).cloned().unwrap_or_default();
+            (from != to).then(|| ConfigFieldChange {
+                item: item.clone(),
+                from,
+                to,
+            })
+        })
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////
+static ADD_CONFIG_AUDIT_EVENT_GQL: &str = r#"
+mutation {
+  addConfigAuditEvent(event: {
+    section: "***SECTION_PARAM***",
+    changedBy: "***USER_PARAM***",
+    changeTime: "***TIME_PARAM***",
+    changes: [***CHANGES_PARAM***]
+  })
+}"#;
+
+////////////////////////////////////////////////////////////////////////////
+// The config change has already been saved by the time this is called so a failure here
+// is logged instead of failing the save.
+async fn record_config_audit_event(section: &str, changes: Vec<ConfigFieldChange>) {
+    if changes.is_empty() {
+        return;
+    }
+    let escape = |v: &str| v.replace("\"", "\\\"").replace("\n", r"\n");
+    let changes_str = changes
+        .iter()
+        .map(|v| {
+            format!(
+                "{{item: \"{}\", from: \"{}\", to: \"{}\"}}",
+                escape(&v.item),
+                escape(&v.from),
+                escape(&v.to)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+    let query = ADD_CONFIG_AUDIT_EVENT_GQL
+        .replace("***SECTION_PARAM***", section)
+        .replace("***USER_PARAM***", &get_active_user().get_id())
+        .replace("***TIME_PARAM***", &Utc::now().to_rfc3339())
+        .replace("***CHANGES_PARAM***", &changes_str);
+
+    info!("Config Audit Mutation:\n{}", &query);
+    let req = GraphQlReq::new(query);
+    if let Err(err) = make_gql_request::<serde_json::Value>(&req).await {
+        error!("Failed to record config audit event for {section}: {err:#?}");
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+static GET_CONFIG_AUDIT_LOG_GQL: &str = r"
+{
+  configAuditLog {
+    section
+    changedBy
+    changeTime
+    changes {
+      item
+      from
+      to
+    }
+  }
+}";
+
+////////////////////////////////////////////////////////////////////////////
+pub async fn get_config_audit_log() -> Result<Vec<ConfigAuditEvent>, Box<dyn std::error::Error>> {
+    #[derive(Deserialize)]
+    struct RespAuditLog {
+        #[serde(alias = "configAuditLog")]
+        audit_log: Vec<ConfigAuditEvent>,
+    }
+
+    let req = GraphQlReq::new(GET_CONFIG_AUDIT_LOG_GQL);
+    make_gql_request::<RespAuditLog>(&req).await.map(|v| {
+        let mut audit_log = v.audit_log;
+        audit_log.sort_by(|a, b| b.change_time.cmp(&a.change_time));
+        audit_log
+    })
+}
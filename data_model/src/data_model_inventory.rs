@@ -0,0 +1,389 @@
+use super::{
+    DistributionPoint,
+    data_model_reports::{get_purchase_to_map, make_report_query},
+    get_active_user, get_distribution_point, get_distribution_point_by_name,
+    get_distribution_points, get_neighborhood,
+    gql_utils::{GraphQlReq, make_gql_request},
+    is_waitlisted_from_report_data,
+};
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::{info, warn};
+
+// Spreading is a service so there is never any stock to track for it
+const NON_INVENTORY_PRODUCTS: [&str; 1] = ["spreading"];
+
+////////////////////////////////////////////////////////////////////////////
+/// What the supplier dropped off at a distribution point for a delivery
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+pub struct InventoryRecord {
+    #[serde(alias = "deliveryId")]
+    pub delivery_id: u32,
+    #[serde(alias = "distPointId")]
+    pub dist_point_id: String,
+    #[serde(alias = "productId")]
+    pub product_id: String,
+    #[serde(alias = "numReceived")]
+    pub num_received: Option<u64>,
+    // What was asked for on the supplier order
+    #[serde(alias = "numOrdered")]
+    pub num_ordered: Option<u64>,
+    #[serde(alias = "updatedBy")]
+    pub updated_by: String,
+    #[serde(alias = "updateTime")]
+    pub update_time: String,
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Stock for a product at a distribution point for a delivery
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+pub struct InventoryStatus {
+    pub delivery_id: u32,
+    pub dist_point: String,
+    // Only distribution points configured with an id can have stock recorded
+    pub dist_point_id: Option<String>,
+    pub product_id: String,
+    pub num_ordered: Option<u64>,
+    pub num_received: Option<u64>,
+    pub num_allocated: u64,
+}
+
+impl InventoryStatus {
+    /// Stock left once every order for the delivery is filled. Since the allocation
+    /// comes from the current orders anything added after the supplier order was
+    /// placed comes out of this.
+    pub fn get_remaining(&self) -> Option<i64> {
+        self.num_received
+            .map(|v| v as i64 - self.num_allocated as i64)
+    }
+
+    pub fn get_leftover(&self) -> u64 {
+        self.get_remaining().map_or(0, |v| v.max(0) as u64)
+    }
+
+    pub fn get_shortage(&self) -> u64 {
+        self.get_remaining().map_or(0, |v| (-v).max(0) as u64)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+pub fn get_inventory_product_ids() -> Vec<String> {
+    super::get_products()
+        .keys()
+        .filter(|v| !NON_INVENTORY_PRODUCTS.contains(&v.as_str()))
+        .cloned()
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////
+static INVENTORY_RECORDS_GQL: &str = r"
+{
+  inventoryRecords***DELIVERY_PARAM*** {
+    deliveryId
+    distPointId
+    productId
+    numOrdered
+    numReceived
+    updatedBy
+    updateTime
+  }
+}";
+
+////////////////////////////////////////////////////////////////////////////
+pub async fn get_inventory_records(
+    delivery_id: Option<u32>,
+) -> Result<Vec<InventoryRecord>, Box<dyn std::error::Error>> {
+    #[derive(Deserialize)]
+    struct RespInventory {
+        #[serde(alias = "inventoryRecords")]
+        records: Vec<InventoryRecord>,
+    }
+
+    let query = INVENTORY_RECORDS_GQL.replace(
+        "***DELIVERY_PARAM***",
+        &delivery_id.map_or("".to_string(), |v| format!("(deliveryId: {v})")),
+    );
+    let req = GraphQlReq::new(query);
+    Ok(make_gql_request::<RespInventory>(&req).await?.records)
+}
+
+////////////////////////////////////////////////////////////////////////////
+static SET_INVENTORY_RECORD_GQL: &str = r#"
+mutation {
+  setInventoryRecord(record: {
+    deliveryId: ***DELIVERY_ID_PARAM***,
+    distPointId: "***DIST_POINT_ID_PARAM***",
+    productId: "***PRODUCT_ID_PARAM***",
+    ***NUM_FIELD_PARAM***: ***NUM_PARAM***,
+    updatedBy: "***USER_PARAM***",
+    updateTime: "***TIME_PARAM***"
+  })
+}"#;
+
+////////////////////////////////////////////////////////////////////////////
+/// The received and ordered counts are set at different times so only the one being
+/// set is sent and the other is left as it is
+async fn set_inventory_count(
+    record: &InventoryRecord,
+    num_field: &str,
+    num: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let query = SET_INVENTORY_RECORD_GQL
+        .replace("***DELIVERY_ID_PARAM***", &record.delivery_id.to_string())
+        .replace("***DIST_POINT_ID_PARAM***", &record.dist_point_id)
+        .replace("***PRODUCT_ID_PARAM***", &record.product_id)
+        .replace("***NUM_FIELD_PARAM***", num_field)
+        .replace("***NUM_PARAM***", &num.to_string())
+        .replace("***USER_PARAM***", &record.updated_by)
+        .replace("***TIME_PARAM***", &record.update_time);
+
+    info!("Set Inventory Record Mutation:\n{}", &query);
+    let req = GraphQlReq::new(query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////
+fn new_inventory_record(
+    delivery_id: u32,
+    dist_point_id: &str,
+    product_id: &str,
+) -> InventoryRecord {
+    InventoryRecord {
+        delivery_id,
+        dist_point_id: dist_point_id.to_string(),
+        product_id: product_id.to_string(),
+        updated_by: get_active_user().get_id(),
+        update_time: Utc::now().to_rfc3339(),
+        ..Default::default()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+pub async fn set_inventory_received(
+    delivery_id: u32,
+    dist_point_id: &str,
+    product_id: &str,
+    num_received: u64,
+) -> Result<InventoryRecord, Box<dyn std::error::Error>> {
+    let mut record = new_inventory_record(delivery_id, dist_point_id, product_id);
+    record.num_received = Some(num_received);
+    set_inventory_count(&record, "numReceived", num_received).await?;
+    Ok(record)
+}
+
+////////////////////////////////////////////////////////////////////////////
+pub async fn set_inventory_ordered(
+    delivery_id: u32,
+    dist_point_id: &str,
+    product_id: &str,
+    num_ordered: u64,
+) -> Result<InventoryRecord, Box<dyn std::error::Error>> {
+    let mut record = new_inventory_record(delivery_id, dist_point_id, product_id);
+    record.num_ordered = Some(num_ordered);
+    set_inventory_count(&record, "numOrdered", num_ordered).await?;
+    Ok(record)
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Total ordered from the supplier for a product across all of the deliveries
+pub async fn get_total_ordered_from_supplier(
+    product_id: &str,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    Ok(get_inventory_records(None)
+        .await?
+        .iter()
+        .filter(|v| v.product_id == product_id)
+        .filter_map(|v| v.num_ordered)
+        .sum())
+}
+
+////////////////////////////////////////////////////////////////////////////
+static INVENTORY_ALLOCATION_GQL: &str = r"
+{
+  mulchOrders {
+    customer {
+        neighborhood
+    }
+    purchases {
+        productId
+        numSold
+    }
+    deliveryId
+    isWaitlisted
+  }
+}
+";
+
+////////////////////////////////////////////////////////////////////////////
+/// Gets the received, allocated and remaining stock by distribution point and product
+/// for a delivery
+pub async fn get_inventory_report_data(
+    delivery_id: u32,
+) -> Result<Vec<InventoryStatus>, Box<dyn std::error::Error>> {
+    let product_ids = get_inventory_product_ids();
+    let new_status = |dist_point: &str, product_id: &str| InventoryStatus {
+        delivery_id,
+        dist_point: dist_point.to_string(),
+        dist_point_id: get_distribution_point_by_name(dist_point).map(|v| v.id),
+        product_id: product_id.to_string(),
+        ..Default::default()
+    };
+
+    // Every configured point shows up even before anything is ordered so stock can be entered
+    let mut statuses: BTreeMap<(String, String), InventoryStatus> = BTreeMap::new();
+    for dist_point in get_distribution_points().iter() {
+        for product_id in &product_ids {
+            statuses.insert(
+                (dist_point.name.clone(), product_id.clone()),
+                new_status(&dist_point.name, product_id),
+            );
+        }
+    }
+
+    // Waitlisted orders aren't getting anything from this delivery until they are promoted
+    let orders = make_report_query(INVENTORY_ALLOCATION_GQL.to_string()).await?;
+    for v in orders.iter().filter(|v| {
+        v["deliveryId"].as_u64() == Some(delivery_id as u64) && !is_waitlisted_from_report_data(v)
+    }) {
+        let dist_point = v["customer"]["neighborhood"]
+            .as_str()
+            .and_then(get_neighborhood)
+            .map_or("".to_string(), |v| v.get_distribution_point_name());
+        for (product_id, num_sold) in get_purchase_to_map(v) {
+            if !product_ids.contains(&product_id) {
+                continue;
+            }
+            statuses
+                .entry((dist_point.clone(), product_id.clone()))
+                .or_insert_with(|| new_status(&dist_point, &product_id))
+                .num_allocated += num_sold;
+        }
+    }
+
+    for record in get_inventory_records(Some(delivery_id)).await? {
+        let Some(dist_point) = get_distribution_point(&record.dist_point_id).map(|v| v.name) else {
+            continue;
+        };
+        let status = statuses
+            .entry((dist_point.clone(), record.product_id.clone()))
+            .or_insert_with(|| new_status(&dist_point, &record.product_id));
+        status.num_received = record.num_received;
+        status.num_ordered = record.num_ordered;
+    }
+
+    Ok(statuses.into_values().collect())
+}
+
+////////////////////////////////////////////////////////////////////////////
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+pub struct SupplierOrderLine {
+    pub product_id: String,
+    pub num_allocated: u64,
+    pub num_to_order: u64,
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// What to ask the supplier to drop off at a distribution point for a delivery
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+pub struct SupplierOrder {
+    pub delivery_id: u32,
+    pub dist_point: String,
+    pub dist_point_id: Option<String>,
+    pub overage_percent: u32,
+    pub lines: Vec<SupplierOrderLine>,
+}
+
+impl SupplierOrder {
+    pub fn get_dist_point_info(&self) -> Option<DistributionPoint> {
+        self.dist_point_id
+            .as_ref()
+            .and_then(|v| get_distribution_point(v))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Rounds up so the overage never leaves a point a partial bag short
+fn add_overage(num_allocated: u64, overage_percent: u32) -> u64 {
+    (num_allocated * (100 + overage_percent as u64)).div_ceil(100)
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Builds the supplier orders for a delivery from what has been allocated to orders
+/// at each distribution point
+pub async fn gen_supplier_orders(
+    delivery_id: u32,
+    overage_percent: u32,
+) -> Result<Vec<SupplierOrder>, Box<dyn std::error::Error>> {
+    let mut orders: BTreeMap<String, SupplierOrder> = BTreeMap::new();
+    for status in get_inventory_report_data(delivery_id)
+        .await?
+        .into_iter()
+        .filter(|v| v.num_allocated != 0)
+    {
+        orders
+            .entry(status.dist_point.clone())
+            .or_insert_with(|| SupplierOrder {
+                delivery_id,
+                dist_point: status.dist_point.clone(),
+                dist_point_id: status.dist_point_id.clone(),
+                overage_percent,
+                lines: Vec::new(),
+            })
+            .lines
+            .push(SupplierOrderLine {
+                num_to_order: add_overage(status.num_allocated, overage_percent),
+                product_id: status.product_id,
+                num_allocated: status.num_allocated,
+            });
+    }
+    Ok(orders.into_values().collect())
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Records what was ordered so it can be compared to what arrives and what was paid.
+/// This replaces what was recorded for the delivery before so anything no longer being
+/// ordered is set back to 0. Returns the distribution points that couldn't be recorded
+/// because they aren't configured.
+pub async fn record_supplier_orders(
+    delivery_id: u32,
+    orders: &[SupplierOrder],
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut skipped_dist_points = Vec::new();
+    let mut num_to_order: BTreeMap<(String, String), u64> = BTreeMap::new();
+    for order in orders {
+        let Some(dist_point_id) = order.dist_point_id.as_ref() else {
+            warn!(
+                "Not recording supplier order for unconfigured distribution point: {}",
+                &order.dist_point
+            );
+            skipped_dist_points.push(if order.dist_point.is_empty() {
+                "(No Distribution Point)".to_string()
+            } else {
+                order.dist_point.clone()
+            });
+            continue;
+        };
+        for line in &order.lines {
+            num_to_order.insert(
+                (dist_point_id.clone(), line.product_id.clone()),
+                line.num_to_order,
+            );
+        }
+    }
+
+    for record in get_inventory_records(Some(delivery_id)).await? {
+        if record.num_ordered.unwrap_or(0) == 0 {
+            continue;
+        }
+        num_to_order
+            .entry((record.dist_point_id, record.product_id))
+            .or_insert(0);
+    }
+
+    for ((dist_point_id, product_id), num) in num_to_order {
+        set_inventory_ordered(delivery_id, &dist_point_id, &product_id, num).await?;
+    }
+    Ok(skipped_dist_points)
+}
@@ -1,16 +1,19 @@
 use super::{
-    get_active_user,
+    format_fundraiser_datetime, get_active_user, get_delivery_bags_remaining, get_fr_config,
+    get_fundraiser_today,
     gql_utils::{GraphQlReq, make_gql_request},
+    is_delivery_locked, is_fundraiser_finalized, is_fundraiser_locked, is_order_reopened,
     is_valid_delivery_id,
 };
 use crate::currency_utils::*;
+use chrono::prelude::*;
 use regex::Regex;
 use rust_decimal::prelude::*;
 use rusty_money::{Money, iso};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{LazyLock, RwLock};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 static ACTIVE_ORDER: LazyLock<RwLock<Option<ActiveOrderState>>> =
     LazyLock::new(|| RwLock::new(None));
@@ -19,6 +22,8 @@ static CHECKNUM_RE_DELIMETERS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[
 #[derive(Default, Clone, PartialEq, Debug)]
 pub struct ActiveOrderState {
     order: MulchOrder,
+    // Order as it was loaded from the db. Used to figure out what changed for the history.
+    original_order: Option<MulchOrder>,
     is_new_order: bool,
     is_dirty: bool,
 }
@@ -38,10 +43,12 @@ pub struct MulchOrder {
     pub check_numbers: Option<String>,
     pub will_collect_money_later: Option<bool>,
     pub is_verified: Option<bool>,
+    pub is_waitlisted: Option<bool>,
     pub customer: CustomerInfo,
     pub purchases: Option<HashMap<String, PurchasedItem>>,
     pub delivery_id: u32,
     pub year_ordered: Option<String>,
+    pub refunds: Option<Vec<RefundRecord>>,
 }
 
 #[derive(Default, Clone, PartialEq, Debug)]
@@ -58,6 +65,23 @@ impl PurchasedItem {
     }
 }
 
+#[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct RefundRecord {
+    pub amount: String,
+    pub method: String,
+    pub reason: String,
+    #[serde(alias = "refundedBy")]
+    pub refunded_by: String,
+    #[serde(alias = "refundTime")]
+    pub refund_time: String,
+}
+
+impl RefundRecord {
+    pub fn get_refund_time_str(&self) -> String {
+        format_fundraiser_datetime(&self.refund_time, "%m/%d/%Y")
+    }
+}
+
 #[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct CustomerInfo {
     pub name: String,
@@ -86,7 +110,12 @@ impl MulchOrder {
             return false;
         }
 
-        if self.is_verified.unwrap_or(false) {
+        // A reopen gets past verification and the delivery cutoff but never a locked fundraiser
+        if is_order_reopened(&self.order_id) {
+            return is_fundraiser_locked() || is_fundraiser_finalized();
+        }
+
+        if self.is_verified.unwrap_or(false) || is_delivery_locked(self.delivery_id) {
             return true;
         }
 
@@ -98,9 +127,21 @@ impl MulchOrder {
     }
 
     pub fn set_delivery_id(&mut self, delivery_id: u32) {
+        if self.delivery_id != delivery_id && self.is_waitlisted() {
+            // A waitlist spot is only for the date it was given on
+            self.is_waitlisted = Some(false);
+        }
         self.delivery_id = delivery_id;
     }
 
+    pub fn is_waitlisted(&self) -> bool {
+        self.is_waitlisted.unwrap_or(false)
+    }
+
+    pub fn set_waitlisted(&mut self, is_waitlisted: bool) {
+        self.is_waitlisted = Some(is_waitlisted);
+    }
+
     pub fn clear_donations(&mut self) {
         self.amount_from_donations = None;
     }
@@ -163,6 +204,21 @@ impl MulchOrder {
         total
     }
 
+    pub fn get_total_refunded(&self) -> Decimal {
+        let mut total = Decimal::ZERO;
+        for refund in self.refunds.as_ref().unwrap_or(&Vec::new()) {
+            let Some(amount) = parse_money_str_as_decimal(&refund.amount) else {
+                warn!(
+                    "Skipping refund with a bad amount: {} for order: {}",
+                    refund.amount, self.order_id
+                );
+                continue;
+            };
+            total = total.checked_add(amount).unwrap();
+        }
+        total
+    }
+
     pub fn is_payment_valid(&self) -> bool {
         self.is_check_numbers_valid()
             && ((self.get_total_to_collect() != Decimal::ZERO
@@ -211,16 +267,37 @@ pub fn is_order_from_report_data_readonly(j_order: &serde_json::Value) -> bool {
         return false;
     }
 
-    if j_order["isVerified"].as_bool().unwrap_or(false) {
+    if j_order["orderId"].as_str().is_some_and(is_order_reopened) {
+        return is_fundraiser_locked() || is_fundraiser_finalized();
+    }
+
+    let is_delivery_locked = j_order["deliveryId"]
+        .as_u64()
+        .is_some_and(|v| is_delivery_locked(v as u32));
+    if j_order["isVerified"].as_bool().unwrap_or(false) || is_delivery_locked {
         return true;
     }
 
     false
 }
 
+/// Waitlisted orders aren't on a delivery until they are promoted
+pub fn is_waitlisted_from_report_data(j_order: &serde_json::Value) -> bool {
+    j_order["isWaitlisted"].as_bool().unwrap_or(false)
+}
+
+/// Money collected on a cancelled order needs to be paid back to the customer
+pub fn is_refund_due_from_report_data(j_order: &serde_json::Value) -> bool {
+    j_order["amountTotalCollected"]
+        .as_str()
+        .and_then(parse_money_str_as_decimal)
+        .is_some_and(|v| v > Decimal::ZERO)
+}
+
 pub fn create_new_active_order(owner_id: &str) {
     let new_active_order_state = ActiveOrderState {
         order: MulchOrder::new(owner_id),
+        original_order: None,
         is_new_order: true,
         is_dirty: true,
     };
@@ -262,6 +339,20 @@ pub fn update_active_order(order: MulchOrder) -> Result<(), Box<dyn std::error::
     Ok(())
 }
 
+/// Returns how many bags the active order can have on a delivery or None if there is no limit.
+/// The bags already on the active order are freed up so editing an order doesn't count it twice.
+pub fn get_active_order_bags_remaining(delivery_id: u32) -> Option<i64> {
+    let remaining = get_delivery_bags_remaining(delivery_id)?;
+    let order_state_opt = ACTIVE_ORDER.read().unwrap();
+    let num_original_bags = order_state_opt
+        .as_ref()
+        .and_then(|v| v.original_order.as_ref())
+        .filter(|v| v.delivery_id == delivery_id && !v.is_waitlisted())
+        .and_then(|v| v.get_num_sold("bags"))
+        .unwrap_or(0);
+    Some(remaining + num_original_bags as i64)
+}
+
 fn gen_submit_active_order_req_str() -> Result<String, Box<dyn std::error::Error>> {
     let order_state_opt = ACTIVE_ORDER.write()?;
     let order_state = order_state_opt.as_ref().unwrap();
@@ -306,6 +397,10 @@ fn gen_submit_active_order_req_str() -> Result<String, Box<dyn std::error::Error
         query.push_str(&format!("\t\t isVerified: {value}\n"));
     }
 
+    if let Some(value) = order.is_waitlisted.as_ref() {
+        query.push_str(&format!("\t\t isWaitlisted: {value}\n"));
+    }
+
     if let Some(value) = order.amount_total_collected.as_ref() {
         query.push_str(&format!(
             "\t\t amountTotalCollected: \"{}\"\n",
@@ -405,6 +500,25 @@ fn gen_submit_active_order_req_str() -> Result<String, Box<dyn std::error::Error
     Ok(query)
 }
 
+fn gen_active_order_history_event() -> (String, OrderHistoryEvent) {
+    let order_state_opt = ACTIVE_ORDER.read().unwrap();
+    let order_state = order_state_opt.as_ref().unwrap();
+    let (action, changes) = match order_state.original_order.as_ref() {
+        Some(original_order) if !order_state.is_new_order => (
+            "updated",
+            gen_order_field_changes(original_order, &order_state.order),
+        ),
+        _ => (
+            "created",
+            gen_order_field_changes(&MulchOrder::default(), &order_state.order),
+        ),
+    };
+    (
+        order_state.order.order_id.clone(),
+        OrderHistoryEvent::new(action, changes),
+    )
+}
+
 pub async fn submit_active_order() -> Result<(), Box<dyn std::error::Error>> {
     let query = gen_submit_active_order_req_str()?;
 
@@ -414,29 +528,203 @@ pub async fn submit_active_order() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     info!("Submitting Request:\n{}", &query);
+    let (order_id, history_event) = gen_active_order_history_event();
 
     //Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, "TODO Issue")))
     let req = GraphQlReq::new(query);
-    make_gql_request::<serde_json::Value>(&req)
-        .await
-        .map(|_| ())
+    make_gql_request::<serde_json::Value>(&req).await?;
+    record_order_history_event(&order_id, history_event).await;
+    Ok(())
+}
+
+static CANCEL_ORDER_GQL: &str = r#"
+mutation {
+  cancelMulchOrder(
+    ***ORDER_ID_PARAM***,
+    reason: "***REASON_PARAM***",
+    cancelledBy: "***USER_PARAM***",
+    cancelledTime: "***TIME_PARAM***"
+  )
+}
+"#;
+
+/// Cancelling is a soft delete. The order is kept (and hidden from the normal reports) so it
+/// can be restored or refunded later.
+pub async fn cancel_order(order_id: &str, reason: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let query = CANCEL_ORDER_GQL
+        .replace("***ORDER_ID_PARAM***", &format!("orderId: \"{order_id}\""))
+        .replace(
+            "***REASON_PARAM***",
+            &reason.trim().replace("\"", "\\\"").replace("\n", r"\n"),
+        )
+        .replace("***USER_PARAM***", &get_active_user().get_id())
+        .replace("***TIME_PARAM***", &Utc::now().to_rfc3339());
+
+    let req = GraphQlReq::new(query);
+    info!("Cancel GraphQL: {}", &req.query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+    record_order_history_event(
+        order_id,
+        OrderHistoryEvent::new(
+            "cancelled",
+            vec![OrderFieldChange::new("reason", "", reason.trim())],
+        ),
+    )
+    .await;
+    Ok(())
 }
 
-static DELETE_ORDER_GQL: &str = r"
+static RESTORE_ORDER_GQL: &str = r"
 mutation {
-  deleteMulchOrder(***ORDER_ID_PARAM***)
+  restoreMulchOrder(***ORDER_ID_PARAM***)
 }
 ";
 
-pub async fn delete_order(order_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn restore_order(order_id: &str) -> Result<(), Box<dyn std::error::Error>> {
     let query =
-        DELETE_ORDER_GQL.replace("***ORDER_ID_PARAM***", &format!("orderId: \"{order_id}\""));
+        RESTORE_ORDER_GQL.replace("***ORDER_ID_PARAM***", &format!("orderId: \"{order_id}\""));
 
     let req = GraphQlReq::new(query);
-    info!("Delete GraphQL: {}", &req.query);
-    make_gql_request::<serde_json::Value>(&req)
-        .await
-        .map(|_| ())
+    info!("Restore GraphQL: {}", &req.query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+    record_order_history_event(order_id, OrderHistoryEvent::new("restored", Vec::new())).await;
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////
+#[derive(Serialize, Deserialize, Default, Copy, Clone, PartialEq, Eq, Debug, PartialOrd, Ord)]
+pub enum DeliveryStatus {
+    #[default]
+    Scheduled,
+    Loaded,
+    OutForDelivery,
+    Delivered,
+    Problem,
+}
+
+impl DeliveryStatus {
+    pub fn all() -> [DeliveryStatus; 5] {
+        [
+            DeliveryStatus::Scheduled,
+            DeliveryStatus::Loaded,
+            DeliveryStatus::OutForDelivery,
+            DeliveryStatus::Delivered,
+            DeliveryStatus::Problem,
+        ]
+    }
+}
+
+impl std::fmt::Display for DeliveryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            DeliveryStatus::Scheduled => write!(f, "Scheduled"),
+            DeliveryStatus::Loaded => write!(f, "Loaded"),
+            DeliveryStatus::OutForDelivery => write!(f, "Out For Delivery"),
+            DeliveryStatus::Delivered => write!(f, "Delivered"),
+            DeliveryStatus::Problem => write!(f, "Problem"),
+        }
+    }
+}
+
+impl std::str::FromStr for DeliveryStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Scheduled" => Ok(DeliveryStatus::Scheduled),
+            "Loaded" => Ok(DeliveryStatus::Loaded),
+            "Out For Delivery" | "OutForDelivery" => Ok(DeliveryStatus::OutForDelivery),
+            "Delivered" => Ok(DeliveryStatus::Delivered),
+            "Problem" => Ok(DeliveryStatus::Problem),
+            _ => Err(format!("'{s}' is not a valid value for DeliveryStatus")),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+pub struct DeliveryStatusRecord {
+    pub status: DeliveryStatus,
+    pub notes: Option<String>,
+    #[serde(alias = "updatedBy")]
+    pub updated_by: String,
+    #[serde(alias = "updateTime")]
+    pub update_time: String,
+}
+
+impl DeliveryStatusRecord {
+    pub fn get_update_time_str(&self) -> String {
+        format_fundraiser_datetime(&self.update_time, "%I:%M %p")
+    }
+}
+
+/// Orders that haven't had a status set yet are considered scheduled
+pub fn get_delivery_status_from_report_data(v: &serde_json::Value) -> DeliveryStatus {
+    v["deliveryStatus"]["status"]
+        .as_str()
+        .and_then(|v| v.parse::<DeliveryStatus>().ok())
+        .unwrap_or_default()
+}
+
+static SET_DELIVERY_STATUS_GQL: &str = r#"
+mutation {
+  setMulchOrderDeliveryStatus(
+    ***ORDER_ID_PARAM***,
+    deliveryStatus: {
+      status: ***STATUS_PARAM***,
+      notes: "***NOTES_PARAM***",
+      updatedBy: "***USER_PARAM***",
+      updateTime: "***TIME_PARAM***"
+    }
+  )
+}
+"#;
+
+pub async fn set_order_delivery_status(
+    order_id: &str,
+    prev_status: DeliveryStatus,
+    status: DeliveryStatus,
+    notes: Option<String>,
+) -> Result<DeliveryStatusRecord, Box<dyn std::error::Error>> {
+    let record = DeliveryStatusRecord {
+        status,
+        notes: notes
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty()),
+        updated_by: get_active_user().get_id(),
+        update_time: Utc::now().to_rfc3339(),
+    };
+    let query = SET_DELIVERY_STATUS_GQL
+        .replace("***ORDER_ID_PARAM***", &format!("orderId: \"{order_id}\""))
+        .replace("***STATUS_PARAM***", &format!("{status:?}"))
+        .replace(
+            "***NOTES_PARAM***",
+            &record
+                .notes
+                .as_deref()
+                .unwrap_or("")
+                .replace("\"", "\\\"")
+                .replace("\n", r"\n"),
+        )
+        .replace("***USER_PARAM***", &record.updated_by)
+        .replace("***TIME_PARAM***", &record.update_time);
+
+    let req = GraphQlReq::new(query);
+    info!("Delivery Status GraphQL: {}", &req.query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+    record_order_history_event(
+        order_id,
+        OrderHistoryEvent::new(
+            "deliveryStatus",
+            vec![OrderFieldChange::new(
+                "deliveryStatus",
+                &prev_status.to_string(),
+                &status.to_string(),
+            )],
+        ),
+    )
+    .await;
+    Ok(record)
 }
 
 static LOAD_ORDER_GQL: &str = r"
@@ -452,6 +740,7 @@ static LOAD_ORDER_GQL: &str = r"
     amountTotalCollected
     willCollectMoneyLater
     isVerified
+    isWaitlisted
     customer {
         name
         addr1
@@ -470,6 +759,13 @@ static LOAD_ORDER_GQL: &str = r"
         numSold
         amountCharged
     }
+    refunds {
+        amount
+        method
+        reason
+        refundedBy
+        refundTime
+    }
   }
 }
 ";
@@ -506,10 +802,13 @@ pub async fn load_active_order_from_db(order_id: &str) -> Result<(), Box<dyn std
         pub will_collect_money_later: Option<bool>,
         #[serde(alias = "isVerified")]
         pub is_verified: Option<bool>,
+        #[serde(alias = "isWaitlisted")]
+        pub is_waitlisted: Option<bool>,
         pub customer: CustomerInfo,
         pub purchases: Option<Vec<PurchasedItemApi>>,
         #[serde(alias = "deliveryId")]
         pub delivery_id: u32,
+        pub refunds: Option<Vec<RefundRecord>>,
     }
 
     #[derive(Deserialize, Debug)]
@@ -530,6 +829,7 @@ pub async fn load_active_order_from_db(order_id: &str) -> Result<(), Box<dyn std
     let order = resp.mulch_order;
 
     let new_active_order_state = ActiveOrderState {
+        original_order: None,
         order: MulchOrder {
             order_id: order.order_id,
             order_owner_id: order.order_owner_id,
@@ -543,8 +843,10 @@ pub async fn load_active_order_from_db(order_id: &str) -> Result<(), Box<dyn std
             amount_total_collected: from_cloud_to_money_str(order.amount_total_collected),
             will_collect_money_later: order.will_collect_money_later,
             is_verified: order.is_verified,
+            is_waitlisted: order.is_waitlisted,
             customer: order.customer,
             delivery_id: order.delivery_id,
+            refunds: order.refunds,
             purchases: order.purchases.map(|v| {
                 v.into_iter()
                     .map(|i| {
@@ -563,42 +865,759 @@ pub async fn load_active_order_from_db(order_id: &str) -> Result<(), Box<dyn std
         is_new_order: false,
         is_dirty: false,
     };
+    let new_active_order_state = ActiveOrderState {
+        original_order: Some(new_active_order_state.order.clone()),
+        ..new_active_order_state
+    };
 
     *ACTIVE_ORDER.write().unwrap() = Some(new_active_order_state);
     Ok(())
 }
 
+static ADD_REFUND_GQL: &str = r#"
+mutation {
+  addMulchOrderRefund(
+    ***ORDER_ID_PARAM***,
+    refund: {
+      amount: "***AMOUNT_PARAM***",
+      method: "***METHOD_PARAM***",
+      reason: "***REASON_PARAM***",
+      refundedBy: "***USER_PARAM***",
+      refundTime: "***TIME_PARAM***"
+    }
+  )
+}
+"#;
+
+/// Records money paid back to the customer. The refunded_by/refund_time fields of the
+/// refund are filled in here and the completed record is returned.
+pub async fn add_order_refund(
+    order_id: &str,
+    prev_refunds: &[RefundRecord],
+    refund: RefundRecord,
+) -> Result<RefundRecord, Box<dyn std::error::Error>> {
+    let refund = RefundRecord {
+        refunded_by: get_active_user().get_id(),
+        refund_time: Utc::now().to_rfc3339(),
+        ..refund
+    };
+    let query = ADD_REFUND_GQL
+        .replace("***ORDER_ID_PARAM***", &format!("orderId: \"{order_id}\""))
+        .replace("***AMOUNT_PARAM***", refund.amount.trim())
+        .replace("***METHOD_PARAM***", refund.method.trim())
+        .replace(
+            "***REASON_PARAM***",
+            &refund
+                .reason
+                .trim()
+                .replace("\"", "\\\"")
+                .replace("\n", r"\n"),
+        )
+        .replace("***USER_PARAM***", &refund.refunded_by)
+        .replace("***TIME_PARAM***", &refund.refund_time);
+
+    let req = GraphQlReq::new(query);
+    info!("Add Refund GraphQL: {}", &req.query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+
+    let get_total_refunded = |refunds: Vec<RefundRecord>| {
+        MulchOrder {
+            refunds: Some(refunds),
+            ..Default::default()
+        }
+        .get_total_refunded()
+        .to_string()
+    };
+    let mut refunds = prev_refunds.to_vec();
+    refunds.push(refund.clone());
+    record_order_history_event(
+        order_id,
+        OrderHistoryEvent::new(
+            "refunded",
+            vec![
+                OrderFieldChange::new(
+                    "refunds",
+                    &get_total_refunded(prev_refunds.to_vec()),
+                    &get_total_refunded(refunds),
+                ),
+                OrderFieldChange::new("reason", "", refund.reason.trim()),
+            ],
+        ),
+    )
+    .await;
+    Ok(refund)
+}
+
 static SET_SPREADERS_GQL: &str = r"
 mutation {
   setSpreaders(
     ***ORDER_ID_PARAM***,
-    spreaders: [***SPREADERS_PARAM***]
+    spreaders: [***SPREADERS_PARAM***],
+    spreaderBags: [***SPREADER_BAGS_PARAM***],
+    spreadingCompletion: ***COMPLETION_PARAM***
   )
 }
 ";
 
+/// How many of an order's spreading bags one spreader did
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+pub struct SpreaderBags {
+    pub uid: String,
+    #[serde(alias = "numBags")]
+    pub num_bags: u64,
+}
+
+/// The split as it is kept in the report table data attributes ("uid:bags,uid:bags")
+pub fn spreader_bags_to_str(spreader_bags: &[SpreaderBags]) -> String {
+    spreader_bags
+        .iter()
+        .map(|v| format!("{}:{}", v.uid, v.num_bags))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+pub fn spreader_bags_from_str(spreader_bags_str: &str) -> Vec<SpreaderBags> {
+    spreader_bags_str
+        .split(',')
+        .filter_map(|v| v.split_once(':'))
+        .filter_map(|(uid, num_bags)| {
+            num_bags.parse::<u64>().ok().map(|num_bags| SpreaderBags {
+                uid: uid.to_string(),
+                num_bags,
+            })
+        })
+        .collect()
+}
+
+/// When and how a spreading job was finished
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+pub struct SpreadingCompletion {
+    #[serde(alias = "completedDate")]
+    pub completed_date: String,
+    // Customer sign-off or what the customer said was wrong
+    #[serde(default)]
+    pub notes: String,
+    #[serde(alias = "hasIssue", default)]
+    pub has_issue: bool,
+}
+
+impl SpreadingCompletion {
+    /// Completed today with no notes
+    pub fn completed_today() -> Self {
+        Self {
+            completed_date: get_fundraiser_today().to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Orders spread before completion was recorded don't have a date
+    pub fn to_report_str(&self) -> String {
+        let completed_date = if self.completed_date.is_empty() {
+            "Unknown Date"
+        } else {
+            &self.completed_date
+        };
+        if self.has_issue {
+            format!("{completed_date} (issue)")
+        } else {
+            completed_date.to_string()
+        }
+    }
+}
+
+/// An empty spreader_bags means the bags were split evenly between the spreaders.
+/// Setting the spreaders records the job as completed, no completion means it isn't spread yet.
 pub async fn set_spreaders(
     order_id: &str,
+    prev_spreaders: &[String],
     spreaders: &Vec<String>,
+    prev_spreader_bags: &[SpreaderBags],
+    spreader_bags: &[SpreaderBags],
+    prev_completion: Option<&SpreadingCompletion>,
+    completion: Option<&SpreadingCompletion>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     info!(
         "Setting Spreaders for order id: {}:{:#?}",
         order_id, &spreaders
     );
-    let spreaders = spreaders
+    let spreaders_param = spreaders
         .iter()
         .map(|v| format!("\"{v}\""))
         .collect::<Vec<String>>()
         .join(",");
+    let spreader_bags_param = spreader_bags
+        .iter()
+        .map(|v| format!("{{uid: \"{}\", numBags: {}}}", v.uid, v.num_bags))
+        .collect::<Vec<String>>()
+        .join(",");
+    let completion_param = completion.map_or("null".to_string(), |v| {
+        // Need to replace/escape quotes.
+        format!(
+            "{{completedDate: \"{}\", notes: \"{}\", hasIssue: {}}}",
+            v.completed_date,
+            v.notes.trim().replace("\"", "\\\"").replace("\n", r"\n"),
+            v.has_issue
+        )
+    });
     let query = SET_SPREADERS_GQL
         .replace("***ORDER_ID_PARAM***", &format!("orderId: \"{order_id}\""))
-        .replace("***SPREADERS_PARAM***", &spreaders);
+        .replace("***SPREADERS_PARAM***", &spreaders_param)
+        .replace("***SPREADER_BAGS_PARAM***", &spreader_bags_param)
+        .replace("***COMPLETION_PARAM***", &completion_param);
 
     let req = GraphQlReq::new(query);
     info!("Setting Spreaders GraphQL: {}", &req.query);
-    make_gql_request::<serde_json::Value>(&req)
-        .await
-        .map(|_| ())
+    make_gql_request::<serde_json::Value>(&req).await?;
+    let completion_str = |completion: Option<&SpreadingCompletion>| {
+        completion.map_or("".to_string(), |v| {
+            format!("{}: {}", v.to_report_str(), v.notes.trim())
+        })
+    };
+    let changes = gen_field_changes([
+        ("spreaders", prev_spreaders.join(","), spreaders.join(",")),
+        (
+            "spreaderBags",
+            spreader_bags_to_str(prev_spreader_bags),
+            spreader_bags_to_str(spreader_bags),
+        ),
+        (
+            "spreadingCompletion",
+            completion_str(prev_completion),
+            completion_str(completion),
+        ),
+    ]);
+    record_order_history_event(order_id, OrderHistoryEvent::new("spreaders", changes)).await;
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// A scout signed up to spread an order before it is spread
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+pub struct SpreadingJobClaim {
+    pub uid: String,
+    // Claims a scout makes for themselves wait on an admin to approve them
+    #[serde(alias = "isApproved")]
+    pub is_approved: bool,
+}
+
+pub fn spreading_claims_to_str(claims: &[SpreadingJobClaim]) -> String {
+    claims
+        .iter()
+        .map(|v| {
+            if v.is_approved {
+                v.uid.clone()
+            } else {
+                format!("{}(pending)", v.uid)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// A spreading order that hasn't had its spreaders set yet
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct SpreadingJob {
+    pub order_id: String,
+    pub owner_id: String,
+    pub delivery_id: Option<u32>,
+    pub address: String,
+    pub neighborhood: String,
+    pub special_instructions: String,
+    pub num_bags: u64,
+    pub claims: Vec<SpreadingJobClaim>,
+}
+
+impl SpreadingJob {
+    pub fn is_claimed_by(&self, uid: &str) -> bool {
+        self.claims.iter().any(|v| v.uid == uid)
+    }
+}
+
+static SPREADING_JOB_BOARD_GQL: &str = r"
+{
+  mulchOrders(doGetSpreadOrdersOnly: true) {
+    orderId
+    ownerId
+    deliveryId
+    specialInstructions
+    customer {
+        addr1
+        addr2
+        neighborhood
+    }
+    purchases {
+        productId
+        numSold
+    }
+    spreaders
+    spreadingCompletion {
+        completedDate
+    }
+    spreadingClaims {
+        uid
+        isApproved
+    }
+  }
+}
+";
+
+/// Spreading orders that haven't been completed yet sorted by date and neighborhood
+pub async fn get_spreading_job_board() -> Result<Vec<SpreadingJob>, Box<dyn std::error::Error>> {
+    #[derive(Deserialize, Debug)]
+    struct CustomerApi {
+        addr1: String,
+        addr2: Option<String>,
+        neighborhood: Option<String>,
+    }
+    #[derive(Deserialize, Debug)]
+    struct PurchaseApi {
+        #[serde(alias = "productId")]
+        product_id: String,
+        #[serde(alias = "numSold")]
+        num_sold: u64,
+    }
+    #[derive(Deserialize, Debug)]
+    struct OrderApi {
+        #[serde(alias = "orderId")]
+        order_id: String,
+        #[serde(alias = "ownerId")]
+        owner_id: String,
+        #[serde(alias = "deliveryId")]
+        delivery_id: Option<u32>,
+        #[serde(alias = "specialInstructions")]
+        special_instructions: Option<String>,
+        customer: CustomerApi,
+        #[serde(default)]
+        purchases: Vec<PurchaseApi>,
+        #[serde(default)]
+        spreaders: Vec<String>,
+        #[serde(alias = "spreadingCompletion", default)]
+        spreading_completion: Option<SpreadingCompletion>,
+        #[serde(alias = "spreadingClaims", default)]
+        spreading_claims: Option<Vec<SpreadingJobClaim>>,
+    }
+    #[derive(Deserialize, Debug)]
+    struct RespApi {
+        #[serde(alias = "mulchOrders")]
+        orders: Vec<OrderApi>,
+    }
+
+    let req = GraphQlReq::new(SPREADING_JOB_BOARD_GQL);
+    let resp = make_gql_request::<RespApi>(&req).await?;
+    let mut jobs = resp
+        .orders
+        .into_iter()
+        // Legacy orders only have spreaders set once they are spread
+        .filter(|v| v.spreading_completion.is_none() && v.spreaders.iter().all(|v| v.is_empty()))
+        .filter_map(|v| {
+            let num_bags = v
+                .purchases
+                .iter()
+                .filter(|p| p.product_id == "spreading")
+                .map(|p| p.num_sold)
+                .sum::<u64>();
+            if 0 == num_bags {
+                return None;
+            }
+            Some(SpreadingJob {
+                order_id: v.order_id,
+                owner_id: v.owner_id,
+                delivery_id: v.delivery_id,
+                address: format!(
+                    "{} {}",
+                    v.customer.addr1,
+                    v.customer.addr2.unwrap_or_default()
+                )
+                .trim()
+                .to_string(),
+                neighborhood: v.customer.neighborhood.unwrap_or_default(),
+                special_instructions: v.special_instructions.unwrap_or_default(),
+                num_bags,
+                claims: v.spreading_claims.unwrap_or_default(),
+            })
+        })
+        .collect::<Vec<SpreadingJob>>();
+    jobs.sort_by(|a, b| {
+        (a.delivery_id, &a.neighborhood, &a.address).cmp(&(
+            b.delivery_id,
+            &b.neighborhood,
+            &b.address,
+        ))
+    });
+    Ok(jobs)
+}
+
+static SET_SPREADING_CLAIM_GQL: &str = r#"
+mutation {
+  setSpreadingClaim(orderId: "***ORDER_ID_PARAM***", uid: "***UID_PARAM***", isApproved: ***APPROVED_PARAM***)
+}
+"#;
+
+static REMOVE_SPREADING_CLAIM_GQL: &str = r#"
+mutation {
+  removeSpreadingClaim(orderId: "***ORDER_ID_PARAM***", uid: "***UID_PARAM***")
+}
+"#;
+
+/// Finds a job on a freshly loaded board so changes are made against what is saved now
+/// and not what was on the screen
+async fn get_current_spreading_job(
+    order_id: &str,
+) -> Result<(Vec<SpreadingJob>, SpreadingJob), Box<dyn std::error::Error>> {
+    let jobs = get_spreading_job_board().await?;
+    let job = jobs
+        .iter()
+        .find(|v| v.order_id == order_id)
+        .cloned()
+        .ok_or_else(|| std::io::Error::other("This spreading job is no longer on the board"))?;
+    Ok((jobs, job))
+}
+
+async fn record_spreading_claims_change(
+    order_id: &str,
+    prev_claims: &[SpreadingJobClaim],
+    claims: &[SpreadingJobClaim],
+) {
+    record_order_history_event(
+        order_id,
+        OrderHistoryEvent::new(
+            "spreadingClaims",
+            vec![OrderFieldChange::new(
+                "spreadingClaims",
+                &spreading_claims_to_str(prev_claims),
+                &spreading_claims_to_str(claims),
+            )],
+        ),
+    )
+    .await;
+}
+
+/// Adds or updates one user's claim on a job. Nobody else's claim is touched.
+async fn save_spreading_claim(
+    job: &SpreadingJob,
+    claim: SpreadingJobClaim,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let query = SET_SPREADING_CLAIM_GQL
+        .replace("***ORDER_ID_PARAM***", &job.order_id)
+        .replace("***UID_PARAM***", &claim.uid)
+        .replace("***APPROVED_PARAM***", &claim.is_approved.to_string());
+    let req = GraphQlReq::new(query);
+    info!("Setting Spreading Claim GraphQL: {}", &req.query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+
+    let mut claims = job.claims.clone();
+    match claims.iter_mut().find(|v| v.uid == claim.uid) {
+        Some(existing) => *existing = claim,
+        None => claims.push(claim),
+    }
+    record_spreading_claims_change(&job.order_id, &job.claims, &claims).await;
+    Ok(())
+}
+
+/// Takes one user off of a job. Nobody else's claim is touched.
+async fn delete_spreading_claim(
+    job: &SpreadingJob,
+    uid: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let query = REMOVE_SPREADING_CLAIM_GQL
+        .replace("***ORDER_ID_PARAM***", &job.order_id)
+        .replace("***UID_PARAM***", uid);
+    let req = GraphQlReq::new(query);
+    info!("Removing Spreading Claim GraphQL: {}", &req.query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+
+    let claims = job
+        .claims
+        .iter()
+        .filter(|v| v.uid != uid)
+        .cloned()
+        .collect::<Vec<SpreadingJobClaim>>();
+    record_spreading_claims_change(&job.order_id, &job.claims, &claims).await;
+    Ok(())
+}
+
+/// Signs the active user up for a job as long as they haven't hit the claim limit.
+/// Returns the board as it is after the claim.
+pub async fn claim_spreading_job(
+    order_id: &str,
+) -> Result<Vec<SpreadingJob>, Box<dyn std::error::Error>> {
+    let uid = get_active_user().get_id();
+    // The limit is checked against what is saved so claims made in another tab count
+    let (jobs, job) = get_current_spreading_job(order_id).await?;
+    if job.is_claimed_by(&uid) {
+        return Ok(jobs);
+    }
+    let max_claims = get_fr_config().max_spreading_job_claims as usize;
+    let num_claims = jobs.iter().filter(|v| v.is_claimed_by(&uid)).count();
+    if num_claims >= max_claims {
+        return Err(Box::new(std::io::Error::other(format!(
+            "You already have {num_claims} spreading jobs claimed and the limit is {max_claims}"
+        ))));
+    }
+    let claim = SpreadingJobClaim {
+        uid,
+        is_approved: get_active_user().is_admin(),
+    };
+    save_spreading_claim(&job, claim).await?;
+    get_spreading_job_board().await
+}
+
+/// Takes the active user off of a job they claimed. Returns the board as it is after.
+pub async fn release_spreading_job(
+    order_id: &str,
+) -> Result<Vec<SpreadingJob>, Box<dyn std::error::Error>> {
+    let (_, job) = get_current_spreading_job(order_id).await?;
+    delete_spreading_claim(&job, &get_active_user().get_id()).await?;
+    get_spreading_job_board().await
+}
+
+/// Used by admins to approve a claim a scout made. Returns the board as it is after.
+pub async fn approve_spreading_job_claim(
+    order_id: &str,
+    uid: &str,
+) -> Result<Vec<SpreadingJob>, Box<dyn std::error::Error>> {
+    let (_, job) = get_current_spreading_job(order_id).await?;
+    if job.is_claimed_by(uid) {
+        let claim = SpreadingJobClaim {
+            uid: uid.to_string(),
+            is_approved: true,
+        };
+        save_spreading_claim(&job, claim).await?;
+    }
+    get_spreading_job_board().await
+}
+
+/// Used by admins to take someone off of a job. Returns the board as it is after.
+pub async fn remove_spreading_job_claim(
+    order_id: &str,
+    uid: &str,
+) -> Result<Vec<SpreadingJob>, Box<dyn std::error::Error>> {
+    let (_, job) = get_current_spreading_job(order_id).await?;
+    if job.is_claimed_by(uid) {
+        delete_spreading_claim(&job, uid).await?;
+    }
+    get_spreading_job_board().await
+}
+
+/// Used by admins to give a job to one user. Anyone else signed up is taken off of it.
+/// Returns the board as it is after.
+pub async fn reassign_spreading_job(
+    order_id: &str,
+    uid: &str,
+) -> Result<Vec<SpreadingJob>, Box<dyn std::error::Error>> {
+    let (_, mut job) = get_current_spreading_job(order_id).await?;
+    for other_uid in job
+        .claims
+        .iter()
+        .filter(|v| v.uid != uid)
+        .map(|v| v.uid.clone())
+        .collect::<Vec<String>>()
+    {
+        delete_spreading_claim(&job, &other_uid).await?;
+        job.claims.retain(|v| v.uid != other_uid);
+    }
+    let claim = SpreadingJobClaim {
+        uid: uid.to_string(),
+        is_approved: true,
+    };
+    save_spreading_claim(&job, claim).await?;
+    get_spreading_job_board().await
+}
+
+static DELIVERY_ORDERS_GQL: &str = r"
+{
+  mulchOrders {
+    orderId
+    ownerId
+    customer {
+        name
+        addr1
+        addr2
+        city
+        zipcode
+        phone
+        email
+        neighborhood
+    }
+    deliveryId
+    isWaitlisted
+    spreaders
+  }
+}
+";
+
+#[derive(Default, Clone, PartialEq, Debug, Deserialize)]
+pub struct DeliveryOrderInfo {
+    #[serde(alias = "orderId")]
+    pub order_id: String,
+    #[serde(alias = "ownerId")]
+    pub order_owner_id: String,
+    pub customer: CustomerInfo,
+    #[serde(alias = "deliveryId")]
+    pub delivery_id: Option<u32>,
+    #[serde(alias = "isWaitlisted", default)]
+    pub is_waitlisted: Option<bool>,
+    pub spreaders: Option<Vec<String>>,
+}
+
+pub async fn get_orders_for_delivery(
+    delivery_id: u32,
+) -> Result<Vec<DeliveryOrderInfo>, Box<dyn std::error::Error>> {
+    #[derive(Deserialize, Debug)]
+    struct RespWrapper {
+        #[serde(alias = "mulchOrders")]
+        mulch_orders: Vec<DeliveryOrderInfo>,
+    }
+
+    // Waitlisted orders stay on the waitlist until they are promoted so they aren't
+    // part of what is delivered or rescheduled
+    let req = GraphQlReq::new(DELIVERY_ORDERS_GQL);
+    make_gql_request::<RespWrapper>(&req).await.map(|v| {
+        v.mulch_orders
+            .into_iter()
+            .filter(|order| order.delivery_id == Some(delivery_id))
+            .filter(|order| !order.is_waitlisted.unwrap_or(false))
+            .collect()
+    })
+}
+
+static RESCHEDULE_ORDERS_GQL: &str = r"
+mutation {
+  rescheduleMulchOrders(
+    orderIds: [***ORDER_IDS_PARAM***],
+    deliveryId: ***DELIVERY_ID_PARAM***
+  )
+}
+";
+
+pub async fn reschedule_orders(
+    orders: &[DeliveryOrderInfo],
+    to_delivery_id: u32,
+) -> Result<(), Box<dyn std::error::Error>> {
+    info!(
+        "Rescheduling {} orders to delivery id: {}",
+        orders.len(),
+        to_delivery_id
+    );
+    // Only the deliveryId is changed so spreading assignments stay with the order
+    let order_ids = orders
+        .iter()
+        .map(|v| format!("\"{}\"", v.order_id))
+        .collect::<Vec<String>>()
+        .join(",");
+    let query = RESCHEDULE_ORDERS_GQL
+        .replace("***ORDER_IDS_PARAM***", &order_ids)
+        .replace("***DELIVERY_ID_PARAM***", &to_delivery_id.to_string());
+
+    let req = GraphQlReq::new(query);
+    info!("Reschedule Orders GraphQL: {}", &req.query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+    for order in orders {
+        record_order_history_event(
+            &order.order_id,
+            OrderHistoryEvent::new(
+                "rescheduled",
+                vec![OrderFieldChange::new(
+                    "deliveryId",
+                    &order.delivery_id.map_or("".to_string(), |v| v.to_string()),
+                    &to_delivery_id.to_string(),
+                )],
+            ),
+        )
+        .await;
+    }
+    Ok(())
+}
+
+static WAITLISTED_ORDERS_GQL: &str = r"
+{
+  mulchOrders(doGetWaitlistedOnly: true) {
+    orderId
+    ownerId
+    customer {
+        name
+        addr1
+        addr2
+        city
+        zipcode
+        phone
+        email
+        neighborhood
+    }
+    deliveryId
+    spreaders
+  }
+}
+";
+
+pub async fn get_waitlisted_orders_for_delivery(
+    delivery_id: u32,
+) -> Result<Vec<DeliveryOrderInfo>, Box<dyn std::error::Error>> {
+    #[derive(Deserialize, Debug)]
+    struct RespWrapper {
+        #[serde(alias = "mulchOrders")]
+        mulch_orders: Vec<DeliveryOrderInfo>,
+    }
+
+    let req = GraphQlReq::new(WAITLISTED_ORDERS_GQL);
+    make_gql_request::<RespWrapper>(&req).await.map(|v| {
+        v.mulch_orders
+            .into_iter()
+            .filter(|order| order.delivery_id == Some(delivery_id))
+            .collect()
+    })
+}
+
+static PROMOTE_WAITLISTED_ORDERS_GQL: &str = r"
+mutation {
+  promoteWaitlistedMulchOrders(
+    orderIds: [***ORDER_IDS_PARAM***],
+    deliveryId: ***DELIVERY_ID_PARAM***
+  )
+}
+";
+
+/// Moves orders off of the waitlist and onto a delivery that has room for them
+pub async fn promote_waitlisted_orders(
+    orders: &[DeliveryOrderInfo],
+    to_delivery_id: u32,
+) -> Result<(), Box<dyn std::error::Error>> {
+    info!(
+        "Promoting {} waitlisted orders to delivery id: {}",
+        orders.len(),
+        to_delivery_id
+    );
+    let order_ids = orders
+        .iter()
+        .map(|v| format!("\"{}\"", v.order_id))
+        .collect::<Vec<String>>()
+        .join(",");
+    let query = PROMOTE_WAITLISTED_ORDERS_GQL
+        .replace("***ORDER_IDS_PARAM***", &order_ids)
+        .replace("***DELIVERY_ID_PARAM***", &to_delivery_id.to_string());
+
+    let req = GraphQlReq::new(query);
+    info!("Promote Waitlisted Orders GraphQL: {}", &req.query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+    for order in orders {
+        record_order_history_event(
+            &order.order_id,
+            OrderHistoryEvent::new(
+                "promoted",
+                vec![
+                    OrderFieldChange::new("isWaitlisted", "true", "false"),
+                    OrderFieldChange::new(
+                        "deliveryId",
+                        &order.delivery_id.map_or("".to_string(), |v| v.to_string()),
+                        &to_delivery_id.to_string(),
+                    ),
+                ],
+            ),
+        )
+        .await;
+    }
+    Ok(())
 }
 
 static TROOP_ORDER_AMOUNT_COLLECTED_GQL: &str = r"
@@ -620,3 +1639,264 @@ pub async fn have_orders_been_created() -> Result<bool, Box<dyn std::error::Erro
             .map_or_else(|| true, |i| i != "0")
     })
 }
+
+////////////////////////////////////////////////////////////////////////////
+// Order History
+////////////////////////////////////////////////////////////////////////////
+#[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct OrderFieldChange {
+    pub field: String,
+    pub from: String,
+    pub to: String,
+}
+
+impl OrderFieldChange {
+    pub(crate) fn new(field: &str, from: &str, to: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+}
+
+#[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct OrderHistoryEvent {
+    pub action: String,
+    #[serde(alias = "changedBy")]
+    pub changed_by: String,
+    #[serde(alias = "changeTime")]
+    pub change_time: String,
+    pub changes: Vec<OrderFieldChange>,
+}
+
+impl OrderHistoryEvent {
+    pub(crate) fn new(action: &str, changes: Vec<OrderFieldChange>) -> Self {
+        Self {
+            action: action.to_string(),
+            changed_by: get_active_user().get_id(),
+            change_time: Utc::now().to_rfc3339(),
+            changes,
+        }
+    }
+
+    pub fn get_change_time_str(&self) -> String {
+        format_fundraiser_datetime(&self.change_time, "%m/%d/%Y %I:%M %p")
+    }
+}
+
+/// Compares the user editable fields of the two orders and returns what changed
+fn gen_order_field_changes(before: &MulchOrder, after: &MulchOrder) -> Vec<OrderFieldChange> {
+    fn opt_str<T: ToString>(v: &Option<T>) -> String {
+        v.as_ref().map_or("".to_string(), |v| v.to_string())
+    }
+    fn purchases_str(order: &MulchOrder) -> String {
+        let mut purchases = order
+            .purchases
+            .as_ref()
+            .map(|v| {
+                v.iter()
+                    .map(|(product_id, item)| {
+                        format!("{product_id}: {} ({})", item.num_sold, item.amount_charged)
+                    })
+                    .collect::<Vec<String>>()
+            })
+            .unwrap_or_default();
+        purchases.sort();
+        purchases.join(", ")
+    }
+
+    let fields = [
+        (
+            "ownerId",
+            before.order_owner_id.clone(),
+            after.order_owner_id.clone(),
+        ),
+        (
+            "name",
+            before.customer.name.clone(),
+            after.customer.name.clone(),
+        ),
+        (
+            "phone",
+            before.customer.phone.clone(),
+            after.customer.phone.clone(),
+        ),
+        (
+            "email",
+            opt_str(&before.customer.email),
+            opt_str(&after.customer.email),
+        ),
+        (
+            "addr1",
+            before.customer.addr1.clone(),
+            after.customer.addr1.clone(),
+        ),
+        (
+            "addr2",
+            opt_str(&before.customer.addr2),
+            opt_str(&after.customer.addr2),
+        ),
+        (
+            "city",
+            opt_str(&before.customer.city),
+            opt_str(&after.customer.city),
+        ),
+        (
+            "zipcode",
+            opt_str(&before.customer.zipcode),
+            opt_str(&after.customer.zipcode),
+        ),
+        (
+            "neighborhood",
+            opt_str(&before.customer.neighborhood),
+            opt_str(&after.customer.neighborhood),
+        ),
+        (
+            "specialInstructions",
+            opt_str(&before.special_instructions),
+            opt_str(&after.special_instructions),
+        ),
+        (
+            "comments",
+            opt_str(&before.comments),
+            opt_str(&after.comments),
+        ),
+        (
+            "deliveryId",
+            before.delivery_id.to_string(),
+            after.delivery_id.to_string(),
+        ),
+        ("purchases", purchases_str(before), purchases_str(after)),
+        (
+            "amountFromDonations",
+            opt_str(&before.amount_from_donations),
+            opt_str(&after.amount_from_donations),
+        ),
+        (
+            "amountFromCashCollected",
+            opt_str(&before.amount_cash_collected),
+            opt_str(&after.amount_cash_collected),
+        ),
+        (
+            "amountFromChecksCollected",
+            opt_str(&before.amount_checks_collected),
+            opt_str(&after.amount_checks_collected),
+        ),
+        (
+            "checkNumbers",
+            opt_str(&before.check_numbers),
+            opt_str(&after.check_numbers),
+        ),
+        (
+            "amountTotalCollected",
+            opt_str(&before.amount_total_collected),
+            opt_str(&after.amount_total_collected),
+        ),
+        (
+            "isVerified",
+            opt_str(&before.is_verified),
+            opt_str(&after.is_verified),
+        ),
+        (
+            "isWaitlisted",
+            opt_str(&before.is_waitlisted),
+            opt_str(&after.is_waitlisted),
+        ),
+    ];
+
+    gen_field_changes(fields)
+}
+
+/// Only the fields whose value actually changed go in the history
+fn gen_field_changes<'a>(
+    fields: impl IntoIterator<Item = (&'a str, String, String)>,
+) -> Vec<OrderFieldChange> {
+    fields
+        .into_iter()
+        .filter(|(_, from, to)| from != to)
+        .map(|(field, from, to)| OrderFieldChange::new(field, &from, &to))
+        .collect()
+}
+
+static ADD_ORDER_HISTORY_EVENT_GQL: &str = r#"
+mutation {
+  addMulchOrderHistoryEvent(
+    ***ORDER_ID_PARAM***,
+    event: {
+      action: "***ACTION_PARAM***",
+      changedBy: "***USER_PARAM***",
+      changeTime: "***TIME_PARAM***",
+      changes: [***CHANGES_PARAM***]
+    }
+  )
+}
+"#;
+
+/// The order change has already gone through at this point so failing to record the
+/// history shouldn't fail the change.
+pub(crate) async fn record_order_history_event(order_id: &str, event: OrderHistoryEvent) {
+    fn escape(v: &str) -> String {
+        v.replace("\"", "\\\"").replace("\n", r"\n")
+    }
+
+    let changes = event
+        .changes
+        .iter()
+        .map(|v| {
+            format!(
+                "{{field: \"{}\", from: \"{}\", to: \"{}\"}}",
+                v.field,
+                escape(&v.from),
+                escape(&v.to)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+    let query = ADD_ORDER_HISTORY_EVENT_GQL
+        .replace("***ORDER_ID_PARAM***", &format!("orderId: \"{order_id}\""))
+        .replace("***ACTION_PARAM***", &event.action)
+        .replace("***USER_PARAM***", &event.changed_by)
+        .replace("***TIME_PARAM***", &event.change_time)
+        .replace("***CHANGES_PARAM***", &changes);
+
+    let req = GraphQlReq::new(query);
+    info!("Order History GraphQL: {}", &req.query);
+    if let Err(err) = make_gql_request::<serde_json::Value>(&req).await {
+        error!("Failed to record order history for {order_id}: {err:#?}");
+    }
+}
+
+static ORDER_HISTORY_GQL: &str = r"
+{
+  mulchOrderHistory(***ORDER_ID_PARAM***) {
+    action
+    changedBy
+    changeTime
+    changes {
+        field
+        from
+        to
+    }
+  }
+}
+";
+
+pub async fn get_order_history(
+    order_id: &str,
+) -> Result<Vec<OrderHistoryEvent>, Box<dyn std::error::Error>> {
+    #[derive(Deserialize, Debug)]
+    struct RespWrapper {
+        #[serde(alias = "mulchOrderHistory")]
+        history: Vec<OrderHistoryEvent>,
+    }
+
+    let query =
+        ORDER_HISTORY_GQL.replace("***ORDER_ID_PARAM***", &format!("orderId: \"{order_id}\""));
+    let req = GraphQlReq::new(query);
+    make_gql_request::<RespWrapper>(&req).await.map(|v| {
+        let mut history = v.history;
+        history.sort_by(|a, b| b.change_time.cmp(&a.change_time));
+        history
+    })
+}
@@ -0,0 +1,241 @@
+use super::{
+    OrderFieldChange, OrderHistoryEvent, format_fundraiser_datetime, get_active_user,
+    gql_utils::{GraphQlReq, make_gql_request},
+    record_order_history_event, to_fundraiser_datetime_str,
+};
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, RwLock};
+use tracing::{error, info};
+
+// The active overrides. Loaded with the config so sellers know what they've been granted.
+static CUTOFF_OVERRIDES: LazyLock<RwLock<Vec<CutoffOverride>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
+
+////////////////////////////////////////////////////////////////////////////
+#[derive(Serialize, Deserialize, Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CutoffOverrideKind {
+    /// Lets a seller keep taking new orders after the delivery cutoffs have passed
+    #[default]
+    SellerExtension,
+    /// Lets the owner edit a single order after it has been locked
+    OrderReopen,
+}
+
+impl std::fmt::Display for CutoffOverrideKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            CutoffOverrideKind::SellerExtension => write!(f, "Seller Extension"),
+            CutoffOverrideKind::OrderReopen => write!(f, "Order Reopen"),
+        }
+    }
+}
+
+impl std::str::FromStr for CutoffOverrideKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Seller Extension" | "SellerExtension" => Ok(CutoffOverrideKind::SellerExtension),
+            "Order Reopen" | "OrderReopen" => Ok(CutoffOverrideKind::OrderReopen),
+            _ => Err(format!("'{s}' is not a valid value for CutoffOverrideKind")),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+pub struct CutoffOverride {
+    pub id: String,
+    pub kind: CutoffOverrideKind,
+    // Seller id for an extension or order id for a reopen
+    #[serde(alias = "targetId")]
+    pub target_id: String,
+    pub reason: String,
+    #[serde(alias = "grantedBy")]
+    pub granted_by: String,
+    #[serde(alias = "grantTime")]
+    pub grant_time: String,
+    #[serde(alias = "expiresAt")]
+    pub expires_at: String,
+    #[serde(alias = "isRevoked")]
+    pub is_revoked: Option<bool>,
+}
+
+impl CutoffOverride {
+    pub fn is_active(&self) -> bool {
+        !self.is_revoked.unwrap_or(false)
+            && DateTime::parse_from_rfc3339(&self.expires_at).is_ok_and(|v| v > Utc::now())
+    }
+
+    pub fn get_grant_time_str(&self) -> String {
+        format_fundraiser_datetime(&self.grant_time, "%m/%d/%Y %I:%M %p")
+    }
+
+    /// Expirations are set in the fundraiser's timezone so they are shown that way too
+    pub fn get_expires_at_str(&self) -> String {
+        to_fundraiser_datetime_str(&self.expires_at)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+static CUTOFF_OVERRIDES_GQL: &str = r"
+{
+  cutoffOverrides***ACTIVE_ONLY_PARAM*** {
+    id
+    kind
+    targetId
+    reason
+    grantedBy
+    grantTime
+    expiresAt
+    isRevoked
+  }
+}";
+
+////////////////////////////////////////////////////////////////////////////
+async fn query_cutoff_overrides(
+    is_active_only: bool,
+) -> Result<Vec<CutoffOverride>, Box<dyn std::error::Error>> {
+    #[derive(Deserialize)]
+    struct RespOverrides {
+        #[serde(alias = "cutoffOverrides")]
+        overrides: Vec<CutoffOverride>,
+    }
+
+    let query = CUTOFF_OVERRIDES_GQL.replace(
+        "***ACTIVE_ONLY_PARAM***",
+        if is_active_only {
+            "(doGetActiveOnly: true)"
+        } else {
+            ""
+        },
+    );
+    let req = GraphQlReq::new(query);
+    let mut overrides = make_gql_request::<RespOverrides>(&req).await?.overrides;
+    overrides.sort_by(|a, b| b.grant_time.cmp(&a.grant_time));
+    Ok(overrides)
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Loads the active overrides so the cutoff and lock checks can use them
+pub async fn load_cutoff_overrides() {
+    match query_cutoff_overrides(true).await {
+        Ok(overrides) => {
+            info!("Loaded {} cutoff overrides", overrides.len());
+            *CUTOFF_OVERRIDES.write().unwrap() = overrides;
+        }
+        Err(err) => error!("Failed to load cutoff overrides: {err:#?}"),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Gets all of the overrides including the expired and revoked ones for the admin
+pub async fn get_all_cutoff_overrides() -> Result<Vec<CutoffOverride>, Box<dyn std::error::Error>> {
+    query_cutoff_overrides(false).await
+}
+
+////////////////////////////////////////////////////////////////////////////
+fn has_active_override(kind: CutoffOverrideKind, target_id: &str) -> bool {
+    CUTOFF_OVERRIDES
+        .read()
+        .unwrap()
+        .iter()
+        .any(|v| v.kind == kind && v.target_id == target_id && v.is_active())
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// True if the active user has been given more time to take new orders
+pub fn does_active_user_have_cutoff_extension() -> bool {
+    has_active_override(
+        CutoffOverrideKind::SellerExtension,
+        &get_active_user().get_id(),
+    )
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// True if an admin has reopened the order for editing
+pub fn is_order_reopened(order_id: &str) -> bool {
+    has_active_override(CutoffOverrideKind::OrderReopen, order_id)
+}
+
+////////////////////////////////////////////////////////////////////////////
+static ADD_CUTOFF_OVERRIDE_GQL: &str = r#"
+mutation {
+  addCutoffOverride(override: {
+    id: "***ID_PARAM***",
+    kind: ***KIND_PARAM***,
+    targetId: "***TARGET_PARAM***",
+    reason: "***REASON_PARAM***",
+    grantedBy: "***USER_PARAM***",
+    grantTime: "***TIME_PARAM***",
+    expiresAt: "***EXPIRES_PARAM***"
+  })
+}"#;
+
+////////////////////////////////////////////////////////////////////////////
+pub async fn add_cutoff_override(
+    kind: CutoffOverrideKind,
+    target_id: &str,
+    reason: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<CutoffOverride, Box<dyn std::error::Error>> {
+    let cutoff_override = CutoffOverride {
+        id: uuid::Uuid::new_v4().to_string(),
+        kind,
+        target_id: target_id.to_string(),
+        reason: reason.to_string(),
+        granted_by: get_active_user().get_id(),
+        grant_time: Utc::now().to_rfc3339(),
+        expires_at: expires_at.to_rfc3339(),
+        is_revoked: None,
+    };
+
+    let query = ADD_CUTOFF_OVERRIDE_GQL
+        .replace("***ID_PARAM***", &cutoff_override.id)
+        .replace("***KIND_PARAM***", &format!("{kind:?}"))
+        .replace("***TARGET_PARAM***", target_id)
+        .replace(
+            "***REASON_PARAM***",
+            &reason.replace("\"", "\\\"").replace("\n", r"\n"),
+        )
+        .replace("***USER_PARAM***", &cutoff_override.granted_by)
+        .replace("***TIME_PARAM***", &cutoff_override.grant_time)
+        .replace("***EXPIRES_PARAM***", &cutoff_override.expires_at);
+
+    info!("Add Cutoff Override Mutation:\n{}", &query);
+    let req = GraphQlReq::new(query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+
+    if CutoffOverrideKind::OrderReopen == kind {
+        let changes = vec![
+            OrderFieldChange::new("reason", "", reason),
+            OrderFieldChange::new("reopenedUntil", "", &cutoff_override.get_expires_at_str()),
+        ];
+        record_order_history_event(target_id, OrderHistoryEvent::new("reopened", changes)).await;
+    }
+
+    CUTOFF_OVERRIDES
+        .write()
+        .unwrap()
+        .push(cutoff_override.clone());
+    Ok(cutoff_override)
+}
+
+////////////////////////////////////////////////////////////////////////////
+static REVOKE_CUTOFF_OVERRIDE_GQL: &str = r#"
+mutation {
+  revokeCutoffOverride(id: "***ID_PARAM***")
+}"#;
+
+////////////////////////////////////////////////////////////////////////////
+/// Ends an override before it expires
+pub async fn revoke_cutoff_override(id: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let query = REVOKE_CUTOFF_OVERRIDE_GQL.replace("***ID_PARAM***", id);
+    info!("Revoke Cutoff Override Mutation:\n{}", &query);
+    let req = GraphQlReq::new(query);
+    make_gql_request::<serde_json::Value>(&req).await?;
+
+    CUTOFF_OVERRIDES.write().unwrap().retain(|v| v.id != id);
+    Ok(())
+}
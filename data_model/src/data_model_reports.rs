@@ -1,11 +1,14 @@
 use super::{
+    SpreadingCompletion, SpreadingJobClaim, get_group_attributes_for, get_users,
     gql_utils::{GraphQlReq, make_gql_request},
+    is_waitlisted_from_report_data, parse_money_str_as_decimal,
     {get_active_user, get_fr_config, get_neighborhood},
 };
 use chrono::prelude::*;
 use gloo::storage::{LocalStorage, SessionStorage, Storage};
+use rust_decimal::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::LazyLock;
 use tracing::{error, info, warn};
 
@@ -22,6 +25,7 @@ pub enum ReportViews {
     Full,
     SpreadingJobs,
     SpreadingAssistJobs,
+    SpreadingJobBoard,
     AllocationSummary,
     SellMap,
     MoneyCollection,
@@ -31,6 +35,16 @@ pub enum ReportViews {
     OrderVerification,
     DistributionPoints,
     Deliveries,
+    RoutePlanner,
+    DeliveryProgress,
+    Inventory,
+    SupplierOrders,
+    TimecardAnomalies,
+    ServiceHours,
+    CancelledOrders,
+
+    // Mulch delivery day
+    DriverView,
 }
 
 impl std::fmt::Display for ReportViews {
@@ -41,12 +55,21 @@ impl std::fmt::Display for ReportViews {
             ReportViews::SpreadingJobs => write!(f, "Spreading Jobs"),
             ReportViews::UnfinishedSpreadingJobs => write!(f, "Unfinished Spreading Jobs"),
             ReportViews::SpreadingAssistJobs => write!(f, "Assisted Spreading Jobs"),
+            ReportViews::SpreadingJobBoard => write!(f, "Spreading Job Board"),
             ReportViews::OrderVerification => write!(f, "Order Verification"),
             ReportViews::DistributionPoints => write!(f, "Distribution Point"),
             ReportViews::Deliveries => write!(f, "Deliveries"),
+            ReportViews::RoutePlanner => write!(f, "Route Planner"),
+            ReportViews::DeliveryProgress => write!(f, "Delivery Progress"),
+            ReportViews::Inventory => write!(f, "Inventory"),
+            ReportViews::SupplierOrders => write!(f, "Supplier Orders"),
+            ReportViews::TimecardAnomalies => write!(f, "Timecard Anomalies"),
+            ReportViews::ServiceHours => write!(f, "Service Hours"),
+            ReportViews::DriverView => write!(f, "Driver View"),
             ReportViews::SellMap => write!(f, "Sales Map"),
             ReportViews::AllocationSummary => write!(f, "Allocation Summary"),
             ReportViews::MoneyCollection => write!(f, "Money Collection"),
+            ReportViews::CancelledOrders => write!(f, "Cancelled Orders"),
         }
     }
 }
@@ -61,12 +84,21 @@ impl std::str::FromStr for ReportViews {
             "Spreading Jobs" => Ok(ReportViews::SpreadingJobs),
             "Unfinished Spreading Jobs" => Ok(ReportViews::UnfinishedSpreadingJobs),
             "Assisted Spreading Jobs" => Ok(ReportViews::SpreadingAssistJobs),
+            "Spreading Job Board" => Ok(ReportViews::SpreadingJobBoard),
             "Order Verification" => Ok(ReportViews::OrderVerification),
             "Distribution Point" => Ok(ReportViews::DistributionPoints),
             "Deliveries" => Ok(ReportViews::Deliveries),
+            "Route Planner" => Ok(ReportViews::RoutePlanner),
+            "Delivery Progress" => Ok(ReportViews::DeliveryProgress),
+            "Inventory" => Ok(ReportViews::Inventory),
+            "Supplier Orders" => Ok(ReportViews::SupplierOrders),
+            "Timecard Anomalies" => Ok(ReportViews::TimecardAnomalies),
+            "Service Hours" => Ok(ReportViews::ServiceHours),
+            "Driver View" => Ok(ReportViews::DriverView),
             "Sales Map" => Ok(ReportViews::SellMap),
             "Allocation Summary" => Ok(ReportViews::AllocationSummary),
             "Money Collection" => Ok(ReportViews::MoneyCollection),
+            "Cancelled Orders" => Ok(ReportViews::CancelledOrders),
             _ => Err(format!("'{s}' is not a valid value for ReportViews")),
         }
     }
@@ -83,15 +115,27 @@ pub fn get_allowed_report_views() -> Vec<ReportViews> {
     if get_fr_config().kind == "mulch" {
         reports.push(ReportViews::SpreadingJobs);
         reports.push(ReportViews::SpreadingAssistJobs);
+        reports.push(ReportViews::SpreadingJobBoard);
+        reports.push(ReportViews::DriverView);
 
         if get_active_user().is_admin() {
             reports.push(ReportViews::UnfinishedSpreadingJobs);
             reports.push(ReportViews::OrderVerification);
             reports.push(ReportViews::DistributionPoints);
             reports.push(ReportViews::Deliveries);
+            reports.push(ReportViews::RoutePlanner);
+            reports.push(ReportViews::DeliveryProgress);
+            reports.push(ReportViews::Inventory);
+            reports.push(ReportViews::SupplierOrders);
+            reports.push(ReportViews::TimecardAnomalies);
+            reports.push(ReportViews::ServiceHours);
         }
     }
 
+    if get_active_user().is_admin() {
+        reports.push(ReportViews::CancelledOrders);
+    }
+
     // if allocation_summary available add allocation summary {
     //      reports.push(ReportViews::AllocationSummary);
     // }
@@ -140,7 +184,20 @@ pub fn do_show_current_seller(current_view: &ReportViews) -> bool {
 }
 
 /////////////////////////////////////////////////////////////////////////////////
-async fn make_report_query(
+/// Orders spread before completion was recorded only have spreaders so those count as
+/// completed without a date.
+pub fn get_spreading_completion(v: &serde_json::Value) -> Option<SpreadingCompletion> {
+    if v["spreadingCompletion"].is_null() {
+        let has_spreaders = v["spreaders"]
+            .as_array()
+            .is_some_and(|v| v.iter().any(|v| v.as_str().is_some_and(|v| !v.is_empty())));
+        return has_spreaders.then(SpreadingCompletion::default);
+    }
+    serde_json::from_value::<SpreadingCompletion>(v["spreadingCompletion"].clone()).ok()
+}
+
+/////////////////////////////////////////////////////////////////////////////////
+pub(crate) async fn make_report_query(
     query: String,
 ) -> Result<Vec<serde_json::Value>, Box<dyn std::error::Error>> {
     #[derive(Serialize, Deserialize, Debug)]
@@ -152,7 +209,29 @@ async fn make_report_query(
     let req = GraphQlReq::new(query);
     make_gql_request::<GqlResp>(&req)
         .await
-        .map(|v| v.mulch_orders)
+        .map(|v| v.mulch_orders.into_iter().map(apply_refunds).collect())
+}
+
+/////////////////////////////////////////////////////////////////////////////////
+/// Refunds are recorded separately from the payments so take them out of the
+/// total collected for any report that asked for them
+fn apply_refunds(mut order: serde_json::Value) -> serde_json::Value {
+    let refunded = order["amountRefunded"]
+        .as_str()
+        .and_then(parse_money_str_as_decimal)
+        .unwrap_or(Decimal::ZERO);
+    if refunded == Decimal::ZERO {
+        return order;
+    }
+
+    if let Some(collected) = order["amountTotalCollected"]
+        .as_str()
+        .and_then(parse_money_str_as_decimal)
+    {
+        order["amountTotalCollected"] =
+            serde_json::Value::String(collected.checked_sub(refunded).unwrap().to_string());
+    }
+    order
 }
 
 /////////////////////////////////////////////////////////////////////////////////
@@ -237,6 +316,15 @@ static QUICK_RPT_GRAPHQL: &str = r"
     ownerId
     deliveryId
     spreaders
+    spreaderBags {
+        uid
+        numBags
+    }
+    spreadingCompletion {
+        completedDate
+        notes
+        hasIssue
+    }
     isVerified
     customer {
         name
@@ -278,6 +366,7 @@ static FULL_RPT_GRAPHQL: &str = r"
     amountFromChecksCollected
     checkNumbers
     amountTotalCollected
+    amountRefunded
     isVerified
     customer {
         name
@@ -297,6 +386,15 @@ static FULL_RPT_GRAPHQL: &str = r"
     }
     deliveryId
     spreaders
+    spreaderBags {
+        uid
+        numBags
+    }
+    spreadingCompletion {
+        completedDate
+        notes
+        hasIssue
+    }
   }
 }
 ";
@@ -326,6 +424,7 @@ static MONEY_COLLECTION_RPT_GRAPHQL: &str = r"
     amountTotalFromCashCollected
     amountTotalFromChecksCollected
     amountTotalCollected
+    amountRefunded
   }
 }
 ";
@@ -359,6 +458,7 @@ static DISTRIBUTION_POINTS_RPT_GRAPHQL: &str = r#"
         numSold
     }
     deliveryId
+    isWaitlisted
   }
 }
 "#;
@@ -366,13 +466,14 @@ static DISTRIBUTION_POINTS_RPT_GRAPHQL: &str = r#"
 /////////////////////////////////////////////////////////////////////////////////
 pub async fn get_distribution_points_report_data()
 -> Result<Vec<serde_json::Value>, Box<dyn std::error::Error>> {
-    use std::collections::{BTreeMap, BTreeSet};
+    use std::collections::BTreeSet;
     let mut delivery_id_map: BTreeMap<u64, BTreeMap<String, u64>> = BTreeMap::new();
     make_report_query(DISTRIBUTION_POINTS_RPT_GRAPHQL.to_string())
         .await
         .map(|orders| {
             orders
                 .into_iter()
+                .filter(|v| !is_waitlisted_from_report_data(v))
                 .map(|v| {
                     let purchases = get_purchase_to_map(&v);
                     let num_bags_sold = purchases.get("bags").copied().unwrap_or(0);
@@ -385,7 +486,7 @@ pub async fn get_distribution_points_report_data()
                     let dist_point_map = delivery_id_map.get_mut(&delivery_id).unwrap();
                     let neighborhood = v["customer"]["neighborhood"].as_str().unwrap();
                     let dist_point = get_neighborhood(neighborhood)
-                        .map_or("".to_string(), |v| v.distribution_point.clone());
+                        .map_or("".to_string(), |v| v.get_distribution_point_name());
                     match dist_point_map.get_mut(&dist_point) {
                         Some(num_bags_for_point) => {
                             *num_bags_for_point += num_bags_sold;
@@ -443,6 +544,7 @@ static DELIVERIES_RPT_GRAPHQL: &str = r#"
         numSold
     }
     deliveryId
+    isWaitlisted
   }
 }
 "#;
@@ -456,6 +558,46 @@ pub async fn get_deliveries_report_data()
             orders
                 .into_iter()
                 .filter(|v| v["deliveryId"].as_u64().is_some())
+                .filter(|v| !is_waitlisted_from_report_data(v))
+                .collect::<Vec<_>>()
+        })
+}
+
+//////////////////////////////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////////////////////////////
+static DELIVERY_PROGRESS_RPT_GRAPHQL: &str = r"
+{
+  mulchOrders {
+    orderId
+    customer {
+        neighborhood
+    }
+    purchases {
+        productId
+        numSold
+    }
+    deliveryId
+    isWaitlisted
+    deliveryStatus {
+        status
+        updateTime
+    }
+  }
+}
+";
+
+/////////////////////////////////////////////////////////////////////////////////
+pub async fn get_delivery_progress_report_data(
+    delivery_id: u32,
+) -> Result<Vec<serde_json::Value>, Box<dyn std::error::Error>> {
+    make_report_query(DELIVERY_PROGRESS_RPT_GRAPHQL.to_string())
+        .await
+        .map(|orders| {
+            orders
+                .into_iter()
+                .filter(|v| v["deliveryId"].as_u64() == Some(delivery_id as u64))
+                .filter(|v| !is_waitlisted_from_report_data(v))
+                .filter(|v| get_purchase_to_map(v).get("bags").is_some_and(|v| *v != 0))
                 .collect::<Vec<_>>()
         })
 }
@@ -483,6 +625,15 @@ static SPREADING_JOBS_RPT_GRAPHQL: &str = r"
     }
     deliveryId
     spreaders
+    spreaderBags {
+        uid
+        numBags
+    }
+    spreadingCompletion {
+        completedDate
+        notes
+        hasIssue
+    }
   }
 }
 ";
@@ -553,22 +704,35 @@ static UNFINISHED_SPREADING_JOBS_RPT_GRAPHQL: &str = r"
         numSold
     }
     spreaders
+    spreadingCompletion {
+        completedDate
+    }
+    spreadingClaims {
+        uid
+        isApproved
+    }
   }
 }
 ";
 
+/////////////////////////////////////////////////////////////////////////////////
+#[derive(Default)]
+struct UnfinishedSpreadingJobs {
+    bags_left: u64,
+    bags_committed: u64,
+    // Who has signed up to spread these bags and how many bags each has taken on
+    committed_to: BTreeMap<(String, bool), u64>,
+}
+
 /////////////////////////////////////////////////////////////////////////////////
 pub async fn get_unfinished_spreading_jobs_report_data()
 -> Result<Vec<serde_json::Value>, Box<dyn std::error::Error>> {
-    use std::collections::BTreeMap;
-    let mut unfinished_job_map: BTreeMap<(String, u64), u64> = BTreeMap::new();
+    let mut unfinished_job_map: BTreeMap<(String, u64), UnfinishedSpreadingJobs> = BTreeMap::new();
     make_report_query(UNFINISHED_SPREADING_JOBS_RPT_GRAPHQL.to_string())
         .await
         .map(|orders| {
             orders.into_iter().for_each(|v| {
-                let num_spreaders = v["spreaders"].as_array().unwrap_or(&Vec::new()).len();
-                if num_spreaders != 0 {
-                    // There is an assumption that if spreaders have been set then all bags have been spread
+                if get_spreading_completion(&v).is_some() {
                     return;
                 }
                 let purchases = get_purchase_to_map(&v);
@@ -579,20 +743,40 @@ pub async fn get_unfinished_spreading_jobs_report_data()
                 }
                 let uid = v["ownerId"].as_str().unwrap().to_string();
                 let delivery_id = v["deliveryId"].as_u64().unwrap();
-                let key = (uid, delivery_id);
-                match unfinished_job_map.get_mut(&key) {
-                    Some(uid_unfinished_jobs) => *uid_unfinished_jobs += num_spreading_bags_sold,
-                    None => {
-                        unfinished_job_map.insert(key, num_spreading_bags_sold);
-                    }
-                };
+                let claims =
+                    serde_json::from_value::<Vec<SpreadingJobClaim>>(v["spreadingClaims"].clone())
+                        .unwrap_or_default();
+                let unfinished_jobs = unfinished_job_map.entry((uid, delivery_id)).or_default();
+                unfinished_jobs.bags_left += num_spreading_bags_sold;
+                if !claims.is_empty() {
+                    unfinished_jobs.bags_committed += num_spreading_bags_sold;
+                }
+                for claim in claims {
+                    *unfinished_jobs
+                        .committed_to
+                        .entry((claim.uid, claim.is_approved))
+                        .or_default() += num_spreading_bags_sold;
+                }
             });
         })?;
     Ok(unfinished_job_map
         .into_iter()
-        .map(
-            |((uid, did), v)| serde_json::json!({"ownerId": uid, "deliveryId": did, "bagsLeft": v}),
-        )
+        .map(|((uid, did), v)| {
+            let committed_to = v
+                .committed_to
+                .into_iter()
+                .map(|((claim_uid, is_approved), num_bags)| {
+                    serde_json::json!({"uid": claim_uid, "isApproved": is_approved, "numBags": num_bags})
+                })
+                .collect::<Vec<serde_json::Value>>();
+            serde_json::json!({
+                "ownerId": uid,
+                "deliveryId": did,
+                "bagsLeft": v.bags_left,
+                "bagsCommitted": v.bags_committed,
+                "committedTo": committed_to,
+            })
+        })
         .collect::<Vec<serde_json::Value>>())
 }
 
@@ -621,6 +805,9 @@ pub struct TroopSummary {
     #[serde(alias = "totalAmountCollected")]
     pub amount_total_collected: String,
 
+    #[serde(alias = "totalAmountRefunded", default)]
+    pub amount_total_refunded: String,
+
     #[serde(alias = "topSellers")]
     pub top_sellers: Vec<TopSeller>,
 
@@ -631,11 +818,17 @@ pub struct TroopSummary {
 /////////////////////////////////////////////////////////////////////////////////
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct TopSeller {
+    #[serde(alias = "ownerId", default)]
+    pub uid: String,
+
     #[serde(alias = "name")]
     pub name: String,
 
     #[serde(alias = "totalAmountCollected")]
     pub amount_total_collected: String,
+
+    #[serde(alias = "totalAmountRefunded", default)]
+    pub amount_total_refunded: String,
 }
 
 /////////////////////////////////////////////////////////////////////////////////
@@ -646,6 +839,9 @@ pub struct GroupSummary {
 
     #[serde(alias = "totalAmountCollected")]
     pub amount_total_collected: String,
+
+    #[serde(alias = "totalAmountRefunded", default)]
+    pub amount_total_refunded: String,
 }
 
 /////////////////////////////////////////////////////////////////////////////////
@@ -675,6 +871,9 @@ pub struct SellerSummary {
     #[serde(alias = "totalAmountCollected")]
     pub amount_total_collected: String,
 
+    #[serde(alias = "totalAmountRefunded", default)]
+    pub amount_total_refunded: String,
+
     #[serde(alias = "allocationsFromDelivery")]
     pub allocations_from_deliveries: String,
 
@@ -701,6 +900,7 @@ static SUMMARY_RPT_GRAPHQL: &str = r"
       totalAmountCollectedForBags
       totalAmountCollectedForBagsToSpread
       totalAmountCollected
+      totalAmountRefunded
       allocationsFromDelivery
       allocationsFromBagsSold
       allocationsFromBagsSpread
@@ -708,19 +908,67 @@ static SUMMARY_RPT_GRAPHQL: &str = r"
     }
     troop(***TOP_SELLERS_PARAM***) {
       totalAmountCollected
+      totalAmountRefunded
       topSellers {
         totalAmountCollected
+        totalAmountRefunded
+        ownerId
         name
       }
       groupSummary {
         groupId
         totalAmountCollected
+        totalAmountRefunded
       }
     }
   }
 }
 ";
 
+/////////////////////////////////////////////////////////////////////////////////
+fn sub_money_str(amount: &str, refunded: &str) -> String {
+    let refunded = parse_money_str_as_decimal(refunded).unwrap_or(Decimal::ZERO);
+    parse_money_str_as_decimal(amount)
+        .unwrap_or(Decimal::ZERO)
+        .checked_sub(refunded)
+        .unwrap()
+        .to_string()
+}
+
+/////////////////////////////////////////////////////////////////////////////////
+/// The summary totals come from what was collected so take the refunds out of them
+/// the same way the order reports and closeout do. The summary refund totals include
+/// refunds on cancelled orders.
+fn apply_summary_refunds(summary: &mut SummaryReport) {
+    let seller_summary = &mut summary.seller_summary;
+    seller_summary.amount_total_collected = sub_money_str(
+        &seller_summary.amount_total_collected,
+        &seller_summary.amount_total_refunded,
+    );
+
+    let troop_summary = &mut summary.troop_summary;
+    troop_summary.amount_total_collected = sub_money_str(
+        &troop_summary.amount_total_collected,
+        &troop_summary.amount_total_refunded,
+    );
+    for group in troop_summary.group_summary.iter_mut() {
+        group.amount_total_collected =
+            sub_money_str(&group.amount_total_collected, &group.amount_total_refunded);
+    }
+    for seller in troop_summary.top_sellers.iter_mut() {
+        seller.amount_total_collected = sub_money_str(
+            &seller.amount_total_collected,
+            &seller.amount_total_refunded,
+        );
+    }
+    // Refunds can change who is on top
+    troop_summary.top_sellers.sort_by_key(|v| {
+        std::cmp::Reverse(
+            parse_money_str_as_decimal(&v.amount_total_collected).unwrap_or(Decimal::ZERO),
+        )
+    });
+}
+
 /////////////////////////////////////////////////////////////////////////////////
 pub async fn get_summary_report_data(
     seller_id: &str,
@@ -737,6 +985,13 @@ pub async fn get_summary_report_data(
         }
     }
 
+    // Ask for enough extra sellers to still have a full list after the hidden ones are removed
+    // Matched by uid since more than one user can have the same name
+    let hidden_uids = get_users()
+        .iter()
+        .filter(|(_, v)| get_group_attributes_for(&v.group).is_hidden_from_top_sellers)
+        .map(|(uid, _)| uid.clone())
+        .collect::<HashSet<String>>();
     let query = SUMMARY_RPT_GRAPHQL
         .replace(
             "***ORDER_OWNER_PARAM***",
@@ -744,7 +999,10 @@ pub async fn get_summary_report_data(
         )
         .replace(
             "***TOP_SELLERS_PARAM***",
-            &format!("numTopSellers: {top_sellers}"),
+            &format!(
+                "numTopSellers: {}",
+                usize::from(top_sellers) + hidden_uids.len()
+            ),
         );
     info!("Running Query: {}", &query);
     #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -754,7 +1012,11 @@ pub async fn get_summary_report_data(
     }
 
     let req = GraphQlReq::new(query);
-    let rslt = make_gql_request::<SummaryReportRslt>(&req).await?;
+    let mut rslt = make_gql_request::<SummaryReportRslt>(&req).await?;
+    apply_summary_refunds(&mut rslt.summary);
+    let top_sellers_list = &mut rslt.summary.troop_summary.top_sellers;
+    top_sellers_list.retain(|v| !hidden_uids.contains(&v.uid));
+    top_sellers_list.truncate(top_sellers.into());
 
     LocalStorage::set(
         "SummaryData",
@@ -780,6 +1042,7 @@ static ORDER_VERIFICATION_GRAPHQL: &str = r"
     amountFromChecksCollected
     checkNumbers
     amountTotalCollected
+    amountRefunded
     isVerified
     customer {
         name
@@ -804,3 +1067,34 @@ pub async fn get_order_verification_report_data(
     info!("Running Query: {}", &query);
     make_report_query(query).await
 }
+
+//////////////////////////////////////////////////////////////////////////////////////
+//////////////////////////////////////////////////////////////////////////////////////
+static CANCELLED_ORDERS_GRAPHQL: &str = r"
+{
+  mulchOrders(doGetCancelledOrdersOnly: true) {
+    orderId
+    ownerId
+    amountTotalCollected
+    amountRefunded
+    customer {
+        name
+        phone
+    }
+    deliveryId
+    cancellation {
+        reason
+        cancelledBy
+        cancelledTime
+    }
+  }
+}
+";
+
+/////////////////////////////////////////////////////////////////////////////////
+/// Cancelled orders are excluded from the other report queries so this is the only
+/// place they show up
+pub async fn get_cancelled_orders_report_data()
+-> Result<Vec<serde_json::Value>, Box<dyn std::error::Error>> {
+    make_report_query(CANCELLED_ORDERS_GRAPHQL.to_string()).await
+}
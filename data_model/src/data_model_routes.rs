@@ -0,0 +1,377 @@
+use super::{
+    DeliveryStatusRecord,
+    data_model_reports::{get_purchase_to_map, make_report_query},
+    get_neighborhood,
+    gql_utils::{GraphQlReq, make_gql_request},
+    is_waitlisted_from_report_data,
+};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{LazyLock, RwLock};
+use tracing::{info, warn};
+
+// Geocoding costs an api call per address so remember what has already been looked up
+static GEOCODE_CACHE: LazyLock<RwLock<HashMap<String, GeoPoint>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+// Keeps from flooding the geocoding api when a delivery has a lot of stops
+const MAX_CONCURRENT_GEOCODES: usize = 8;
+
+////////////////////////////////////////////////////////////////////////////
+// Where maps are centered when there isn't anything better to center them on
+pub const DEFAULT_MAP_CENTER: GeoPoint = GeoPoint {
+    lat: 30.5461096,
+    lng: -97.6723646,
+};
+
+////////////////////////////////////////////////////////////////////////////
+#[derive(Serialize, Deserialize, Default, Copy, Clone, Debug, PartialEq)]
+pub struct GeoPoint {
+    pub lat: f64,
+    pub lng: f64,
+}
+
+impl GeoPoint {
+    /// Great circle distance in kilometers
+    pub fn distance_to(&self, other: &GeoPoint) -> f64 {
+        const EARTH_RADIUS_KM: f64 = 6371.0;
+        let d_lat = (other.lat - self.lat).to_radians();
+        let d_lng = (other.lng - self.lng).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2)
+            + self.lat.to_radians().cos()
+                * other.lat.to_radians().cos()
+                * (d_lng / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct RouteStop {
+    pub order_id: String,
+    pub name: String,
+    pub phone: String,
+    pub address: String,
+    pub neighborhood: String,
+    pub special_instructions: String,
+    pub num_bags: u64,
+    pub num_spreading: u64,
+    pub location: Option<GeoPoint>,
+    pub delivery_status: Option<DeliveryStatusRecord>,
+}
+
+////////////////////////////////////////////////////////////////////////////
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct DeliveryRoute {
+    pub truck_num: usize,
+    pub stops: Vec<RouteStop>,
+}
+
+impl DeliveryRoute {
+    pub fn get_num_bags(&self) -> u64 {
+        self.stops.iter().map(|v| v.num_bags).sum()
+    }
+
+    pub fn get_distance_km(&self, depot: &GeoPoint) -> f64 {
+        get_path_distance(depot, &self.stops)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct DistPointRoutes {
+    pub dist_point: String,
+    pub depot: Option<GeoPoint>,
+    pub routes: Vec<DeliveryRoute>,
+    // Stops that couldn't be geocoded still need to be delivered so they are kept to be
+    // handed out manually
+    pub unlocated: Vec<RouteStop>,
+}
+
+////////////////////////////////////////////////////////////////////////////
+static ROUTE_PLANNER_GRAPHQL: &str = r"
+{
+  mulchOrders {
+    orderId
+    customer {
+        name
+        addr1
+        addr2
+        city
+        zipcode
+        phone
+        neighborhood
+        latitude
+        longitude
+    }
+    specialInstructions
+    purchases {
+        productId
+        numSold
+    }
+    deliveryId
+    isWaitlisted
+    deliveryStatus {
+        status
+        notes
+        updatedBy
+        updateTime
+    }
+  }
+}
+";
+
+////////////////////////////////////////////////////////////////////////////
+static GET_COORDINATES_GQL: &str = r#"
+{
+  getCoordinates(address: "***ADDR_PARAM***") {
+    lat
+    lng
+  }
+}"#;
+
+////////////////////////////////////////////////////////////////////////////
+pub async fn geocode_address(address: &str) -> Option<GeoPoint> {
+    if let Some(pt) = GEOCODE_CACHE.read().unwrap().get(address) {
+        return Some(*pt);
+    }
+
+    #[derive(Deserialize)]
+    struct RespCoordinates {
+        #[serde(rename = "getCoordinates")]
+        coordinates: Option<GeoPoint>,
+    }
+
+    let query = GET_COORDINATES_GQL.replace("***ADDR_PARAM***", &address.replace("\"", "\\\""));
+    let req = GraphQlReq::new(query);
+    match make_gql_request::<RespCoordinates>(&req).await {
+        Ok(RespCoordinates {
+            coordinates: Some(pt),
+        }) => {
+            GEOCODE_CACHE
+                .write()
+                .unwrap()
+                .insert(address.to_string(), pt);
+            Some(pt)
+        }
+        Ok(_) => None,
+        Err(err) => {
+            warn!("Failed to geocode {address}: {err:#?}");
+            None
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Gets the stops for a delivery grouped by distribution point. Stored coordinates are
+/// used when the order has them otherwise the address is geocoded.
+pub async fn get_route_stops_for_delivery(
+    delivery_id: u32,
+) -> Result<BTreeMap<String, Vec<RouteStop>>, Box<dyn std::error::Error>> {
+    fn get_stored_location(v: &serde_json::Value) -> Option<GeoPoint> {
+        match (
+            v["customer"]["latitude"].as_f64(),
+            v["customer"]["longitude"].as_f64(),
+        ) {
+            (Some(lat), Some(lng)) => Some(GeoPoint { lat, lng }),
+            _ => None,
+        }
+    }
+
+    fn get_address(v: &serde_json::Value) -> String {
+        [
+            v["customer"]["addr1"].as_str().unwrap_or("").to_string(),
+            v["customer"]["addr2"].as_str().unwrap_or("").to_string(),
+            v["customer"]["city"].as_str().unwrap_or("").to_string(),
+            v["customer"]["zipcode"]
+                .as_u64()
+                .map_or("".to_string(), |v| v.to_string()),
+        ]
+        .iter()
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .collect::<Vec<&str>>()
+        .join(", ")
+    }
+
+    let orders = make_report_query(ROUTE_PLANNER_GRAPHQL.to_string())
+        .await?
+        .into_iter()
+        .filter(|v| v["deliveryId"].as_u64() == Some(delivery_id as u64))
+        .filter(|v| !is_waitlisted_from_report_data(v))
+        .filter(|v| get_purchase_to_map(v).get("bags").copied().unwrap_or(0) != 0)
+        .collect::<Vec<serde_json::Value>>();
+
+    // Look up all of the addresses that don't have stored coordinates at once
+    let addresses_to_geocode = orders
+        .iter()
+        .filter(|v| get_stored_location(v).is_none())
+        .map(get_address)
+        .collect::<HashSet<String>>();
+    let geocoded_locations = stream::iter(addresses_to_geocode)
+        .map(|address| async move {
+            let location = geocode_address(&address).await;
+            (address, location)
+        })
+        .buffer_unordered(MAX_CONCURRENT_GEOCODES)
+        .collect::<HashMap<String, Option<GeoPoint>>>()
+        .await;
+
+    let mut dist_point_map: BTreeMap<String, Vec<RouteStop>> = BTreeMap::new();
+    for v in orders.iter() {
+        let purchases = get_purchase_to_map(v);
+        let num_bags = purchases.get("bags").copied().unwrap_or(0);
+        let address = get_address(v);
+        let location =
+            get_stored_location(v).or_else(|| geocoded_locations.get(&address).copied().flatten());
+        let neighborhood = v["customer"]["neighborhood"]
+            .as_str()
+            .unwrap_or("")
+            .to_string();
+        let dist_point = get_neighborhood(&neighborhood)
+            .map_or("".to_string(), |v| v.get_distribution_point_name());
+
+        dist_point_map
+            .entry(dist_point)
+            .or_default()
+            .push(RouteStop {
+                order_id: v["orderId"].as_str().unwrap_or("").to_string(),
+                name: v["customer"]["name"].as_str().unwrap_or("").to_string(),
+                phone: v["customer"]["phone"].as_str().unwrap_or("").to_string(),
+                address,
+                neighborhood,
+                special_instructions: v["specialInstructions"].as_str().unwrap_or("").to_string(),
+                num_bags,
+                num_spreading: purchases.get("spreading").copied().unwrap_or(0),
+                location,
+                delivery_status: serde_json::from_value(v["deliveryStatus"].clone()).ok(),
+            });
+    }
+
+    info!(
+        "Found stops for {} distribution points for delivery: {delivery_id}",
+        dist_point_map.len()
+    );
+    Ok(dist_point_map)
+}
+
+////////////////////////////////////////////////////////////////////////////
+fn get_path_distance(depot: &GeoPoint, stops: &[RouteStop]) -> f64 {
+    let mut distance = 0.0;
+    let mut cur_pt = *depot;
+    for pt in stops.iter().filter_map(|v| v.location) {
+        distance += cur_pt.distance_to(&pt);
+        cur_pt = pt;
+    }
+    distance
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Improves an open route starting at the depot by reversing segments until no reversal
+/// shortens it any further
+fn two_opt_route(depot: &GeoPoint, stops: &mut [RouteStop]) {
+    let pt = |stops: &[RouteStop], idx: usize| -> GeoPoint {
+        if 0 == idx {
+            *depot
+        } else {
+            stops[idx - 1].location.unwrap()
+        }
+    };
+
+    let num_stops = stops.len();
+    let mut is_improved = true;
+    while is_improved {
+        is_improved = false;
+        for i in 1..num_stops {
+            for k in (i + 1)..=num_stops {
+                // Path indexes are offset by one since the depot is index 0
+                let before = pt(stops, i - 1).distance_to(&pt(stops, i))
+                    + if k < num_stops {
+                        pt(stops, k).distance_to(&pt(stops, k + 1))
+                    } else {
+                        0.0
+                    };
+                let after = pt(stops, i - 1).distance_to(&pt(stops, k))
+                    + if k < num_stops {
+                        pt(stops, i).distance_to(&pt(stops, k + 1))
+                    } else {
+                        0.0
+                    };
+                if after + 1e-9 < before {
+                    stops[(i - 1)..k].reverse();
+                    is_improved = true;
+                }
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Splits the stops for a distribution point into truck routes. Each truck is filled by
+/// visiting the nearest stop that still fits within the truck capacity and then the
+/// route is cleaned up with 2-opt.
+pub fn plan_delivery_routes(
+    dist_point: &str,
+    depot: Option<GeoPoint>,
+    stops: Vec<RouteStop>,
+    truck_capacity: u64,
+) -> DistPointRoutes {
+    let (mut remaining, unlocated): (Vec<RouteStop>, Vec<RouteStop>) =
+        stops.into_iter().partition(|v| v.location.is_some());
+
+    // Without a known location for the distribution point start from the middle of the stops
+    let depot = depot.or_else(|| {
+        if remaining.is_empty() {
+            return None;
+        }
+        let num_stops = remaining.len() as f64;
+        let (lat, lng) = remaining
+            .iter()
+            .filter_map(|v| v.location)
+            .fold((0.0, 0.0), |(lat, lng), v| (lat + v.lat, lng + v.lng));
+        Some(GeoPoint {
+            lat: lat / num_stops,
+            lng: lng / num_stops,
+        })
+    });
+
+    let mut routes = Vec::new();
+    if let Some(depot) = depot.as_ref() {
+        while !remaining.is_empty() {
+            let mut route_stops: Vec<RouteStop> = Vec::new();
+            let mut load = 0;
+            let mut cur_pt = *depot;
+            loop {
+                let next_stop = remaining
+                    .iter()
+                    .enumerate()
+                    // An order bigger than a truck gets a truck to itself
+                    .filter(|(_, v)| route_stops.is_empty() || load + v.num_bags <= truck_capacity)
+                    .map(|(idx, v)| (idx, cur_pt.distance_to(&v.location.unwrap())))
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|(idx, _)| idx);
+                let Some(idx) = next_stop else {
+                    break;
+                };
+                let stop = remaining.swap_remove(idx);
+                load += stop.num_bags;
+                cur_pt = stop.location.unwrap();
+                route_stops.push(stop);
+            }
+
+            two_opt_route(depot, &mut route_stops);
+            routes.push(DeliveryRoute {
+                truck_num: routes.len() + 1,
+                stops: route_stops,
+            });
+        }
+    }
+
+    DistPointRoutes {
+        dist_point: dist_point.to_string(),
+        depot,
+        routes,
+        unlocated,
+    }
+}
@@ -0,0 +1,341 @@
+use super::{
+    SpreaderBags, TimeCard, data_model_reports::make_report_query, get_bags_spread_per_user,
+    get_deliveries, get_purchase_to_map, get_spreading_completion, get_timecards, get_users,
+    is_timecard_excluded_group, time_val_str_to_duration,
+};
+use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+// Nobody works a delivery this long so it is most likely a typo
+pub const MAX_TIMECARD_DURATION: Duration = Duration::from_secs(12 * 60 * 60);
+
+////////////////////////////////////////////////////////////////////////////
+/// What a scout was doing for a span of time. Closeout can credit each one differently.
+#[derive(
+    Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum TimecardActivity {
+    #[default]
+    Delivery,
+    TruckLoading,
+    Spreading,
+}
+
+impl TimecardActivity {
+    pub fn all() -> [TimecardActivity; 3] {
+        [
+            TimecardActivity::Delivery,
+            TimecardActivity::TruckLoading,
+            TimecardActivity::Spreading,
+        ]
+    }
+
+    /// The value the api uses
+    pub fn as_api_str(&self) -> &'static str {
+        match self {
+            TimecardActivity::Delivery => "delivery",
+            TimecardActivity::TruckLoading => "truckLoading",
+            TimecardActivity::Spreading => "spreading",
+        }
+    }
+}
+
+impl std::fmt::Display for TimecardActivity {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TimecardActivity::Delivery => write!(f, "Delivery"),
+            TimecardActivity::TruckLoading => write!(f, "Truck Loading"),
+            TimecardActivity::Spreading => write!(f, "Spreading"),
+        }
+    }
+}
+
+impl std::str::FromStr for TimecardActivity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TimecardActivity::all()
+            .into_iter()
+            .find(|v| {
+                v.as_api_str().eq_ignore_ascii_case(s.trim())
+                    || v.to_string().eq_ignore_ascii_case(s.trim())
+            })
+            .ok_or_else(|| format!("'{s}' is not a valid value for TimecardActivity"))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone, PartialEq, Debug)]
+pub enum TimecardAnomalyKind {
+    InvalidTime,
+    TimeOutBeforeTimeIn,
+    TooLong,
+    MissingTimeOut,
+    // Group the user is in
+    ExcludedGroup(String),
+    // The delivery of the other timecard on the same day the times overlap with
+    Overlapping(u32),
+}
+
+impl std::fmt::Display for TimecardAnomalyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TimecardAnomalyKind::InvalidTime => write!(f, "Time in or time out isn't a valid time"),
+            TimecardAnomalyKind::TimeOutBeforeTimeIn => write!(f, "Time out isn't after time in"),
+            TimecardAnomalyKind::TooLong => write!(
+                f,
+                "Longer than {} hours",
+                MAX_TIMECARD_DURATION.as_secs() / (60 * 60)
+            ),
+            TimecardAnomalyKind::MissingTimeOut => write!(f, "Missing time out"),
+            TimecardAnomalyKind::ExcludedGroup(group) => {
+                write!(f, "{group} members don't get timecards")
+            }
+            TimecardAnomalyKind::Overlapping(delivery_id) => write!(
+                f,
+                "Overlaps with another timecard for delivery {}",
+                get_deliveries()
+                    .get(delivery_id)
+                    .map_or(delivery_id.to_string(), |v| v.get_delivery_date_str())
+            ),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone, PartialEq, Debug)]
+pub struct TimecardAnomaly {
+    pub timecard: TimeCard,
+    pub kind: TimecardAnomalyKind,
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Checks a time in/out pair the same way for the timesheet, imports and the anomaly report
+pub fn get_timecard_span_error(
+    time_in: &Duration,
+    time_out: &Duration,
+) -> Option<TimecardAnomalyKind> {
+    match time_out.checked_sub(*time_in) {
+        Some(total) if total.is_zero() => Some(TimecardAnomalyKind::TimeOutBeforeTimeIn),
+        Some(total) if total > MAX_TIMECARD_DURATION => Some(TimecardAnomalyKind::TooLong),
+        Some(_) => None,
+        None => Some(TimecardAnomalyKind::TimeOutBeforeTimeIn),
+    }
+}
+
+// Timecard with its time in and time out
+type TimecardSpan<'a> = (&'a TimeCard, Duration, Duration);
+
+////////////////////////////////////////////////////////////////////////////
+pub fn find_timecard_anomalies(timecards: &[TimeCard]) -> Vec<TimecardAnomaly> {
+    let users = get_users();
+    let deliveries = get_deliveries();
+    let mut anomalies = Vec::new();
+    // Keyed by uid and delivery date to find anyone at two deliveries at the same time
+    let mut spans: BTreeMap<(String, String), Vec<TimecardSpan>> = BTreeMap::new();
+
+    for tc in timecards {
+        if tc.time_in.is_empty() && tc.time_out.is_empty() {
+            continue;
+        }
+        let mut add_anomaly = |kind: TimecardAnomalyKind| {
+            anomalies.push(TimecardAnomaly {
+                timecard: tc.clone(),
+                kind,
+            });
+        };
+
+        if let Some(user_info) = users.get(&tc.uid)
+            && is_timecard_excluded_group(&user_info.group)
+        {
+            add_anomaly(TimecardAnomalyKind::ExcludedGroup(user_info.group.clone()));
+        }
+
+        if tc.time_out.is_empty() {
+            add_anomaly(TimecardAnomalyKind::MissingTimeOut);
+            continue;
+        }
+
+        let (Some(time_in), Some(time_out)) = (
+            time_val_str_to_duration(&tc.time_in),
+            time_val_str_to_duration(&tc.time_out),
+        ) else {
+            add_anomaly(TimecardAnomalyKind::InvalidTime);
+            continue;
+        };
+
+        if let Some(kind) = get_timecard_span_error(&time_in, &time_out) {
+            add_anomaly(kind);
+            continue;
+        }
+
+        if let Some(delivery) = deliveries.get(&tc.delivery_id) {
+            spans
+                .entry((tc.uid.clone(), delivery.get_delivery_date_str()))
+                .or_default()
+                .push((tc, time_in, time_out));
+        }
+    }
+
+    for day_spans in spans.values() {
+        for (tc, time_in, time_out) in day_spans {
+            anomalies.extend(
+                day_spans
+                    .iter()
+                    .filter(|(other_tc, other_in, other_out)| {
+                        (other_tc.delivery_id, &other_tc.span_id) != (tc.delivery_id, &tc.span_id)
+                            && time_in < other_out
+                            && other_in < time_out
+                    })
+                    .map(|(other_tc, _, _)| TimecardAnomaly {
+                        timecard: (*tc).clone(),
+                        kind: TimecardAnomalyKind::Overlapping(other_tc.delivery_id),
+                    }),
+            );
+        }
+    }
+
+    anomalies
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Anomalies across every delivery for the whole troop
+pub async fn get_timecard_anomalies() -> Result<Vec<TimecardAnomaly>, Box<dyn std::error::Error>> {
+    Ok(find_timecard_anomalies(&get_timecards(None).await?))
+}
+
+////////////////////////////////////////////////////////////////////////////
+// Service Hours
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone, PartialEq, Debug)]
+pub struct ServiceSpreadingJob {
+    pub order_id: String,
+    pub delivery_id: Option<u32>,
+    // Empty for jobs spread before completion was recorded
+    pub completed_date: String,
+    // This scout's share of the bags on the order
+    pub num_bags: Decimal,
+}
+
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ScoutServiceHours {
+    pub uid: String,
+    pub name: String,
+    pub group: String,
+    pub timecards: Vec<TimeCard>,
+    pub spreading_jobs: Vec<ServiceSpreadingJob>,
+}
+
+impl ScoutServiceHours {
+    pub fn get_activity_totals(&self) -> BTreeMap<TimecardActivity, Duration> {
+        let mut totals = BTreeMap::new();
+        for tc in &self.timecards {
+            *totals.entry(tc.activity).or_default() += tc.get_time_total();
+        }
+        totals
+    }
+
+    pub fn get_time_total(&self) -> Duration {
+        self.timecards.iter().map(|v| v.get_time_total()).sum()
+    }
+
+    pub fn get_num_bags_spread(&self) -> Decimal {
+        self.spreading_jobs.iter().map(|v| v.num_bags).sum()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+static SERVICE_SPREADING_JOBS_GRAPHQL: &str = r"
+{
+  mulchOrders(doGetSpreadOrdersOnly: true) {
+    orderId
+    deliveryId
+    purchases {
+        productId
+        numSold
+    }
+    spreaders
+    spreaderBags {
+        uid
+        numBags
+    }
+    spreadingCompletion {
+        completedDate
+    }
+  }
+}
+";
+
+////////////////////////////////////////////////////////////////////////////
+/// Everything each scout did across all of the deliveries sorted by name
+pub async fn get_service_hours() -> Result<Vec<ScoutServiceHours>, Box<dyn std::error::Error>> {
+    let mut service_map: BTreeMap<String, ScoutServiceHours> = BTreeMap::new();
+    fn get_entry<'a>(
+        service_map: &'a mut BTreeMap<String, ScoutServiceHours>,
+        uid: &str,
+    ) -> &'a mut ScoutServiceHours {
+        service_map.entry(uid.to_string()).or_insert_with(|| {
+            let user_info = get_users().get(uid).cloned();
+            ScoutServiceHours {
+                uid: uid.to_string(),
+                name: user_info
+                    .as_ref()
+                    .map_or(uid.to_string(), |v| v.name.clone()),
+                group: user_info.map_or("".to_string(), |v| v.group),
+                ..Default::default()
+            }
+        })
+    }
+
+    for tc in get_timecards(None).await? {
+        if tc.get_time_total().is_zero() {
+            continue;
+        }
+        get_entry(&mut service_map, &tc.uid).timecards.push(tc);
+    }
+
+    for order in make_report_query(SERVICE_SPREADING_JOBS_GRAPHQL.to_string()).await? {
+        let spreaders = order["spreaders"]
+            .as_array()
+            .map(|v| {
+                v.iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect::<Vec<String>>()
+            })
+            .unwrap_or_default();
+        let num_bags = *get_purchase_to_map(&order).get("spreading").unwrap_or(&0);
+        let Some(completion) = get_spreading_completion(&order) else {
+            continue;
+        };
+        if spreaders.is_empty() || 0 == num_bags {
+            continue;
+        }
+        let spreader_bags =
+            serde_json::from_value::<Vec<SpreaderBags>>(order["spreaderBags"].clone())
+                .unwrap_or_default();
+        for (uid, bags_per_spreader) in
+            get_bags_spread_per_user(&spreaders, &spreader_bags, num_bags as usize)
+        {
+            get_entry(&mut service_map, &uid)
+                .spreading_jobs
+                .push(ServiceSpreadingJob {
+                    order_id: order["orderId"].as_str().unwrap_or_default().to_string(),
+                    delivery_id: order["deliveryId"].as_u64().map(|v| v as u32),
+                    completed_date: completion.completed_date.clone(),
+                    num_bags: bags_per_spreader,
+                });
+        }
+    }
+
+    let mut service_hours = service_map.into_values().collect::<Vec<_>>();
+    for v in service_hours.iter_mut() {
+        v.timecards
+            .sort_by(|a, b| (a.delivery_id, &a.time_in).cmp(&(b.delivery_id, &b.time_in)));
+    }
+    service_hours.sort_by(|a, b| (&a.name, &a.uid).cmp(&(&b.name, &b.uid)));
+    Ok(service_hours)
+}
@@ -0,0 +1,65 @@
+use calamine::{Ods, RangeDeserializerBuilder, Reader, Xlsx, open_workbook_from_rs};
+use serde::de::DeserializeOwned;
+use std::io::Cursor;
+
+////////////////////////////////////////////////////////////////////////////
+fn read_csv_records<T: DeserializeOwned>(
+    data: Vec<u8>,
+) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+    let mut rdr = csv::Reader::from_reader(&data[..]);
+    let mut records = Vec::new();
+    for result in rdr.deserialize() {
+        records.push(result?);
+    }
+    Ok(records)
+}
+
+////////////////////////////////////////////////////////////////////////////
+fn read_spreadsheet_records<T, W>(mut wb: W) -> Result<Vec<T>, Box<dyn std::error::Error>>
+where
+    T: DeserializeOwned,
+    W: Reader<Cursor<Vec<u8>>>,
+{
+    let range = wb
+        .worksheet_range_at(0)
+        .ok_or_else(|| std::io::Error::other("The spreadsheet doesn't have any sheets"))?
+        .map_err(|_| calamine::Error::Msg("Cannot read the first sheet"))?;
+
+    let iter_records = RangeDeserializerBuilder::new()
+        .has_headers(true)
+        .from_range(&range)?;
+
+    let mut records = Vec::new();
+    for result in iter_records {
+        records.push(result?);
+    }
+    Ok(records)
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Reads the rows of an uploaded csv, xlsx or ods file. The first row is the header.
+pub fn read_uploaded_file_records<T: DeserializeOwned>(
+    filename: &str,
+    mimetype: &str,
+    data: Vec<u8>,
+) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+    // 2ndBatch.xlsx type: application/vnd.openxmlformats-officedocument.spreadsheetml.sheet
+    // 2ndBatch.ods type: application/vnd.oasis.opendocument.spreadsheet
+    let lc_filename = filename.to_ascii_lowercase();
+    match true {
+        _ if lc_filename.ends_with(".csv") || mimetype.eq("text/csv") => read_csv_records(data),
+        _ if lc_filename.ends_with(".xlsx")
+            || mimetype.eq("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet") =>
+        {
+            let wb: Xlsx<_> = open_workbook_from_rs(Cursor::new(data))?;
+            read_spreadsheet_records(wb)
+        }
+        _ if lc_filename.ends_with(".ods")
+            || mimetype.eq("application/vnd.oasis.opendocument.spreadsheet") =>
+        {
+            let wb: Ods<_> = open_workbook_from_rs(Cursor::new(data))?;
+            read_spreadsheet_records(wb)
+        }
+        _ => Err(format!("Unsupported file type: {filename}").into()),
+    }
+}
@@ -1,13 +1,23 @@
 mod currency_utils;
 mod data_model;
+mod data_model_inventory;
 mod data_model_orders;
+mod data_model_overrides;
 mod data_model_reports;
+mod data_model_routes;
+mod data_model_timecards;
+mod file_utils;
 mod gql_utils;
 
 pub use currency_utils::*;
 pub use data_model::*;
+pub use data_model_inventory::*;
 pub use data_model_orders::*;
+pub use data_model_overrides::*;
 pub use data_model_reports::*;
+pub use data_model_routes::*;
+pub use data_model_timecards::*;
+pub use file_utils::*;
 pub use js::auth_utils::{get_active_user, get_active_user_async};
 
 // Needed for HTML functions
@@ -41,6 +51,8 @@ pub enum AppRoutes {
     Reports,
     #[at("/timecards")]
     Timecards,
+    #[at("/timeclock")]
+    TimeClock,
     #[at("/frcloseout")]
     FundraiserCloseout,
     #[at("/frcconfig")]
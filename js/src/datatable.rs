@@ -21,8 +21,12 @@ extern "C" {
     fn removeRowWithTr(dt: &JsValue, tr: &web_sys::Node) -> Result<(), JsValue>;
 
     #[wasm_bindgen(catch)]
-    fn setSpreadersWithTr(dt: &JsValue, tr: &web_sys::Node, spreaders: &str)
-    -> Result<(), JsValue>;
+    fn setSpreadersWithTr(
+        dt: &JsValue,
+        tr: &web_sys::Node,
+        spreaders: &str,
+        spread_completed: &str,
+    ) -> Result<(), JsValue>;
 }
 
 pub fn get_datatable(params: &serde_json::Value) -> Option<DataTable> {
@@ -38,6 +42,7 @@ pub fn set_spreaders_with_tr(
     dt: &JsValue,
     tr: &web_sys::Node,
     spreaders: &str,
+    spread_completed: &str,
 ) -> Result<(), JsValue> {
-    setSpreadersWithTr(dt, tr, spreaders)
+    setSpreadersWithTr(dt, tr, spreaders, spread_completed)
 }
@@ -88,12 +88,13 @@ const getQuickViewReportDataTable = (params) => {
         { title: "Name", className: "all" },
         { title: "Delivery Date", name: "DeliveryDate", type: "string" },
         { title: "Spreaders", name: "Spreaders", visible: false },
+        { title: "Spread Completed", name: "SpreadCompleted", visible: false },
         {
             title: "Spreading",
             type: "string",
             render: (data, _, row, meta) => {
                 if (0 !== row[meta.col - 1].length) {
-                    return `${data}: Spread`
+                    return `${data}: Spread ${row[meta.col - 1]}`
                 } else {
                     return data;
                 }
@@ -177,12 +178,13 @@ const getFullViewReportDataTable = (params) => {
         { title: "Neighborhood" },
         { title: "Delivery Date", type: "string" },
         { title: "Spreaders", name: "Spreaders", visible: false },
+        { title: "Spread Completed", name: "SpreadCompleted", visible: false },
         {
             title: "Spreading",
             type: "string",
             render: (data, _, row, meta) => {
                 if (0 !== row[meta.col - 1].length) {
-                    return `${data}: Spread`
+                    return `${data}: Spread ${row[meta.col - 1]}`
                 } else {
                     return data;
                 }
@@ -232,7 +234,9 @@ const getSpreadingJobsUnfinishedViewReportDataTable = (params) => {
         { title: "Order Owner", name: "OrderOwner" },
         { title: "Name" },
         { title: "Delivery Date", type: "string" },
-        { title: "Bags Left To Spread", className: "all", type: "string" }
+        { title: "Bags Left To Spread", className: "all", type: "string" },
+        { title: "Bags Committed", type: "string" },
+        { title: "Committed Spreaders" }
     ];
 
     return new DataTable(params.id, getCommonDtOptions(tableColumns));
@@ -251,12 +255,13 @@ const getSpreadingJobsViewReportDataTable = (params) => {
         { title: "Address" },
         { title: "Neighborhood", className: "all" },
         { title: "Spreaders", name: "Spreaders", visible: false },
+        { title: "Spread Completed", name: "SpreadCompleted", visible: false },
         {
             title: "Spreading",
             type: "string",
             render: (data, _, row, meta) => {
                 if (0 !== row[meta.col - 1].length) {
-                    return `${data}: Spread`
+                    return `${data}: Spread ${row[meta.col - 1]}`
                 } else {
                     return data;
                 }
@@ -284,6 +289,49 @@ const getSpreadingAssistJobsViewReportDataTable = (params) => {
     return new DataTable(params.id, getCommonDtOptions(tableColumns));
 };
 
+/////////////////////////////////////////////////////////////////////
+//
+const getCancelledOrdersViewReportDataTable = (params) => {
+    console.log("Setting Cancelled Orders Report View");
+    let tableColumns = [
+        { name: "OrderId", className: "all", visible: false },
+        { title: "Name", className: "all" },
+        { title: "Phone", type: "string" },
+        { title: "Delivery Date", type: "string" },
+        { title: "Total Amount" },
+        { title: "Refund Due" },
+        { title: "Reason" },
+        { title: "Cancelled By" },
+        { title: "Cancelled On", type: "string" },
+        { title: "Order Owner", name: "OrderOwner", visible: params.showOrderOwner },
+        { title: "Actions", "orderable": false, className: "all" }
+    ];
+
+    return new DataTable(params.id, getCommonDtOptions(tableColumns));
+};
+
+/////////////////////////////////////////////////////////////////////
+//
+const getLoadManifestsReportDataTable = (params) => {
+    console.log("Setting Load Manifests Report View");
+    const tableColumns = [
+        { title: "Truck", className: "all" },
+        { title: "Load #", type: "num" },
+        { title: "Stop #", type: "num" },
+        { title: "OrderId", name: "OrderId", visible: false },
+        { title: "Name", className: "all" },
+        { title: "Address" },
+        { title: "Bags", type: "num", className: "all" },
+        { title: "Spreading", type: "num" },
+        { title: "Notes" },
+        { title: "Running Total", className: "all" },
+    ];
+    const dtOpts = getCommonDtOptions(tableColumns);
+    // Rows are generated in loading order so keep them that way
+    dtOpts["order"] = [];
+    return new DataTable(params.id, dtOpts);
+};
+
 /////////////////////////////////////////////////////////////////////
 //
 const getDataTable = (mapOfParams) => {
@@ -309,6 +357,10 @@ const getDataTable = (mapOfParams) => {
         return getMoneyCollectionReportDataTable(params);
     } else if (params.reportType === "spreadingJobsUnfinished") {
         return getSpreadingJobsUnfinishedViewReportDataTable(params);
+    } else if (params.reportType === "cancelledOrders") {
+        return getCancelledOrdersViewReportDataTable(params);
+    } else if (params.reportType === "loadManifests") {
+        return getLoadManifestsReportDataTable(params);
     }
     return undefined;
 };
@@ -322,12 +374,14 @@ const removeRowWithTr = (dt, tr) => {
 
 /////////////////////////////////////////////////////////////////////
 //
-const setSpreadersWithTr = (dt, tr, spreaders) => {
+const setSpreadersWithTr = (dt, tr, spreaders, spreadCompleted) => {
     const row = dt.row(tr);
     //const rowData = row.data();
     const spreadersIdx = dt.column('Spreaders:name').index();
+    const spreadCompletedIdx = dt.column('SpreadCompleted:name').index();
     //rowData[spreadersIdx] = spreaders;
-    dt.cell(row.index(), spreadersIdx).data(spreaders).draw();
+    dt.cell(row.index(), spreadersIdx).data(spreaders);
+    dt.cell(row.index(), spreadCompletedIdx).data(spreadCompleted).draw();
     //row.data(rowData).draw();
 };
 
@@ -3,6 +3,8 @@ import L, {
   TileLayer,
   GeoJSON,
   CircleMarker,
+  Polyline,
+  LatLngBounds,
 } from "leaflet";
 
 /////////////////////////////////////////////////////////////////////
@@ -38,4 +40,69 @@ const createSellMap = (params) => {
   return map;
 };
 
-export { createSellMap };
+/////////////////////////////////////////////////////////////////////
+//
+const ROUTE_COLORS = [
+  "#e6194b",
+  "#3cb44b",
+  "#4363d8",
+  "#f58231",
+  "#911eb4",
+  "#42d4f4",
+  "#f032e6",
+  "#9a6324",
+];
+
+/////////////////////////////////////////////////////////////////////
+//
+const createRouteMap = (params) => {
+  console.log("Creating Route Map");
+
+  const map = new LeafletMap(params.id);
+
+  new TileLayer("https://tile.openstreetmap.org/{z}/{x}/{y}.png", {
+    attribution:
+      '&copy; <a href="http://www.openstreetmap.org/copyright">OpenStreetMap</a>',
+  }).addTo(map);
+
+  const bounds = new LatLngBounds([]);
+  params.routes.forEach((route, routeIdx) => {
+    const color = ROUTE_COLORS[routeIdx % ROUTE_COLORS.length];
+    const path = [route.depot, ...route.stops.map((stop) => stop.pt)];
+    new Polyline(path, { color: color, weight: 3 })
+      .bindTooltip(route.label)
+      .addTo(map);
+
+    new CircleMarker(route.depot, {
+      radius: 10,
+      fillColor: "#000",
+      color: "#000",
+      fillOpacity: 0.8,
+    })
+      .bindTooltip(route.depotLabel)
+      .addTo(map);
+
+    route.stops.forEach((stop, stopIdx) => {
+      new CircleMarker(stop.pt, {
+        radius: 7,
+        fillColor: color,
+        color: "#000",
+        weight: 1,
+        fillOpacity: 0.9,
+      })
+        .bindTooltip(`${route.label} #${stopIdx + 1}: ${stop.label}`)
+        .addTo(map);
+    });
+    path.forEach((pt) => bounds.extend(pt));
+  });
+
+  if (bounds.isValid()) {
+    map.fitBounds(bounds, { padding: [20, 20] });
+  } else {
+    map.setView(params.centerPt, 12);
+  }
+
+  return map;
+};
+
+export { createSellMap, createRouteMap };
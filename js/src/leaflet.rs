@@ -6,12 +6,18 @@ use wasm_bindgen::prelude::*;
 extern "C" {
     #[wasm_bindgen]
     pub type Map;
+
+    #[wasm_bindgen(method)]
+    pub fn remove(this: &Map);
 }
 
 #[wasm_bindgen(module = "/src/js/leaflet.js")]
 extern "C" {
     #[wasm_bindgen(catch)]
     fn createSellMap(params: &JsValue) -> Result<Map, JsValue>;
+
+    #[wasm_bindgen(catch)]
+    fn createRouteMap(params: &JsValue) -> Result<Map, JsValue>;
 }
 
 pub fn create_sell_map(params: &serde_json::Value) -> Option<Map> {
@@ -20,3 +26,9 @@ pub fn create_sell_map(params: &serde_json::Value) -> Option<Map> {
     let serialized_params = (*params).serialize(&serializer).unwrap();
     createSellMap(&serialized_params).ok()
 }
+
+pub fn create_route_map(params: &serde_json::Value) -> Option<Map> {
+    let serializer = Serializer::new().serialize_maps_as_objects(true);
+    let serialized_params = (*params).serialize(&serializer).unwrap();
+    createRouteMap(&serialized_params).ok()
+}
@@ -1,5 +1,5 @@
 use data_model::*;
-use tracing::info;
+use tracing::{error, info};
 use wasm_bindgen::JsCast;
 use web_sys::{HtmlSelectElement, InputEvent};
 use yew::prelude::*;
@@ -9,9 +9,41 @@ pub struct DeliveryDateSelectorProps {
     pub on_delivery_change: Callback<Option<u32>>,
 }
 
+/////////////////////////////////////////////////
+fn gen_delivery_option_label(delivery_id: u32, delivery: &DeliveryInfo) -> String {
+    let delivery_date = delivery.get_delivery_date_str();
+    match get_active_order_bags_remaining(delivery_id) {
+        None => delivery_date,
+        Some(remaining) if remaining > 0 => format!("{delivery_date} ({remaining} bags left)"),
+        Some(_) if delivery.is_waitlist_enabled() => format!("{delivery_date} (Full - Waitlist)"),
+        Some(_) => format!("{delivery_date} (Full)"),
+    }
+}
+
+/////////////////////////////////////////////////
+fn is_delivery_full(delivery_id: u32, delivery: &DeliveryInfo) -> bool {
+    !delivery.is_waitlist_enabled()
+        && get_active_order_bags_remaining(delivery_id).is_some_and(|v| v <= 0)
+}
+
 #[component(DeliveryDateSelector)]
 pub fn delivery_date_selector(props: &DeliveryDateSelectorProps) -> Html {
     let order = get_active_order().unwrap();
+    let are_bag_counts_loaded = use_state_eq(|| false);
+
+    {
+        let are_bag_counts_loaded = are_bag_counts_loaded.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                // Without the counts the dates are still shown just without capacity info
+                if let Err(err) = load_delivery_bag_counts().await {
+                    error!("Failed to load delivery bag counts: {err:#?}");
+                }
+                are_bag_counts_loaded.set(true);
+            });
+            || {}
+        });
+    }
 
     let on_input = {
         let on_delivery_change = props.on_delivery_change.clone();
@@ -35,6 +67,8 @@ pub fn delivery_date_selector(props: &DeliveryDateSelectorProps) -> Html {
 
     let mut found_selected_delivery = false;
     let is_admin = get_active_user().is_admin();
+    // Sellers with an extension can still pick dates that are past their cutoff
+    let has_cutoff_extension = does_active_user_have_cutoff_extension();
 
     html! {
         <div class="delivery-selector-widget">
@@ -51,13 +85,17 @@ pub fn delivery_date_selector(props: &DeliveryDateSelectorProps) -> Html {
                             found_selected_delivery = true;
                             html!{
                                 <option value={delivery_id.to_string()} selected={is_selected}>
-                                    {delivery.get_delivery_date_str()}
+                                    {gen_delivery_option_label(*delivery_id, delivery)}
                                 </option>
                             }
-                        } else if is_admin || delivery.can_take_orders() {
+                        } else if is_admin
+                            || ((has_cutoff_extension || delivery.can_take_orders())
+                                && !delivery.is_locked()
+                                && !is_delivery_full(*delivery_id, delivery))
+                        {
                             html!{
                                 <option value={delivery_id.to_string()} selected={is_selected}>
-                                    {delivery.get_delivery_date_str()}
+                                    {gen_delivery_option_label(*delivery_id, delivery)}
                                 </option>
                             }
                         } else {
@@ -69,6 +107,11 @@ pub fn delivery_date_selector(props: &DeliveryDateSelectorProps) -> Html {
                     <option value="none" selected=true disabled=true hidden=true>{"Select delivery date"}</option>
                 }
             </select>
+            if let Some(delivery) = get_deliveries().get(&order.delivery_id) {
+                <small class="text-muted">
+                    {format!("Orders for this date close {}", delivery.get_new_order_cutoff_display_str())}
+                </small>
+            }
         </div>
     }
 }
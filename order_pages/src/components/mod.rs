@@ -1,4 +1,6 @@
 mod add_new_order_button;
 pub(crate) mod delivery_selector;
+pub(crate) mod order_history;
+pub(crate) mod order_refunds;
 
 pub use add_new_order_button::*;
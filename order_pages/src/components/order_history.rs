@@ -0,0 +1,94 @@
+use data_model::*;
+use tracing::info;
+use yew::prelude::*;
+
+/////////////////////////////////////////////////
+fn gen_field_change_html(change: &OrderFieldChange) -> Html {
+    let from = if change.from.is_empty() {
+        "(empty)"
+    } else {
+        change.from.as_str()
+    };
+    let to = if change.to.is_empty() {
+        "(empty)"
+    } else {
+        change.to.as_str()
+    };
+    html! {
+        <li>
+            <b>{format!("{}: ", &change.field)}</b>
+            <span class="text-muted">{from}</span>
+            {" → "}
+            <span>{to}</span>
+        </li>
+    }
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(OrderHistory)]
+pub fn order_history() -> Html {
+    let order_id = get_active_order().unwrap().order_id;
+    let history: UseStateHandle<Option<Vec<OrderHistoryEvent>>> = use_state_eq(|| None);
+
+    {
+        let history = history.clone();
+        use_effect_with(order_id, move |order_id| {
+            let order_id = order_id.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Loading history for order: {order_id}");
+                match get_order_history(&order_id).await {
+                    Ok(events) => history.set(Some(events)),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to load order history: {err:#?}"));
+                        history.set(Some(Vec::new()));
+                    }
+                }
+            });
+            || {}
+        });
+    }
+
+    html! {
+        <div class="card mt-2">
+            <div class="card-body">
+                <h5 class="card-title">{"Order History"}</h5>
+                {
+                    match &*history {
+                        None => html! {
+                            <div class="spinner-border spinner-border-sm" role="status">
+                                <span class="visually-hidden">{"Loading..."}</span>
+                            </div>
+                        },
+                        Some(events) if events.is_empty() => html! {
+                            <small class="text-muted">{"No changes have been recorded for this order"}</small>
+                        },
+                        Some(events) => html! {
+                            <ul class="list-group">
+                            {
+                                events.iter().map(|event| {
+                                    let changed_by = get_username_from_id(&event.changed_by)
+                                        .unwrap_or(event.changed_by.clone());
+                                    html! {
+                                        <li class="list-group-item">
+                                            <div class="d-flex justify-content-between">
+                                                <div class="text-capitalize">{event.action.clone()}</div>
+                                                <small class="text-muted">
+                                                    {format!("{} {}", changed_by, event.get_change_time_str())}
+                                                </small>
+                                            </div>
+                                            <ul class="small mb-0">
+                                                {event.changes.iter().map(gen_field_change_html).collect::<Html>()}
+                                            </ul>
+                                        </li>
+                                    }
+                                }).collect::<Html>()
+                            }
+                            </ul>
+                        },
+                    }
+                }
+            </div>
+        </div>
+    }
+}
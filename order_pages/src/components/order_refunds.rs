@@ -0,0 +1,145 @@
+use data_model::*;
+use rust_decimal::prelude::*;
+use tracing::info;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    HtmlButtonElement, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement, MouseEvent,
+};
+use yew::prelude::*;
+
+/////////////////////////////////////////////////
+fn disable_add_refund_button(document: &web_sys::Document, value: bool) {
+    if let Some(btn) = document
+        .get_element_by_id("btnAddRefund")
+        .and_then(|t| t.dyn_into::<HtmlButtonElement>().ok())
+    {
+        btn.set_disabled(value);
+    }
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(OrderRefunds)]
+pub fn order_refunds() -> Html {
+    let order = get_active_order().unwrap();
+    let refunds = use_state_eq(|| order.refunds.clone().unwrap_or_default());
+
+    let on_add_refund = {
+        let refunds = refunds.clone();
+        let order_id = order.order_id.clone();
+        let total_collected = order.get_total_collected();
+        Callback::from(move |evt: MouseEvent| {
+            evt.prevent_default();
+            evt.stop_propagation();
+            let document = gloo::utils::document();
+
+            let amount = get_html_input_value("formRefundAmount", &document)
+                .filter(|v| Decimal::from_str(v).is_ok_and(|v| v > Decimal::ZERO));
+            let reason = get_html_textarea_value("formRefundReason", &document);
+            let (Some(amount), Some(reason)) = (amount, reason) else {
+                gloo::dialogs::alert("A refund needs an amount greater than zero and a reason");
+                return;
+            };
+            let already_refunded = MulchOrder {
+                refunds: Some((*refunds).clone()),
+                ..Default::default()
+            }
+            .get_total_refunded();
+            let refundable = total_collected.checked_sub(already_refunded).unwrap();
+            if Decimal::from_str(&amount).is_ok_and(|v| v > refundable) {
+                gloo::dialogs::alert(&format!(
+                    "A refund can't be more than what is left of the amount collected: {}",
+                    decimal_to_money_string(&refundable)
+                ));
+                return;
+            }
+            let method = get_element::<HtmlSelectElement>("formRefundMethod", &document).value();
+
+            disable_add_refund_button(&document, true);
+            let refunds = refunds.clone();
+            let order_id = order_id.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Adding refund of {amount} to order: {order_id}");
+                let refund = RefundRecord {
+                    amount,
+                    method,
+                    reason,
+                    ..Default::default()
+                };
+                match add_order_refund(&order_id, &refunds, refund).await {
+                    Ok(refund) => {
+                        let mut new_refunds = (*refunds).clone();
+                        new_refunds.push(refund);
+                        refunds.set(new_refunds);
+                        get_element::<HtmlInputElement>("formRefundAmount", &document)
+                            .set_value("");
+                        get_element::<HtmlTextAreaElement>("formRefundReason", &document)
+                            .set_value("");
+                    }
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to record refund: {err:#?}"));
+                    }
+                }
+                disable_add_refund_button(&document, false);
+            });
+        })
+    };
+
+    let total_refunded = MulchOrder {
+        refunds: Some((*refunds).clone()),
+        ..Default::default()
+    }
+    .get_total_refunded();
+
+    html! {
+        <div class="card mt-2">
+            <div class="card-body">
+                <h5 class="card-title">
+                    {"Refunds"}
+                    <small class="text-muted ms-2">
+                        {format!("Total Refunded: {}", decimal_to_money_string(&total_refunded))}
+                    </small>
+                </h5>
+                <ul class="list-group mb-2">
+                {
+                    refunds.iter().map(|refund| {
+                        let refunded_by = get_username_from_id(&refund.refunded_by)
+                            .unwrap_or(refund.refunded_by.clone());
+                        html! {
+                            <li class="list-group-item">
+                                <div class="d-flex justify-content-between">
+                                    <div>{format!("{} ({})", to_money_str(Some(refund.amount.as_str())), &refund.method)}</div>
+                                    <small class="text-muted">{format!("{} {}", refunded_by, refund.get_refund_time_str())}</small>
+                                </div>
+                                <small>{refund.reason.clone()}</small>
+                            </li>
+                        }
+                    }).collect::<Html>()
+                }
+                </ul>
+                <div class="row mb-2 g-2">
+                    <div class="form-floating col-md-3">
+                        <input class="form-control" type="number" min="0" step="any"
+                               id="formRefundAmount" placeholder="0.00"/>
+                        <label for="formRefundAmount">{"Refund Amount"}</label>
+                    </div>
+                    <div class="form-floating col-md-3">
+                        <select class="form-control" id="formRefundMethod">
+                            <option value="Cash" selected=true>{"Cash"}</option>
+                            <option value="Check">{"Check"}</option>
+                            <option value="Other">{"Other"}</option>
+                        </select>
+                        <label for="formRefundMethod">{"Refund Method"}</label>
+                    </div>
+                    <div class="form-floating col-md-6">
+                        <textarea class="form-control" id="formRefundReason" rows="1"/>
+                        <label for="formRefundReason">{"Reason"}</label>
+                    </div>
+                </div>
+                <button type="button" class="btn btn-primary" id="btnAddRefund" onclick={on_add_refund}>
+                    {"Add Refund"}
+                </button>
+            </div>
+        </div>
+    }
+}
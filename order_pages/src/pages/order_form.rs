@@ -1,4 +1,6 @@
 use crate::components::delivery_selector::DeliveryDateSelector;
+use crate::components::order_history::OrderHistory;
+use crate::components::order_refunds::OrderRefunds;
 use data_model::*;
 use rust_decimal::prelude::*;
 use rusty_money::{Money, iso};
@@ -183,6 +185,48 @@ fn validate_order_form(document: &web_sys::Document) -> bool {
     is_valid
 }
 
+/////////////////////////////////////////////////
+// Returns false if the order shouldn't be submitted because the delivery doesn't have room
+fn check_delivery_capacity() -> bool {
+    let mut order = get_active_order().unwrap();
+    let num_bags = order.get_num_sold("bags").unwrap_or(0) as i64;
+    let Some(remaining) = get_active_order_bags_remaining(order.delivery_id) else {
+        return true;
+    };
+    if 0 == num_bags || num_bags <= remaining || order.is_waitlisted() {
+        return true;
+    }
+
+    let remaining = remaining.max(0);
+    if get_active_user().is_admin() {
+        return gloo::dialogs::confirm(&format!(
+            "This order has {num_bags} bags but only {remaining} bags are left for this delivery date.\n\
+             Submit the order anyway?"
+        ));
+    }
+
+    let is_waitlist_enabled = get_deliveries()
+        .get(&order.delivery_id)
+        .is_some_and(|v| v.is_waitlist_enabled());
+    if !is_waitlist_enabled {
+        gloo::dialogs::alert(&format!(
+            "This order has {num_bags} bags but only {remaining} bags are left for this delivery date.\n\
+             Please choose another delivery date."
+        ));
+        return false;
+    }
+
+    if !gloo::dialogs::confirm(&format!(
+        "This order has {num_bags} bags but only {remaining} bags are left for this delivery date.\n\
+         Add the order to the waitlist for this date?"
+    )) {
+        return false;
+    }
+    order.set_waitlisted(true);
+    update_active_order(order).unwrap();
+    true
+}
+
 /////////////////////////////////////////////////
 /////////////////////////////////////////////////
 #[component(RequiredSmall)]
@@ -450,6 +494,10 @@ pub fn order_form_fields() -> Html {
                 info!("Form isn't valid refusing submission");
                 disable_submit_button(&document, false, false);
                 disable_cancel_button(&document, false);
+            } else if !check_delivery_capacity() {
+                info!("Delivery doesn't have room refusing submission");
+                disable_submit_button(&document, false, false);
+                disable_cancel_button(&document, false);
             } else {
                 // Send request
                 wasm_bindgen_futures::spawn_local(async move {
@@ -734,6 +782,9 @@ pub fn order_form_fields() -> Html {
                     <DeliveryDateSelector
                         on_delivery_change={on_delivery_selection_change.clone()}
                     />
+                    if order.is_waitlisted() {
+                        <span class="badge bg-warning text-dark">{"Waitlisted"}</span>
+                    }
                 </div>
             </div>
 
@@ -837,6 +888,7 @@ pub fn order_form_fields() -> Html {
 
 #[component(OrderForm)]
 pub fn order_form() -> Html {
+    let is_existing_order = is_active_order() && is_active_order_from_db();
     html! {
         <div class="col-xs-1 justify-content-center">
             <div class="card">
@@ -845,6 +897,12 @@ pub fn order_form() -> Html {
                     <OrderFormFields/>
                 </div>
             </div>
+            if is_existing_order && get_active_user().is_admin() {
+                <OrderRefunds/>
+            }
+            if is_existing_order {
+                <OrderHistory/>
+            }
         </div>
     }
 }
@@ -50,6 +50,12 @@ pub(crate) struct ReportActionButtonsProps {
     pub(crate) oneditspreading: Callback<MouseEvent>,
     #[prop_or_default]
     pub(crate) spreaders: AttrValue,
+    #[prop_or_default]
+    pub(crate) spreadingbags: u64,
+    #[prop_or_default]
+    pub(crate) spreaderbags: AttrValue,
+    #[prop_or_default]
+    pub(crate) spreadingcompletion: AttrValue,
 }
 
 #[component(ReportActionButtons)]
@@ -61,6 +67,9 @@ pub(crate) fn report_action_buttons(props: &ReportActionButtonsProps) -> Html {
                 onclick={props.oneditspreading.clone()}
                 data-orderid={props.orderid.clone()}
                 data-spreaders={props.spreaders.as_str().to_string()}
+                data-spreadingbags={props.spreadingbags.to_string()}
+                data-spreaderbags={props.spreaderbags.as_str().to_string()}
+                data-spreadingcompletion={props.spreadingcompletion.as_str().to_string()}
                 data-bs-toggle="tooltip" title="Select Spreaders" data-bs-placement="left">
                  <i class="bi bi-layout-wtf" fill="currentColor" />
             </button>
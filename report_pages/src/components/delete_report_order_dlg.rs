@@ -4,7 +4,10 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use tracing::info;
 use wasm_bindgen::{JsCast, JsValue};
-use web_sys::{Element, HtmlButtonElement, HtmlElement, HtmlInputElement, InputEvent, MouseEvent};
+use web_sys::{
+    Element, HtmlButtonElement, HtmlElement, HtmlInputElement, HtmlTextAreaElement, InputEvent,
+    MouseEvent,
+};
 use yew::prelude::*;
 
 thread_local! {
@@ -68,13 +71,11 @@ pub(crate) fn on_delete_order_from_rpt(evt: MouseEvent, datatable: Rc<RefCell<Op
 #[component(DeleteOrderDlg)]
 pub(crate) fn delete_order_confirmation_dlg() -> Html {
     let on_confirm_input = {
-        Callback::from(move |evt: InputEvent| {
-            let value = evt
-                .target()
-                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
-                .unwrap()
-                .value();
-            if "delete" == &value {
+        Callback::from(move |_evt: InputEvent| {
+            let document = gloo::utils::document();
+            let value = get_html_input_value("confirmDeleteOrderInput", &document);
+            let reason = get_html_textarea_value("deleteOrderReason", &document);
+            if Some("delete") == value.as_deref() && reason.is_some_and(|v| !v.trim().is_empty()) {
                 gloo::utils::document()
                     .get_element_by_id("deleteDlgBtn")
                     .and_then(|t| t.dyn_into::<HtmlButtonElement>().ok())
@@ -102,7 +103,10 @@ pub(crate) fn delete_order_confirmation_dlg() -> Html {
                 wasm_bindgen_futures::spawn_local(async move {
                     let maybe_to_delete_order = f.borrow().as_ref().map(|v| v.clone());
                     if let Some(to_delete) = maybe_to_delete_order {
-                        if let Err(err) = delete_order(&to_delete.order_id).await {
+                        let document = gloo::utils::document();
+                        let reason = get_html_textarea_value("deleteOrderReason", &document)
+                            .unwrap_or_default();
+                        if let Err(err) = cancel_order(&to_delete.order_id, &reason).await {
                             gloo::dialogs::alert(&format!(
                                 "Failed to delete order in the cloud: {err:#?}"
                             ));
@@ -115,11 +119,16 @@ pub(crate) fn delete_order_confirmation_dlg() -> Html {
                         }
 
                         to_delete.delete_dlg.hide();
-                        gloo::utils::document()
+                        document
                             .get_element_by_id("confirmDeleteOrderInput")
                             .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
                             .unwrap()
                             .set_value("");
+                        document
+                            .get_element_by_id("deleteOrderReason")
+                            .and_then(|t| t.dyn_into::<HtmlTextAreaElement>().ok())
+                            .unwrap()
+                            .set_value("");
                     }
                     *f.borrow_mut() = None;
 
@@ -143,11 +152,16 @@ pub(crate) fn delete_order_confirmation_dlg() -> Html {
                         </h5>
                     </div>
                     <div class="modal-body">
+                        <div class="form-floating mb-2">
+                            <textarea class="form-control" id="deleteOrderReason"
+                                oninput={on_confirm_input.clone()} />
+                            <label for="deleteOrderReason">{"Reason for cancelling the order"}</label>
+                        </div>
                         <input type="text" class="form-control" id="confirmDeleteOrderInput"
                                placeholder="type delete to confirm" autocomplete="fr-new-cust-info"
                                oninput={on_confirm_input.clone()} aria-describedby="confirmDeleteOrderHelp" />
                         <small id="confirmDeleteOrderHelp" class="form-text text-muted">
-                            {"Enter \"delete\" to confirm order deletion."}
+                            {"Enter \"delete\" to confirm order deletion. The order can be restored from the Cancelled Orders report."}
                         </small>
 
                     </div>
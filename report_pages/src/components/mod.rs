@@ -1,14 +1,24 @@
 pub(crate) mod action_report_buttons;
 pub(crate) mod delete_report_order_dlg;
 pub(crate) mod report_assisted_spreading_jobs;
+pub(crate) mod report_cancelled_orders;
 pub(crate) mod report_deliveries;
+pub(crate) mod report_delivery_progress;
 pub(crate) mod report_distribution_points;
+pub(crate) mod report_driver_view;
 pub(crate) mod report_full;
+pub(crate) mod report_inventory;
+pub(crate) mod report_load_manifests;
 pub(crate) mod report_loading_spinny;
 pub(crate) mod report_money_collection;
 pub(crate) mod report_quick;
+pub(crate) mod report_route_planner;
 pub(crate) mod report_sell_map;
+pub(crate) mod report_service_hours;
 pub(crate) mod report_spreaders_dlg;
+pub(crate) mod report_spreading_job_board;
 pub(crate) mod report_spreading_jobs;
 pub(crate) mod report_spreading_jobs_unfinished;
+pub(crate) mod report_supplier_orders;
+pub(crate) mod report_timecard_anomalies;
 pub(crate) mod report_verify;
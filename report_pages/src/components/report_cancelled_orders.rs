@@ -0,0 +1,167 @@
+use crate::components::report_loading_spinny::*;
+use data_model::*;
+use js::datatable::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use tracing::info;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlButtonElement, HtmlElement, MouseEvent};
+use yew::prelude::*;
+
+/////////////////////////////////////////////////
+fn on_restore_order_from_rpt(evt: MouseEvent, datatable: Rc<RefCell<Option<DataTable>>>) {
+    evt.prevent_default();
+    evt.stop_propagation();
+    let btn_elm = evt
+        .target()
+        .and_then(|t| t.dyn_into::<Element>().ok())
+        .and_then(|t| {
+            if t.node_name() == "I" {
+                t.parent_element()
+            } else {
+                Some(t)
+            }
+        })
+        .unwrap();
+    let table_row_node = btn_elm.parent_node().and_then(|t| t.parent_node()).unwrap();
+    let btn_elm = btn_elm.dyn_into::<HtmlElement>().ok().unwrap();
+    let order_id = btn_elm.dataset().get("orderid").unwrap();
+    let datatable = (*datatable.borrow().as_ref().unwrap()).clone();
+
+    info!("on_restore_order: {order_id}");
+    if !gloo::dialogs::confirm("Restore this order so it shows up in the reports again?") {
+        return;
+    }
+
+    if let Ok(btn) = btn_elm.dyn_into::<HtmlButtonElement>() {
+        btn.set_disabled(true);
+    }
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(err) = restore_order(&order_id).await {
+            gloo::dialogs::alert(&format!("Failed to restore order: {order_id}: {err:#?}"));
+        } else if let Err(err) = remove_row_with_tr(&datatable, &table_row_node) {
+            gloo::dialogs::alert(&format!(
+                "Order was restored in the cloud but not the local table: {err:#?}"
+            ));
+        }
+    });
+}
+
+/////////////////////////////////////////////////
+fn get_cancelled_time_str(v: &serde_json::Value) -> String {
+    v["cancellation"]["cancelledTime"]
+        .as_str()
+        .map_or("".to_string(), |v| {
+            format_fundraiser_datetime(v, "%m/%d/%Y %I:%M %p")
+        })
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(CancelledOrdersReportView)]
+pub(crate) fn report_cancelled_orders_view() -> Html {
+    let report_state = use_state(|| ReportViewState::IsLoading);
+    let datatable: Rc<RefCell<Option<DataTable>>> = use_mut_ref(|| None);
+
+    let on_restore_order = {
+        let datatable = datatable.clone();
+        Callback::from(move |evt: MouseEvent| {
+            on_restore_order_from_rpt(evt, datatable.clone());
+        })
+    };
+
+    {
+        let report_state = report_state.clone();
+        use_effect(move || {
+            match &*report_state {
+                ReportViewState::IsLoading => {
+                    wasm_bindgen_futures::spawn_local(async move {
+                        info!("Downloading Cancelled Orders Report View Data");
+                        let resp = get_cancelled_orders_report_data().await.unwrap();
+                        info!("Report Data has been downloaded");
+                        report_state.set(ReportViewState::ReportHtmlGenerated(resp));
+                    });
+                }
+                ReportViewState::ReportHtmlGenerated(_) => {
+                    *datatable.borrow_mut() = get_datatable(&serde_json::json!({
+                        "reportType": "cancelledOrders",
+                        "id": ".data-table-report table",
+                        "showOrderOwner": true,
+                    }));
+                }
+            };
+
+            || {}
+        });
+    }
+
+    match &*report_state {
+        ReportViewState::IsLoading => html! { <ReportLoadingSpinny/> },
+        ReportViewState::ReportHtmlGenerated(orders) => {
+            let header_footer = html! {
+                <tr>
+                    <th>{"OrderId"}</th>
+                    <th>{"Name"}</th>
+                    <th>{"Phone"}</th>
+                    <th>{"Delivery Date"}</th>
+                    <th>{"Total Amount"}</th>
+                    <th>{"Refund Due"}</th>
+                    <th>{"Reason"}</th>
+                    <th>{"Cancelled By"}</th>
+                    <th>{"Cancelled On"}</th>
+                    <th>{"Order Owner"}</th>
+                    <th>{"Actions"}</th>
+                </tr>
+            };
+            html! {
+                <div class="data-table-report">
+                    <table class="display responsive nowrap collapsed" role="grid" cellspacing="0" width="100%">
+                        <thead>
+                            {header_footer.clone()}
+                        </thead>
+                        <tbody>
+                        {
+                            orders.iter().map(|v|{
+                                let delivery_date = match v["deliveryId"].as_u64() {
+                                    Some(delivery_id) => get_delivery_date(&(delivery_id as u32)),
+                                    None => "Donation".to_string(),
+                                };
+                                let uid = v["ownerId"].as_str().unwrap();
+                                let cancelled_by = v["cancellation"]["cancelledBy"].as_str().unwrap_or("");
+                                html!{
+                                    <tr>
+                                        <td>{v["orderId"].as_str().unwrap()}</td>
+                                        <td>{v["customer"]["name"].as_str().unwrap()}</td>
+                                        <td>{v["customer"]["phone"].as_str().unwrap()}</td>
+                                        <td>{delivery_date}</td>
+                                        <td>{to_money_str(v["amountTotalCollected"].as_str())}</td>
+                                        <td>
+                                            if is_refund_due_from_report_data(v) {
+                                                <span class="badge bg-danger">{"Refund Due"}</span>
+                                            }
+                                        </td>
+                                        <td>{v["cancellation"]["reason"].as_str().unwrap_or("")}</td>
+                                        <td>{get_username_from_id(cancelled_by).unwrap_or(cancelled_by.to_string())}</td>
+                                        <td>{get_cancelled_time_str(v)}</td>
+                                        <td>{get_username_from_id(uid).map_or(uid.to_string(), |v|format!("{v}[{uid}]"))}</td>
+                                        <td>
+                                            <button type="button" class="btn btn-outline-info me-1 order-restore-btn"
+                                                onclick={on_restore_order.clone()} data-orderid={v["orderId"].as_str().unwrap().to_string()}
+                                                data-bs-toggle="tooltip" title="Restore Order" data-bs-placement="left">
+                                                 <i class="bi bi-arrow-counterclockwise" fill="currentColor" />
+                                            </button>
+                                        </td>
+                                    </tr>
+                                }
+                            }).collect::<Html>()
+                        }
+                        </tbody>
+                        <tfoot>
+                            {header_footer}
+                        </tfoot>
+                    </table>
+                </div>
+            }
+        }
+    }
+}
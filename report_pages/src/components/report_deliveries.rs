@@ -91,7 +91,7 @@ pub(crate) fn report_deliveries_view() -> Html {
                                 };
                                 let neighborhood = v["customer"]["neighborhood"].as_str().unwrap();
                                 let dist_point = get_neighborhood(neighborhood)
-                                    .map_or("".to_string(), |v|v.distribution_point.clone());
+                                    .map_or("".to_string(), |v|v.get_distribution_point_name());
                                 let uid = v["ownerId"].as_str().unwrap();
                                 html!{
                                     <tr>
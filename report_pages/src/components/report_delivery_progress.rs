@@ -0,0 +1,187 @@
+use crate::components::report_loading_spinny::*;
+use data_model::*;
+use gloo::timers::callback::Interval;
+use std::collections::BTreeMap;
+use tracing::info;
+use web_sys::{Event, HtmlSelectElement, MouseEvent};
+use yew::prelude::*;
+
+// How often to refresh the progress while the view is open
+const REFRESH_INTERVAL_MS: u32 = 60_000;
+
+#[derive(Default, Clone, PartialEq, Debug)]
+struct DistPointProgress {
+    status_counts: BTreeMap<DeliveryStatus, usize>,
+    num_orders: usize,
+    num_bags: u64,
+    num_bags_delivered: u64,
+}
+
+/////////////////////////////////////////////////
+fn gen_progress_summary(orders: &[serde_json::Value]) -> BTreeMap<String, DistPointProgress> {
+    let mut summary: BTreeMap<String, DistPointProgress> = BTreeMap::new();
+    for v in orders {
+        let dist_point = v["customer"]["neighborhood"]
+            .as_str()
+            .and_then(get_neighborhood)
+            .map_or("".to_string(), |v| v.get_distribution_point_name());
+        let num_bags = get_purchase_to_map(v).get("bags").copied().unwrap_or(0);
+        let status = get_delivery_status_from_report_data(v);
+
+        let progress = summary.entry(dist_point).or_default();
+        *progress.status_counts.entry(status).or_default() += 1;
+        progress.num_orders += 1;
+        progress.num_bags += num_bags;
+        if DeliveryStatus::Delivered == status {
+            progress.num_bags_delivered += num_bags;
+        }
+    }
+    summary
+}
+
+/////////////////////////////////////////////////
+fn gen_progress_row(dist_point: &str, progress: &DistPointProgress) -> Html {
+    let percent_done = (progress.num_bags_delivered * 100)
+        .checked_div(progress.num_bags)
+        .unwrap_or(0);
+    html! {
+        <tr>
+            <td>{dist_point}</td>
+            <td>{progress.num_orders}</td>
+            {
+                DeliveryStatus::all().iter().map(|status| html! {
+                    <td>{progress.status_counts.get(status).copied().unwrap_or(0)}</td>
+                }).collect::<Html>()
+            }
+            <td>{format!("{} / {}", progress.num_bags_delivered, progress.num_bags)}</td>
+            <td style="min-width: 8em;">
+                <div class="progress" role="progressbar" aria-valuemin="0" aria-valuemax="100"
+                    aria-valuenow={percent_done.to_string()}>
+                    <div class="progress-bar bg-success" style={format!("width: {percent_done}%")}>
+                        {format!("{percent_done}%")}
+                    </div>
+                </div>
+            </td>
+        </tr>
+    }
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(DeliveryProgressReportView)]
+pub(crate) fn report_delivery_progress_view() -> Html {
+    let report_state = use_state(|| ReportViewState::IsLoading);
+    let delivery_id = use_state_eq(|| get_deliveries().keys().next().copied().unwrap_or(0));
+    let refresh_count = use_state_eq(|| 0_u32);
+    let timer_tick = use_state_eq(|| 0_u32);
+
+    {
+        let report_state = report_state.clone();
+        use_effect_with(
+            (*delivery_id, *refresh_count, *timer_tick),
+            move |(delivery_id, _, _)| {
+                let delivery_id = *delivery_id;
+                wasm_bindgen_futures::spawn_local(async move {
+                    info!("Downloading Delivery Progress for: {delivery_id}");
+                    match get_delivery_progress_report_data(delivery_id).await {
+                        Ok(resp) => report_state.set(ReportViewState::ReportHtmlGenerated(resp)),
+                        Err(err) => {
+                            gloo::dialogs::alert(&format!(
+                                "Failed to get delivery progress: {err:#?}"
+                            ));
+                            report_state.set(ReportViewState::ReportHtmlGenerated(Vec::new()));
+                        }
+                    }
+                });
+                || {}
+            },
+        );
+    }
+
+    {
+        let timer_tick = timer_tick.clone();
+        use_effect_with((), move |_| {
+            // The handle captured here never sees new values so keep the count locally
+            let mut num_ticks = 0;
+            let interval = Interval::new(REFRESH_INTERVAL_MS, move || {
+                num_ticks += 1;
+                timer_tick.set(num_ticks);
+            });
+            move || drop(interval)
+        });
+    }
+
+    let on_delivery_change = {
+        let delivery_id = delivery_id.clone();
+        let report_state = report_state.clone();
+        Callback::from(move |evt: Event| {
+            let value = evt.target_unchecked_into::<HtmlSelectElement>().value();
+            report_state.set(ReportViewState::IsLoading);
+            delivery_id.set(value.parse::<u32>().unwrap_or(0));
+        })
+    };
+
+    let on_refresh = {
+        let refresh_count = refresh_count.clone();
+        Callback::from(move |_evt: MouseEvent| {
+            refresh_count.set(*refresh_count + 1);
+        })
+    };
+
+    html! {
+        <div>
+            <div class="row g-2 mt-1">
+                <div class="form-floating col-md-4">
+                    <select class="form-select" id="formProgressDelivery" onchange={on_delivery_change}>
+                    {
+                        get_deliveries().iter().map(|(id, v)| html! {
+                            <option value={id.to_string()} selected={*id == *delivery_id}>
+                                {v.get_delivery_date_str()}
+                            </option>
+                        }).collect::<Html>()
+                    }
+                    </select>
+                    <label for="formProgressDelivery">{"Delivery Date"}</label>
+                </div>
+                <div class="col-md-2 d-flex align-items-center">
+                    <button type="button" class="btn btn-outline-primary" onclick={on_refresh}>
+                        <i class="bi bi-arrow-clockwise me-1" fill="currentColor" />
+                        {"Refresh"}
+                    </button>
+                </div>
+            </div>
+            {
+                match &*report_state {
+                    ReportViewState::IsLoading => html! { <ReportLoadingSpinny/> },
+                    ReportViewState::ReportHtmlGenerated(orders) => {
+                        let summary = gen_progress_summary(orders);
+                        html! {
+                            <table class="table table-sm table-striped mt-2">
+                                <thead>
+                                    <tr>
+                                        <th>{"Distribution Point"}</th>
+                                        <th>{"Orders"}</th>
+                                        {
+                                            DeliveryStatus::all().iter().map(|v| html! {
+                                                <th>{v.to_string()}</th>
+                                            }).collect::<Html>()
+                                        }
+                                        <th>{"Bags Delivered"}</th>
+                                        <th>{"Progress"}</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                {
+                                    summary.iter().map(|(dist_point, progress)| {
+                                        gen_progress_row(dist_point, progress)
+                                    }).collect::<Html>()
+                                }
+                                </tbody>
+                            </table>
+                        }
+                    }
+                }
+            }
+        </div>
+    }
+}
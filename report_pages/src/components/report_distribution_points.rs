@@ -75,8 +75,19 @@ pub(crate) fn report_distribution_points_view() -> Html {
                                         <td>{delivery_date}</td>
                                         <td>{dist_pt_map.get("TotalBagSummary").unwrap_or(&0).to_string()}</td>
                                         {
-                                            dist_points.iter().map(|v| html!{
-                                                <td>{dist_pt_map.get(v).unwrap_or(&0).to_string()}</td>
+                                            dist_points.iter().map(|v| {
+                                                let num_bags = dist_pt_map.get(v).copied().unwrap_or(0);
+                                                let capacity = get_distribution_point_by_name(v)
+                                                    .and_then(|v| v.capacity);
+                                                match capacity {
+                                                    Some(capacity) if num_bags > capacity as u64 => html!{
+                                                        <td class="text-danger fw-bold"
+                                                            title={format!("Over the capacity of {capacity} bags")}>
+                                                            {num_bags.to_string()}
+                                                        </td>
+                                                    },
+                                                    _ => html!{ <td>{num_bags.to_string()}</td> },
+                                                }
                                             }).collect::<Html>()
                                         }
                                     </tr>
@@ -0,0 +1,294 @@
+use crate::components::report_loading_spinny::*;
+use crate::components::report_route_planner::gen_unlocated_stops;
+use data_model::*;
+use std::collections::BTreeSet;
+use tracing::info;
+use web_sys::js_sys::encode_uri;
+use web_sys::{Event, HtmlElement, HtmlSelectElement, MouseEvent};
+use yew::prelude::*;
+
+#[derive(PartialEq, Clone, Debug)]
+enum DriverViewState {
+    Choosing,
+    IsLoading,
+    Loaded(DistPointRoutes),
+}
+
+/////////////////////////////////////////////////
+fn get_status_badge_class(status: &DeliveryStatus) -> &'static str {
+    match status {
+        DeliveryStatus::Scheduled => "badge bg-secondary",
+        DeliveryStatus::Loaded => "badge bg-info",
+        DeliveryStatus::OutForDelivery => "badge bg-primary",
+        DeliveryStatus::Delivered => "badge bg-success",
+        DeliveryStatus::Problem => "badge bg-danger",
+    }
+}
+
+/////////////////////////////////////////////////
+#[derive(Properties, PartialEq, Clone, Debug)]
+struct DriverStopCardProps {
+    stopnum: usize,
+    stop: RouteStop,
+    onstatuschange: Callback<(String, DeliveryStatusRecord)>,
+}
+
+#[component(DriverStopCard)]
+fn driver_stop_card(props: &DriverStopCardProps) -> Html {
+    let is_updating = use_state_eq(|| false);
+    let stop = &props.stop;
+    let cur_status = stop
+        .delivery_status
+        .as_ref()
+        .map_or(DeliveryStatus::Scheduled, |v| v.status);
+
+    let on_status_click = {
+        let is_updating = is_updating.clone();
+        let order_id = stop.order_id.clone();
+        let onstatuschange = props.onstatuschange.clone();
+        Callback::from(move |evt: MouseEvent| {
+            evt.prevent_default();
+            let Some(status) = evt
+                .current_target()
+                .and_then(|t| wasm_bindgen::JsCast::dyn_into::<HtmlElement>(t).ok())
+                .and_then(|t| t.dataset().get("status"))
+                .and_then(|v| v.parse::<DeliveryStatus>().ok())
+            else {
+                return;
+            };
+
+            let notes = if DeliveryStatus::Problem == status {
+                match gloo::dialogs::prompt("What was the problem?", None) {
+                    Some(notes) => Some(notes),
+                    None => return,
+                }
+            } else {
+                None
+            };
+
+            is_updating.set(true);
+            let is_updating = is_updating.clone();
+            let order_id = order_id.clone();
+            let onstatuschange = onstatuschange.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Setting delivery status for {order_id} to {status}");
+                match set_order_delivery_status(&order_id, cur_status, status, notes).await {
+                    Ok(record) => onstatuschange.emit((order_id, record)),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to update status: {err:#?}"));
+                    }
+                }
+                is_updating.set(false);
+            });
+        })
+    };
+
+    let google_map_url: String = encode_uri(&format!(
+        "https://www.google.com/maps/search/?api=1&query={}",
+        &stop.address
+    ))
+    .into();
+
+    html! {
+        <div class="card mb-2">
+            <div class="card-body p-2">
+                <div class="d-flex justify-content-between">
+                    <h6 class="mb-1">{format!("{}. {}", props.stopnum, &stop.name)}</h6>
+                    <span class={get_status_badge_class(&cur_status)}>{cur_status.to_string()}</span>
+                </div>
+                <div><a href={google_map_url} target="_blank">{stop.address.clone()}</a></div>
+                <div>
+                    {format!("{} bags", stop.num_bags)}
+                    {" | "}
+                    <a href={format!("tel:{}", &stop.phone)}>{stop.phone.clone()}</a>
+                </div>
+                if !stop.special_instructions.is_empty() {
+                    <div class="small text-muted">{stop.special_instructions.clone()}</div>
+                }
+                if let Some(record) = stop.delivery_status.as_ref() {
+                    <div class="small text-muted">
+                        {format!("{} by {}",
+                            record.get_update_time_str(),
+                            get_username_from_id(&record.updated_by).unwrap_or(record.updated_by.clone()))}
+                        if let Some(notes) = record.notes.as_ref() {
+                            {format!(": {notes}")}
+                        }
+                    </div>
+                }
+                <div class="d-flex gap-1 mt-2">
+                {
+                    [DeliveryStatus::Loaded, DeliveryStatus::OutForDelivery, DeliveryStatus::Delivered, DeliveryStatus::Problem]
+                        .iter()
+                        .map(|status| {
+                            let btn_class = if *status == cur_status {
+                                "btn btn-sm btn-primary flex-fill"
+                            } else {
+                                "btn btn-sm btn-outline-primary flex-fill"
+                            };
+                            html! {
+                                <button type="button" class={btn_class} data-status={status.to_string()}
+                                    disabled={*is_updating} onclick={on_status_click.clone()}>
+                                    {status.to_string()}
+                                </button>
+                            }
+                        })
+                        .collect::<Html>()
+                }
+                </div>
+            </div>
+        </div>
+    }
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(DriverReportView)]
+pub(crate) fn report_driver_view() -> Html {
+    let view_state = use_state_eq(|| DriverViewState::Choosing);
+    let selected_truck = use_state_eq(|| 1_usize);
+    let dist_points = get_neighborhoods()
+        .iter()
+        .map(|v| v.get_distribution_point_name())
+        .collect::<BTreeSet<String>>();
+
+    let on_load_routes = {
+        let view_state = view_state.clone();
+        let selected_truck = selected_truck.clone();
+        Callback::from(move |_evt: MouseEvent| {
+            let document = gloo::utils::document();
+            let delivery_id = get_element::<HtmlSelectElement>("formDriverDelivery", &document)
+                .value()
+                .parse::<u32>()
+                .unwrap_or(0);
+            let dist_point =
+                get_element::<HtmlSelectElement>("formDriverDistPoint", &document).value();
+            // Same capacity the route planner used so trucks match the printed driver sheets
+            let truck_capacity = get_fr_config().truck_capacity;
+
+            view_state.set(DriverViewState::IsLoading);
+            selected_truck.set(1);
+            let view_state = view_state.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match get_route_stops_for_delivery(delivery_id).await {
+                    Ok(mut dist_point_stops) => {
+                        let stops = dist_point_stops.remove(&dist_point).unwrap_or_default();
+                        let depot = get_distribution_point_by_name(&dist_point)
+                            .and_then(|v| v.get_location());
+                        view_state.set(DriverViewState::Loaded(plan_delivery_routes(
+                            &dist_point,
+                            depot,
+                            stops,
+                            truck_capacity,
+                        )));
+                    }
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to load routes: {err:#?}"));
+                        view_state.set(DriverViewState::Choosing);
+                    }
+                }
+            });
+        })
+    };
+
+    let on_truck_change = {
+        let selected_truck = selected_truck.clone();
+        Callback::from(move |evt: Event| {
+            let value = evt.target_unchecked_into::<HtmlSelectElement>().value();
+            selected_truck.set(value.parse::<usize>().unwrap_or(1));
+        })
+    };
+
+    let on_status_change = {
+        let view_state = view_state.clone();
+        Callback::from(move |(order_id, record): (String, DeliveryStatusRecord)| {
+            if let DriverViewState::Loaded(plan) = &*view_state {
+                let mut plan = plan.clone();
+                plan.routes
+                    .iter_mut()
+                    .flat_map(|v| v.stops.iter_mut())
+                    .filter(|v| v.order_id == order_id)
+                    .for_each(|v| v.delivery_status = Some(record.clone()));
+                view_state.set(DriverViewState::Loaded(plan));
+            }
+        })
+    };
+
+    html! {
+        <div class="container-fluid">
+            <div class="row g-2 mt-1">
+                <div class="form-floating col-12 col-md-4">
+                    <select class="form-select" id="formDriverDelivery">
+                    {
+                        get_deliveries().iter().map(|(id, v)| html! {
+                            <option value={id.to_string()}>{v.get_delivery_date_str()}</option>
+                        }).collect::<Html>()
+                    }
+                    </select>
+                    <label for="formDriverDelivery">{"Delivery Date"}</label>
+                </div>
+                <div class="form-floating col-12 col-md-4">
+                    <select class="form-select" id="formDriverDistPoint">
+                    {
+                        dist_points.iter().map(|v| html! {
+                            <option value={v.clone()}>{v.clone()}</option>
+                        }).collect::<Html>()
+                    }
+                    </select>
+                    <label for="formDriverDistPoint">{"Distribution Point"}</label>
+                </div>
+                <div class="col-12 col-md-4 d-flex align-items-center">
+                    <button type="button" class="btn btn-primary w-100" onclick={on_load_routes}
+                        disabled={DriverViewState::IsLoading == *view_state}>
+                        {"Load Stops"}
+                    </button>
+                </div>
+            </div>
+            {
+                match &*view_state {
+                    DriverViewState::Choosing => html! {},
+                    DriverViewState::IsLoading => html! { <ReportLoadingSpinny/> },
+                    DriverViewState::Loaded(plan) if plan.routes.is_empty() && plan.unlocated.is_empty() => html! {
+                        <div class="mt-2">{"There are no stops for this distribution point"}</div>
+                    },
+                    DriverViewState::Loaded(plan) if plan.routes.is_empty() => {
+                        gen_unlocated_stops(&plan.dist_point, &plan.unlocated)
+                    },
+                    DriverViewState::Loaded(plan) => {
+                        let route = plan.routes.iter()
+                            .find(|v| v.truck_num == *selected_truck)
+                            .unwrap_or(&plan.routes[0]);
+                        let num_delivered = route.stops.iter()
+                            .filter(|v| v.delivery_status.as_ref().is_some_and(|v| DeliveryStatus::Delivered == v.status))
+                            .count();
+                        html! {
+                            <>
+                                <div class="form-floating mt-2 mb-2">
+                                    <select class="form-select" id="formDriverTruck" onchange={on_truck_change}>
+                                    {
+                                        plan.routes.iter().map(|v| html! {
+                                            <option value={v.truck_num.to_string()} selected={v.truck_num == route.truck_num}>
+                                                {format!("Truck {} ({} stops, {} bags)", v.truck_num, v.stops.len(), v.get_num_bags())}
+                                            </option>
+                                        }).collect::<Html>()
+                                    }
+                                    </select>
+                                    <label for="formDriverTruck">{"Truck"}</label>
+                                </div>
+                                <div class="mb-2">
+                                    {format!("{num_delivered} of {} stops delivered", route.stops.len())}
+                                </div>
+                                {
+                                    route.stops.iter().enumerate().map(|(idx, stop)| html! {
+                                        <DriverStopCard key={stop.order_id.clone()} stopnum={idx + 1}
+                                            stop={stop.clone()} onstatuschange={on_status_change.clone()}/>
+                                    }).collect::<Html>()
+                                }
+                                {gen_unlocated_stops(&plan.dist_point, &plan.unlocated)}
+                            </>
+                        }
+                    },
+                }
+            }
+        </div>
+    }
+}
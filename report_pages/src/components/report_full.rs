@@ -105,6 +105,7 @@ pub(crate) fn report_full_view(props: &FullReportViewProps) -> Html {
                     <th>{"Neighborhood"}</th>
                     <th>{"Delivery Date"}</th>
                     <th>{"Spreaders"}</th>
+                    <th>{"Spread Completed"}</th>
                     <th>{"Spreading"}</th>
                     <th>{"Bags"}</th>
                     <th>{"Special Instructions"}</th>
@@ -139,6 +140,11 @@ pub(crate) fn report_full_view(props: &FullReportViewProps) -> Html {
                                 let spreaders: String = serde_json::from_value::<Vec<String>>(v["spreaders"].clone())
                                     .unwrap_or_default()
                                     .join(",");
+                                let spreader_bags = spreader_bags_to_str(
+                                    &serde_json::from_value::<Vec<SpreaderBags>>(v["spreaderBags"].clone())
+                                        .unwrap_or_default());
+                                let completion = get_spreading_completion(v);
+                                let spread_completed = completion.as_ref().map_or("".to_string(), |v| v.to_report_str());
                                 let uid = v["ownerId"].as_str().unwrap();
                                 html!{
                                     <tr>
@@ -153,6 +159,7 @@ pub(crate) fn report_full_view(props: &FullReportViewProps) -> Html {
                                         <td>{v["customer"]["neighborhood"].as_str().unwrap()}</td>
                                         <td data-deliveryid={delivery_id}>{delivery_date}</td>
                                         <td>{spreaders.clone()}</td>
+                                        <td>{spread_completed}</td>
                                         <td>{&spreading.to_string()}</td>
                                         <td>{&bags}</td>
                                         <td>{v["specialInstructions"].as_str().unwrap_or("")}</td>
@@ -173,6 +180,9 @@ pub(crate) fn report_full_view(props: &FullReportViewProps) -> Html {
                                                 oneditorder={on_view_or_edit_order.clone()}
                                                 oneditspreading={on_edit_spreading.clone()}
                                                 spreaders={spreaders}
+                                                spreadingbags={spreading}
+                                                spreaderbags={spreader_bags}
+                                                spreadingcompletion={completion.map_or("".to_string(), |v| serde_json::to_string(&v).unwrap_or_default())}
                                             />
                                         </td>
                                     </tr>
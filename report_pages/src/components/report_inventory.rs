@@ -0,0 +1,206 @@
+use crate::components::report_loading_spinny::*;
+use data_model::*;
+use tracing::info;
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+#[derive(PartialEq, Clone, Debug)]
+enum InventoryViewState {
+    IsLoading,
+    Loaded(Vec<InventoryStatus>),
+}
+
+/////////////////////////////////////////////////
+fn get_product_label(product_id: &str) -> String {
+    get_products()
+        .get(product_id)
+        .map_or(product_id.to_string(), |v| v.label.clone())
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[derive(Properties, PartialEq, Clone, Debug)]
+struct InventoryRowProps {
+    status: InventoryStatus,
+    onreceivedchange: Callback<InventoryRecord>,
+}
+
+#[component(InventoryRow)]
+fn inventory_row(props: &InventoryRowProps) -> Html {
+    let is_saving = use_state_eq(|| false);
+    let status = &props.status;
+
+    let on_received_change = {
+        let is_saving = is_saving.clone();
+        let status = status.clone();
+        let onreceivedchange = props.onreceivedchange.clone();
+        Callback::from(move |evt: Event| {
+            let input = evt.target_unchecked_into::<HtmlInputElement>();
+            let (Some(dist_point_id), Ok(num_received)) =
+                (status.dist_point_id.clone(), input.value().parse::<u64>())
+            else {
+                gloo::dialogs::alert("Bags received must be a whole number");
+                return;
+            };
+
+            is_saving.set(true);
+            let is_saving = is_saving.clone();
+            let status = status.clone();
+            let onreceivedchange = onreceivedchange.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match set_inventory_received(
+                    status.delivery_id,
+                    &dist_point_id,
+                    &status.product_id,
+                    num_received,
+                )
+                .await
+                {
+                    Ok(record) => onreceivedchange.emit(record),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to save received stock: {err:#?}"));
+                    }
+                }
+                is_saving.set(false);
+            });
+        })
+    };
+
+    let row_class = if status.get_shortage() > 0 {
+        "table-danger"
+    } else {
+        ""
+    };
+    html! {
+        <tr class={row_class}>
+            <td>{status.dist_point.clone()}</td>
+            <td>{get_product_label(&status.product_id)}</td>
+            <td>{status.num_ordered.map_or("".to_string(), |v| v.to_string())}</td>
+            <td style="max-width: 8em;">
+                if status.dist_point_id.is_some() {
+                    <input class="form-control form-control-sm" type="number" min="0"
+                        value={status.num_received.map_or("".to_string(), |v| v.to_string())}
+                        disabled={*is_saving} onchange={on_received_change}/>
+                } else {
+                    <span class="text-muted" title="Neighborhoods for this point need to be assigned a configured distribution point">
+                        {"N/A"}
+                    </span>
+                }
+            </td>
+            <td>{status.num_allocated}</td>
+            <td>{status.get_remaining().map_or("".to_string(), |v| v.to_string())}</td>
+            <td>{status.get_leftover()}</td>
+            <td>{status.get_shortage()}</td>
+        </tr>
+    }
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(InventoryReportView)]
+pub(crate) fn report_inventory_view() -> Html {
+    let view_state = use_state_eq(|| InventoryViewState::IsLoading);
+    let delivery_id = use_state_eq(|| get_deliveries().keys().next().copied().unwrap_or(0));
+
+    {
+        let view_state = view_state.clone();
+        use_effect_with(*delivery_id, move |delivery_id| {
+            let delivery_id = *delivery_id;
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Downloading Inventory for: {delivery_id}");
+                match get_inventory_report_data(delivery_id).await {
+                    Ok(resp) => view_state.set(InventoryViewState::Loaded(resp)),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to get inventory: {err:#?}"));
+                        view_state.set(InventoryViewState::Loaded(Vec::new()));
+                    }
+                }
+            });
+            || {}
+        });
+    }
+
+    let on_delivery_change = {
+        let delivery_id = delivery_id.clone();
+        let view_state = view_state.clone();
+        Callback::from(move |evt: Event| {
+            let value = evt.target_unchecked_into::<HtmlSelectElement>().value();
+            view_state.set(InventoryViewState::IsLoading);
+            delivery_id.set(value.parse::<u32>().unwrap_or(0));
+        })
+    };
+
+    let on_received_change = {
+        let view_state = view_state.clone();
+        Callback::from(move |record: InventoryRecord| {
+            if let InventoryViewState::Loaded(statuses) = &*view_state {
+                let mut statuses = statuses.clone();
+                statuses
+                    .iter_mut()
+                    .filter(|v| {
+                        v.dist_point_id.as_ref() == Some(&record.dist_point_id)
+                            && v.product_id == record.product_id
+                    })
+                    .for_each(|v| v.num_received = record.num_received);
+                view_state.set(InventoryViewState::Loaded(statuses));
+            }
+        })
+    };
+
+    html! {
+        <div>
+            <div class="row g-2 mt-1">
+                <div class="form-floating col-md-4">
+                    <select class="form-select" id="formInventoryDelivery" onchange={on_delivery_change}>
+                    {
+                        get_deliveries().iter().map(|(id, v)| html! {
+                            <option value={id.to_string()} selected={*id == *delivery_id}>
+                                {v.get_delivery_date_str()}
+                            </option>
+                        }).collect::<Html>()
+                    }
+                    </select>
+                    <label for="formInventoryDelivery">{"Delivery Date"}</label>
+                </div>
+            </div>
+            {
+                match &*view_state {
+                    InventoryViewState::IsLoading => html! { <ReportLoadingSpinny/> },
+                    InventoryViewState::Loaded(statuses) => {
+                        let total_shortage: u64 = statuses.iter().map(|v| v.get_shortage()).sum();
+                        let total_leftover: u64 = statuses.iter().map(|v| v.get_leftover()).sum();
+                        html! {
+                            <>
+                                <div class="mt-2">
+                                    {format!("Shortage: {total_shortage} | Leftover: {total_leftover}")}
+                                </div>
+                                <table class="table table-sm table-striped mt-2">
+                                    <thead>
+                                        <tr>
+                                            <th>{"Distribution Point"}</th>
+                                            <th>{"Product"}</th>
+                                            <th>{"Ordered"}</th>
+                                            <th>{"Received"}</th>
+                                            <th>{"Allocated"}</th>
+                                            <th>{"Remaining"}</th>
+                                            <th>{"Leftover"}</th>
+                                            <th>{"Shortage"}</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                    {
+                                        statuses.iter().map(|v| html! {
+                                            <InventoryRow key={format!("{}-{}", &v.dist_point, &v.product_id)}
+                                                status={v.clone()} onreceivedchange={on_received_change.clone()}/>
+                                        }).collect::<Html>()
+                                    }
+                                    </tbody>
+                                </table>
+                            </>
+                        }
+                    }
+                }
+            }
+        </div>
+    }
+}
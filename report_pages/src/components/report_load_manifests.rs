@@ -0,0 +1,107 @@
+use data_model::*;
+use js::datatable::*;
+use tracing::info;
+use yew::prelude::*;
+
+/////////////////////////////////////////////////
+#[derive(Properties, PartialEq, Clone, Debug)]
+pub(crate) struct LoadManifestsTableProps {
+    pub(crate) plans: Vec<DistPointRoutes>,
+    pub(crate) truckcapacity: u64,
+}
+
+/////////////////////////////////////////////////
+fn gen_manifest_row(
+    truck: &str,
+    load_num: usize,
+    stop_num: Option<usize>,
+    stop: &RouteStop,
+    running_total: &str,
+) -> Html {
+    html! {
+        <tr>
+            <td>{truck}</td>
+            <td>{load_num}</td>
+            <td>{stop_num.map_or("".to_string(), |v| v.to_string())}</td>
+            <td>{stop.order_id.clone()}</td>
+            <td>{stop.name.clone()}</td>
+            <td>{stop.address.clone()}</td>
+            <td>{stop.num_bags}</td>
+            <td>{stop.num_spreading}</td>
+            <td>{stop.special_instructions.clone()}</td>
+            <td>{running_total}</td>
+        </tr>
+    }
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(LoadManifestsTable)]
+pub(crate) fn load_manifests_table(props: &LoadManifestsTableProps) -> Html {
+    let datatable: std::rc::Rc<std::cell::RefCell<Option<DataTable>>> = use_mut_ref(|| None);
+
+    use_effect_with((), move |_| {
+        info!("Setting Load Manifests DataTable");
+        *datatable.borrow_mut() = get_datatable(&serde_json::json!({
+            "reportType": "loadManifests",
+            "id": ".data-table-report table",
+        }));
+        || {}
+    });
+
+    let header_footer = html! {
+        <tr>
+            <th>{"Truck"}</th>
+            <th>{"Load #"}</th>
+            <th>{"Stop #"}</th>
+            <th>{"OrderId"}</th>
+            <th>{"Name"}</th>
+            <th>{"Address"}</th>
+            <th>{"Bags"}</th>
+            <th>{"Spreading"}</th>
+            <th>{"Notes"}</th>
+            <th>{"Running Total"}</th>
+        </tr>
+    };
+
+    html! {
+        <div class="data-table-report">
+            <table class="display responsive nowrap collapsed" role="grid" cellspacing="0" width="100%">
+                <thead>
+                    {header_footer.clone()}
+                </thead>
+                <tbody>
+                {
+                    props.plans.iter().map(|plan| {
+                        let routes_html = plan.routes.iter().map(|route| {
+                            let truck = format!("{} - Truck {}", &plan.dist_point, route.truck_num);
+                            let num_stops = route.stops.len();
+                            let mut running_total = 0;
+                            // The last stop gets loaded first so the first stop is at the tailgate
+                            route.stops.iter().rev().enumerate().map(|(idx, stop)| {
+                                running_total += stop.num_bags;
+                                gen_manifest_row(
+                                    &truck,
+                                    idx + 1,
+                                    Some(num_stops - idx),
+                                    stop,
+                                    &format!("{running_total} / {}", props.truckcapacity))
+                            }).collect::<Html>()
+                        }).collect::<Html>();
+
+                        let truck = format!("{} - Unassigned", &plan.dist_point);
+                        let unlocated_html = plan.unlocated.iter().enumerate().map(|(idx, stop)| {
+                            gen_manifest_row(&truck, idx + 1, None, stop, "")
+                        }).collect::<Html>();
+
+                        html! { <>{routes_html}{unlocated_html}</> }
+                    }).collect::<Html>()
+                }
+                </tbody>
+                <tfoot>
+                    {header_footer}
+                </tfoot>
+            </table>
+        </div>
+    }
+}
@@ -0,0 +1,306 @@
+use crate::components::report_load_manifests::*;
+use crate::components::report_loading_spinny::*;
+use data_model::*;
+use js::leaflet::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use tracing::info;
+use web_sys::MouseEvent;
+use web_sys::js_sys::encode_uri;
+use yew::prelude::*;
+
+const ROUTE_MAP_ID: &str = "routeMap";
+
+#[derive(PartialEq, Clone, Debug)]
+enum RoutePlannerState {
+    ChoosingDelivery,
+    IsLoading,
+    Planned(Vec<DistPointRoutes>),
+}
+
+/////////////////////////////////////////////////
+fn gen_route_map_params(plans: &[DistPointRoutes]) -> serde_json::Value {
+    let routes = plans
+        .iter()
+        .flat_map(|plan| {
+            let depot = plan.depot.unwrap_or(DEFAULT_MAP_CENTER);
+            plan.routes.iter().map(move |route| {
+                serde_json::json!({
+                    "label": format!("{} Truck {}", &plan.dist_point, route.truck_num),
+                    "depot": depot,
+                    "depotLabel": &plan.dist_point,
+                    "stops": route.stops.iter().map(|stop| serde_json::json!({
+                        "pt": stop.location.unwrap_or(depot),
+                        "label": format!("{} ({} bags)", &stop.name, stop.num_bags),
+                    })).collect::<Vec<_>>(),
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::json!({
+        "id": ROUTE_MAP_ID,
+        "routes": routes,
+        "centerPt": DEFAULT_MAP_CENTER,
+    })
+}
+
+/////////////////////////////////////////////////
+fn gen_driver_sheet(
+    dist_point: &str,
+    delivery_date: &str,
+    depot: &GeoPoint,
+    truck_capacity: u64,
+    route: &DeliveryRoute,
+) -> Html {
+    html! {
+        <div class="driver-sheet card mt-2">
+            <div class="card-body">
+                <h5 class="card-title">
+                    {format!("{} - Truck {}", dist_point, route.truck_num)}
+                    <small class="text-muted ms-2">
+                        {format!("{} | {} of {} bags | {:.1} km",
+                            delivery_date, route.get_num_bags(), truck_capacity, route.get_distance_km(depot))}
+                    </small>
+                </h5>
+                <table class="table table-sm table-striped">
+                    <thead>
+                        <tr>
+                            <th>{"#"}</th>
+                            <th>{"Name"}</th>
+                            <th>{"Address"}</th>
+                            <th>{"Map"}</th>
+                            <th>{"Bags"}</th>
+                            <th>{"Phone"}</th>
+                            <th>{"Notes"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                    {
+                        route.stops.iter().enumerate().map(|(idx, stop)| {
+                            let google_map_url: String = encode_uri(
+                                &format!("https://www.google.com/maps/search/?api=1&query={}", &stop.address)).into();
+                            html! {
+                                <tr>
+                                    <td>{idx + 1}</td>
+                                    <td>{stop.name.clone()}</td>
+                                    <td>{stop.address.clone()}</td>
+                                    <td><a href={google_map_url} target="_blank">{"map"}</a></td>
+                                    <td>{stop.num_bags}</td>
+                                    <td>{stop.phone.clone()}</td>
+                                    <td>{stop.special_instructions.clone()}</td>
+                                </tr>
+                            }
+                        }).collect::<Html>()
+                    }
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    }
+}
+
+/////////////////////////////////////////////////
+pub(crate) fn gen_unlocated_stops(dist_point: &str, stops: &[RouteStop]) -> Html {
+    if stops.is_empty() {
+        return html! {};
+    }
+    html! {
+        <div class="card mt-2 border-warning">
+            <div class="card-body">
+                <h5 class="card-title">
+                    {format!("{dist_point} - Unable to locate, assign manually")}
+                </h5>
+                <ul class="list-group">
+                {
+                    stops.iter().map(|stop| html! {
+                        <li class="list-group-item">
+                            {format!("{}: {} ({} bags)", &stop.name, &stop.address, stop.num_bags)}
+                        </li>
+                    }).collect::<Html>()
+                }
+                </ul>
+            </div>
+        </div>
+    }
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(RoutePlannerReportView)]
+pub(crate) fn report_route_planner_view() -> Html {
+    let planner_state = use_state_eq(|| RoutePlannerState::ChoosingDelivery);
+    let delivery_id = use_state_eq(|| get_deliveries().keys().next().copied().unwrap_or(0));
+    let truck_capacity = use_state_eq(|| get_fr_config().truck_capacity);
+    let is_showing_manifests = use_state_eq(|| false);
+    let route_map: Rc<RefCell<Option<Map>>> = use_mut_ref(|| None);
+
+    {
+        let route_map = route_map.clone();
+        use_effect_with((*planner_state).clone(), move |planner_state| {
+            if let Some(map) = route_map.borrow_mut().take() {
+                map.remove();
+            }
+            if let RoutePlannerState::Planned(plans) = planner_state {
+                *route_map.borrow_mut() = create_route_map(&gen_route_map_params(plans));
+            }
+            || {}
+        });
+    }
+
+    let on_delivery_change = {
+        let delivery_id = delivery_id.clone();
+        let planner_state = planner_state.clone();
+        Callback::from(move |evt: Event| {
+            let value = evt
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            delivery_id.set(value.parse::<u32>().unwrap_or(0));
+            planner_state.set(RoutePlannerState::ChoosingDelivery);
+        })
+    };
+
+    let on_capacity_change = {
+        let truck_capacity = truck_capacity.clone();
+        let planner_state = planner_state.clone();
+        Callback::from(move |evt: Event| {
+            let input = evt.target_unchecked_into::<web_sys::HtmlInputElement>();
+            let Some(new_capacity) = input.value().parse::<u64>().ok().filter(|v| *v > 0) else {
+                input.set_value(&truck_capacity.to_string());
+                return;
+            };
+            truck_capacity.set(new_capacity);
+            planner_state.set(RoutePlannerState::ChoosingDelivery);
+            // Saved so the driver view plans the same trucks as the printed sheets
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(err) = set_truck_capacity(new_capacity).await {
+                    gloo::dialogs::alert(&format!("Failed to save the truck capacity: {err:#?}"));
+                }
+            });
+        })
+    };
+
+    let on_plan_routes = {
+        let delivery_id = delivery_id.clone();
+        let truck_capacity = truck_capacity.clone();
+        let planner_state = planner_state.clone();
+        Callback::from(move |_evt: MouseEvent| {
+            let delivery_id = *delivery_id;
+            let truck_capacity = *truck_capacity;
+            let planner_state = planner_state.clone();
+            planner_state.set(RoutePlannerState::IsLoading);
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Planning routes for delivery: {delivery_id}");
+                match get_route_stops_for_delivery(delivery_id).await {
+                    Ok(dist_point_stops) => {
+                        let plans = dist_point_stops
+                            .into_iter()
+                            .map(|(dist_point, stops)| {
+                                let depot = get_distribution_point_by_name(&dist_point)
+                                    .and_then(|v| v.get_location());
+                                plan_delivery_routes(&dist_point, depot, stops, truck_capacity)
+                            })
+                            .collect::<Vec<_>>();
+                        planner_state.set(RoutePlannerState::Planned(plans));
+                    }
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to plan routes: {err:#?}"));
+                        planner_state.set(RoutePlannerState::ChoosingDelivery);
+                    }
+                }
+            });
+        })
+    };
+
+    let on_print = Callback::from(move |_evt: MouseEvent| {
+        let _ = gloo::utils::window().print();
+    });
+
+    let on_toggle_manifests = {
+        let is_showing_manifests = is_showing_manifests.clone();
+        Callback::from(move |_evt: MouseEvent| {
+            is_showing_manifests.set(!*is_showing_manifests);
+        })
+    };
+
+    let delivery_date = get_deliveries()
+        .get(&*delivery_id)
+        .map_or("".to_string(), |v| v.get_delivery_date_str());
+
+    html! {
+        <div>
+            <div class="row g-2 mt-1 d-print-none">
+                <div class="form-floating col-md-4">
+                    <select class="form-select" id="formRouteDelivery" onchange={on_delivery_change}>
+                    {
+                        get_deliveries().iter().map(|(id, v)| html! {
+                            <option value={id.to_string()} selected={*id == *delivery_id}>
+                                {v.get_delivery_date_str()}
+                            </option>
+                        }).collect::<Html>()
+                    }
+                    </select>
+                    <label for="formRouteDelivery">{"Delivery Date"}</label>
+                </div>
+                <div class="form-floating col-md-3">
+                    <input class="form-control" type="number" min="1" id="formTruckCapacity"
+                        value={truck_capacity.to_string()} onchange={on_capacity_change}/>
+                    <label for="formTruckCapacity">{"Bags Per Truck"}</label>
+                </div>
+                <div class="col-md-5 d-flex align-items-center">
+                    <button type="button" class="btn btn-primary me-2" onclick={on_plan_routes}
+                        disabled={RoutePlannerState::IsLoading == *planner_state}>
+                        {"Plan Routes"}
+                    </button>
+                    if let RoutePlannerState::Planned(_) = &*planner_state {
+                        <button type="button" class="btn btn-outline-secondary me-2" onclick={on_toggle_manifests}>
+                            if *is_showing_manifests {
+                                {"Show Driver Sheets"}
+                            } else {
+                                {"Show Load Manifests"}
+                            }
+                        </button>
+                        if !*is_showing_manifests {
+                            <button type="button" class="btn btn-outline-secondary" onclick={on_print}>
+                                <i class="bi bi-printer me-1" fill="currentColor" />
+                                {"Print Driver Sheets"}
+                            </button>
+                        }
+                    }
+                </div>
+            </div>
+            {
+                match &*planner_state {
+                    RoutePlannerState::ChoosingDelivery => html! {},
+                    RoutePlannerState::IsLoading => html! { <ReportLoadingSpinny/> },
+                    RoutePlannerState::Planned(plans) => html! {
+                        <>
+                            <div class="route-map-container mt-2 d-print-none">
+                                <div id={ROUTE_MAP_ID} />
+                            </div>
+                            if *is_showing_manifests {
+                                <LoadManifestsTable plans={plans.clone()} truckcapacity={*truck_capacity}/>
+                            } else {
+                                {
+                                plans.iter().map(|plan| {
+                                    let depot = plan.depot.unwrap_or(DEFAULT_MAP_CENTER);
+                                    html! {
+                                        <>
+                                            {
+                                                plan.routes.iter().map(|route| gen_driver_sheet(
+                                                    &plan.dist_point, &delivery_date, &depot, *truck_capacity, route)
+                                                ).collect::<Html>()
+                                            }
+                                            {gen_unlocated_stops(&plan.dist_point, &plan.unlocated)}
+                                        </>
+                                    }
+                                }).collect::<Html>()
+                                }
+                            }
+                        </>
+                    },
+                }
+            }
+        </div>
+    }
+}
@@ -1,14 +1,9 @@
 use crate::components::report_loading_spinny::*;
 use data_model::*;
 use js::leaflet::*;
-use serde::{Deserialize, Serialize};
 use tracing::info;
 use yew::prelude::*;
 
-#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
-pub struct Point(pub f64, pub f64);
-
-const SJV: Point = Point(30.5461096, -97.6723646);
 const SELL_MAP_ID: &str = "sellMap";
 
 /////////////////////////////////////////////////
@@ -37,7 +32,7 @@ pub(crate) fn report_sell_view() -> Html {
                         *sell_map.borrow_mut() = create_sell_map(&serde_json::json!({
                             "id": SELL_MAP_ID,
                             "geoJson": json_list[0],
-                            "centerPt": SJV,
+                            "centerPt": DEFAULT_MAP_CENTER,
                         }));
                     }
                 }
@@ -0,0 +1,260 @@
+use crate::components::report_loading_spinny::*;
+use data_model::*;
+use std::time::Duration;
+use tracing::info;
+use wasm_bindgen::JsCast;
+use web_sys::{Event, HtmlElement, HtmlSelectElement, MouseEvent};
+use yew::prelude::*;
+
+#[derive(PartialEq, Clone, Debug)]
+enum ServiceHoursViewState {
+    IsLoading,
+    Loaded(Vec<ScoutServiceHours>),
+}
+
+/////////////////////////////////////////////////
+fn duration_to_hours_str(dur: &Duration) -> String {
+    format!("{:.2}", (dur.as_secs() / 60) as f64 / 60.0)
+}
+
+/////////////////////////////////////////////////
+fn time_to_display(time_str: &str) -> String {
+    time_str.get(0..5).unwrap_or(time_str).to_string()
+}
+
+/////////////////////////////////////////////////
+// Each statement prints on its own page so it can be signed and handed to the scout
+fn gen_service_statement(scout: &ScoutServiceHours) -> Html {
+    let fr_config = get_fr_config();
+    html! {
+        <div class="card mt-2" style="break-after: page;">
+            <div class="card-body">
+                <h5 class="card-title">{format!("{} Service Hours Statement", &fr_config.description)}</h5>
+                <div>{format!("Scout: {}", &scout.name)}</div>
+                <div>{format!("Group: {}", &scout.group)}</div>
+                <table class="table table-sm mt-2">
+                    <thead>
+                        <tr>
+                            <th>{"Delivery Date"}</th>
+                            <th>{"Activity"}</th>
+                            <th>{"Time In"}</th>
+                            <th>{"Time Out"}</th>
+                            <th>{"Hours"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                    {
+                        scout.timecards.iter().map(|tc| html! {
+                            <tr>
+                                <td>{get_delivery_date(&tc.delivery_id)}</td>
+                                <td>{tc.activity.to_string()}</td>
+                                <td>{time_to_display(&tc.time_in)}</td>
+                                <td>{time_to_display(&tc.time_out)}</td>
+                                <td>{duration_to_hours_str(&tc.get_time_total())}</td>
+                            </tr>
+                        }).collect::<Html>()
+                    }
+                    </tbody>
+                    <tfoot>
+                    {
+                        scout.get_activity_totals().iter().map(|(activity, dur)| html! {
+                            <tr>
+                                <th colspan="4">{format!("{activity} Hours")}</th>
+                                <th>{duration_to_hours_str(dur)}</th>
+                            </tr>
+                        }).collect::<Html>()
+                    }
+                        <tr>
+                            <th colspan="4">{"Total Hours"}</th>
+                            <th>{duration_to_hours_str(&scout.get_time_total())}</th>
+                        </tr>
+                    </tfoot>
+                </table>
+                if !scout.spreading_jobs.is_empty() {
+                    <table class="table table-sm mt-2">
+                        <thead>
+                            <tr>
+                                <th>{"Delivery Date"}</th>
+                                <th>{"Completed"}</th>
+                                <th>{"Spreading Job"}</th>
+                                <th>{"Bags Spread"}</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                        {
+                            scout.spreading_jobs.iter().map(|job| html! {
+                                <tr>
+                                    <td>{job.delivery_id.map_or("".to_string(), |v| get_delivery_date(&v))}</td>
+                                    <td>{job.completed_date.clone()}</td>
+                                    <td>{job.order_id.clone()}</td>
+                                    <td>{job.num_bags.round_dp(2).to_string()}</td>
+                                </tr>
+                            }).collect::<Html>()
+                        }
+                        </tbody>
+                        <tfoot>
+                            <tr>
+                                <th colspan="3">{format!("{} Spreading Jobs", scout.spreading_jobs.len())}</th>
+                                <th>{scout.get_num_bags_spread().round_dp(2).to_string()}</th>
+                            </tr>
+                        </tfoot>
+                    </table>
+                }
+                <div class="row mt-5">
+                    <div class="col-6 border-top">{"Fundraiser Chair Signature"}</div>
+                    <div class="col-1"></div>
+                    <div class="col-3 border-top">{"Date"}</div>
+                </div>
+                <div class="row mt-5">
+                    <div class="col-6 border-top">{"Fundraiser Chair Printed Name"}</div>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(ServiceHoursReportView)]
+pub(crate) fn report_service_hours_view() -> Html {
+    let view_state = use_state_eq(|| ServiceHoursViewState::IsLoading);
+    // Empty shows the summary for everyone, "all" shows every statement
+    let selected_uid = use_state_eq(String::new);
+
+    {
+        let view_state = view_state.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Downloading Service Hours");
+                match get_service_hours().await {
+                    Ok(service_hours) => {
+                        view_state.set(ServiceHoursViewState::Loaded(service_hours))
+                    }
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to get service hours: {err:#?}"));
+                        view_state.set(ServiceHoursViewState::Loaded(Vec::new()));
+                    }
+                }
+            });
+            || {}
+        });
+    }
+
+    let on_scout_change = {
+        let selected_uid = selected_uid.clone();
+        Callback::from(move |evt: Event| {
+            selected_uid.set(evt.target_unchecked_into::<HtmlSelectElement>().value());
+        })
+    };
+
+    let on_view_statement = {
+        let selected_uid = selected_uid.clone();
+        Callback::from(move |evt: MouseEvent| {
+            if let Some(uid) = evt
+                .current_target()
+                .and_then(|t| t.dyn_into::<HtmlElement>().ok())
+                .and_then(|t| t.dataset().get("uid"))
+            {
+                selected_uid.set(uid);
+            }
+        })
+    };
+
+    let on_print = Callback::from(move |_evt: MouseEvent| {
+        let _ = gloo::utils::window().print();
+    });
+
+    let ServiceHoursViewState::Loaded(service_hours) = &*view_state else {
+        return html! { <ReportLoadingSpinny/> };
+    };
+
+    if service_hours.is_empty() {
+        return html! { <div class="mt-2">{"No service hours have been recorded"}</div> };
+    }
+
+    html! {
+        <div>
+            <div class="row g-2 mt-1 d-print-none">
+                <div class="form-floating col-md-4">
+                    <select class="form-select" id="formServiceHoursScout" onchange={on_scout_change}>
+                        <option value="" selected={selected_uid.is_empty()}>{"Summary"}</option>
+                        <option value="all" selected={"all" == selected_uid.as_str()}>{"All Statements"}</option>
+                        {
+                            service_hours.iter().map(|v| html! {
+                                <option value={v.uid.clone()} selected={v.uid == *selected_uid}>
+                                    {v.name.clone()}
+                                </option>
+                            }).collect::<Html>()
+                        }
+                    </select>
+                    <label for="formServiceHoursScout">{"Scout"}</label>
+                </div>
+                <div class="col-md-8 d-flex align-items-center">
+                    <button type="button" class="btn btn-outline-secondary" onclick={on_print}
+                        title="Print or save as a PDF">
+                        <i class="bi bi-printer me-1" fill="currentColor" />
+                        {"PDF"}
+                    </button>
+                </div>
+            </div>
+            {
+                match selected_uid.as_str() {
+                    "" => html! {
+                        <table class="table table-sm table-striped mt-2">
+                            <thead>
+                                <tr>
+                                    <th>{"Scout"}</th>
+                                    <th>{"Group"}</th>
+                                    {
+                                        TimecardActivity::all().iter().map(|v| html! {
+                                            <th>{format!("{v} Hours")}</th>
+                                        }).collect::<Html>()
+                                    }
+                                    <th>{"Total Hours"}</th>
+                                    <th>{"Spreading Jobs"}</th>
+                                    <th>{"Bags Spread"}</th>
+                                    <th class="d-print-none"></th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                            {
+                                service_hours.iter().map(|v| {
+                                    let activity_totals = v.get_activity_totals();
+                                    html! {
+                                        <tr>
+                                            <td>{v.name.clone()}</td>
+                                            <td>{v.group.clone()}</td>
+                                            {
+                                                TimecardActivity::all().iter().map(|activity| html! {
+                                                    <td>
+                                                        {duration_to_hours_str(&activity_totals.get(activity).copied().unwrap_or_default())}
+                                                    </td>
+                                                }).collect::<Html>()
+                                            }
+                                            <td>{duration_to_hours_str(&v.get_time_total())}</td>
+                                            <td>{v.spreading_jobs.len()}</td>
+                                            <td>{v.get_num_bags_spread().round_dp(2).to_string()}</td>
+                                            <td class="d-print-none">
+                                                <button class="btn btn-outline-info btn-sm" title="Statement"
+                                                    data-uid={v.uid.clone()} onclick={on_view_statement.clone()}>
+                                                    <i class="bi bi-file-earmark-text" fill="currentColor"></i>
+                                                </button>
+                                            </td>
+                                        </tr>
+                                    }
+                                }).collect::<Html>()
+                            }
+                            </tbody>
+                        </table>
+                    },
+                    "all" => service_hours.iter().map(gen_service_statement).collect::<Html>(),
+                    uid => service_hours
+                        .iter()
+                        .filter(|v| v.uid == uid)
+                        .map(gen_service_statement)
+                        .collect::<Html>(),
+                }
+            }
+        </div>
+    }
+}
@@ -4,7 +4,7 @@ use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::rc::Rc;
 use wasm_bindgen::{JsCast, JsValue};
-use web_sys::{Element, HtmlElement, HtmlInputElement, MouseEvent};
+use web_sys::{Element, HtmlElement, HtmlInputElement, HtmlTextAreaElement, MouseEvent};
 use yew::prelude::*;
 
 /////////////////////////////////////////////////
@@ -21,9 +21,60 @@ struct DlgMeta {
     tr_node: web_sys::Node,
     order_id: String,
     selected_users: BTreeMap<String, String>,
+    num_bags: u64,
+    // Bags entered for each spreader, an empty map means they were split evenly
+    spreader_bags: BTreeMap<String, u64>,
+    completion: SpreadingCompletion,
     dataset_elm: HtmlElement,
 }
 
+impl DlgMeta {
+    /// Gets the split to save for the selected spreaders or why it can't be saved
+    fn get_spreader_bags_to_save(&self) -> Result<Vec<SpreaderBags>, String> {
+        if self.selected_users.len() < 2 {
+            return Ok(Vec::new());
+        }
+        let spreader_bags = self
+            .selected_users
+            .keys()
+            .filter_map(|uid| {
+                self.spreader_bags.get(uid).map(|num_bags| SpreaderBags {
+                    uid: uid.clone(),
+                    num_bags: *num_bags,
+                })
+            })
+            .collect::<Vec<SpreaderBags>>();
+        if spreader_bags.is_empty() {
+            return Ok(spreader_bags);
+        }
+        if spreader_bags.len() != self.selected_users.len() {
+            return Err(
+                "Enter the bags spread for every spreader or leave them all blank to split evenly"
+                    .to_string(),
+            );
+        }
+        let total_bags: u64 = spreader_bags.iter().map(|v| v.num_bags).sum();
+        if total_bags != self.num_bags {
+            return Err(format!(
+                "The spreaders add up to {total_bags} bags but the order has {} bags to spread",
+                self.num_bags
+            ));
+        }
+        Ok(spreader_bags)
+    }
+
+    /// Spreading is only recorded as completed when there are spreaders
+    fn get_completion_to_save(&self) -> Result<Option<SpreadingCompletion>, String> {
+        if self.selected_users.is_empty() {
+            return Ok(None);
+        }
+        if self.completion.completed_date.is_empty() {
+            return Err("Enter the date the spreading was completed".to_string());
+        }
+        Ok(Some(self.completion.clone()))
+    }
+}
+
 /////////////////////////////////////////////////
 pub(crate) fn on_edit_spreading_from_rpt(
     evt: MouseEvent,
@@ -61,6 +112,23 @@ pub(crate) fn on_edit_spreading_from_rpt(
             )
         })
         .collect();
+    let num_bags = elm
+        .dataset()
+        .get("spreadingbags")
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or_default();
+    let spreader_bags: BTreeMap<String, u64> =
+        spreader_bags_from_str(&elm.dataset().get("spreaderbags").unwrap_or_default())
+            .into_iter()
+            .map(|v| (v.uid, v.num_bags))
+            .collect();
+    let mut completion = serde_json::from_str::<SpreadingCompletion>(
+        &elm.dataset().get("spreadingcompletion").unwrap_or_default(),
+    )
+    .unwrap_or_default();
+    if completion.completed_date.is_empty() {
+        completion.completed_date = SpreadingCompletion::completed_today().completed_date;
+    }
     info!("on_edit_spreading: {order_id_str}");
 
     let dlg = bootstrap::get_modal_by_id("spreadingDlg").unwrap();
@@ -75,6 +143,9 @@ pub(crate) fn on_edit_spreading_from_rpt(
                 .into_iter()
                 .filter(|(_id, name)| !name.is_empty())
                 .collect(),
+            num_bags,
+            spreader_bags,
+            completion,
             dataset_elm: elm,
         });
     });
@@ -133,6 +204,19 @@ pub(crate) fn choose_spreaders_dlg() -> Html {
             // evt.prevent_default();
             // evt.stop_propagation();
 
+            let validation: Result<(), String> = META.with(|metarc| {
+                metarc.borrow().as_ref().map_or(Ok(()), |meta| {
+                    meta.get_spreader_bags_to_save()?;
+                    meta.get_completion_to_save()?;
+                    Ok(())
+                })
+            });
+            if let Err(err) = validation {
+                gloo::dialogs::alert(&err);
+                dlg_state.set(SelectionState::Reviewing);
+                return;
+            }
+
             dlg_state.set(SelectionState::Submitting);
 
             META.with(|metarc| {
@@ -143,16 +227,63 @@ pub(crate) fn choose_spreaders_dlg() -> Html {
                     if let Some(meta) = maybe_meta {
                         let spreaders: Vec<String> =
                             meta.selected_users.keys().cloned().collect::<_>();
-                        match set_spreaders(&meta.order_id, &spreaders).await { Err(err) => {
+                        let prev_spreaders: Vec<String> = meta
+                            .dataset_elm
+                            .dataset()
+                            .get("spreaders")
+                            .unwrap_or_default()
+                            .split(",")
+                            .filter(|v| !v.is_empty())
+                            .map(|v| v.to_string())
+                            .collect();
+                        let prev_spreader_bags = spreader_bags_from_str(
+                            &meta.dataset_elm.dataset().get("spreaderbags").unwrap_or_default(),
+                        );
+                        let spreader_bags = meta.get_spreader_bags_to_save().unwrap_or_default();
+                        let prev_completion = serde_json::from_str::<SpreadingCompletion>(
+                            &meta
+                                .dataset_elm
+                                .dataset()
+                                .get("spreadingcompletion")
+                                .unwrap_or_default(),
+                        )
+                        .ok();
+                        let completion = meta.get_completion_to_save().unwrap_or_default();
+                        match set_spreaders(
+                            &meta.order_id,
+                            &prev_spreaders,
+                            &spreaders,
+                            &prev_spreader_bags,
+                            &spreader_bags,
+                            prev_completion.as_ref(),
+                            completion.as_ref(),
+                        )
+                        .await
+                        { Err(err) => {
                             gloo::dialogs::alert(&format!(
                                 "Failed to submit spreaders: {err:#?}"
                             ));
                         } _ => {
                             let spreaders = spreaders.join(",");
                             let _ = meta.dataset_elm.dataset().set("spreaders", &spreaders);
-                            if let Err(err) =
-                                set_spreaders_with_tr(&meta.datatable, &meta.tr_node, &spreaders)
-                            {
+                            let _ = meta
+                                .dataset_elm
+                                .dataset()
+                                .set("spreaderbags", &spreader_bags_to_str(&spreader_bags));
+                            let _ = meta.dataset_elm.dataset().set(
+                                "spreadingcompletion",
+                                &completion.as_ref().map_or("".to_string(), |v| {
+                                    serde_json::to_string(v).unwrap_or_default()
+                                }),
+                            );
+                            let spread_completed =
+                                completion.as_ref().map_or("".to_string(), |v| v.to_report_str());
+                            if let Err(err) = set_spreaders_with_tr(
+                                &meta.datatable,
+                                &meta.tr_node,
+                                &spreaders,
+                                &spread_completed,
+                            ) {
                                 gloo::dialogs::alert(&format!(
                                     "Order was set in the cloud db but not the local table: {err:#?}"
                                 ));
@@ -216,6 +347,58 @@ pub(crate) fn choose_spreaders_dlg() -> Html {
         })
     };
 
+    let on_spreader_bags_change = {
+        Callback::from(move |evt: InputEvent| {
+            let target_elm = evt.target_unchecked_into::<HtmlInputElement>();
+            let uid = target_elm.dataset().get("uid").unwrap();
+            META.with(|metarc| {
+                if let Some(meta) = metarc.borrow_mut().as_mut() {
+                    match target_elm.value().trim().parse::<u64>() {
+                        Ok(num_bags) => {
+                            let _ = meta.spreader_bags.insert(uid, num_bags);
+                        }
+                        Err(_) => {
+                            let _ = meta.spreader_bags.remove(&uid);
+                        }
+                    }
+                }
+            });
+        })
+    };
+
+    let on_completed_date_change = {
+        Callback::from(move |evt: Event| {
+            let value = evt.target_unchecked_into::<HtmlInputElement>().value();
+            META.with(|metarc| {
+                if let Some(meta) = metarc.borrow_mut().as_mut() {
+                    meta.completion.completed_date = value;
+                }
+            });
+        })
+    };
+
+    let on_has_issue_change = {
+        Callback::from(move |evt: Event| {
+            let is_checked = evt.target_unchecked_into::<HtmlInputElement>().checked();
+            META.with(|metarc| {
+                if let Some(meta) = metarc.borrow_mut().as_mut() {
+                    meta.completion.has_issue = is_checked;
+                }
+            });
+        })
+    };
+
+    let on_notes_change = {
+        Callback::from(move |evt: InputEvent| {
+            let value = evt.target_unchecked_into::<HtmlTextAreaElement>().value();
+            META.with(|metarc| {
+                if let Some(meta) = metarc.borrow_mut().as_mut() {
+                    meta.completion.notes = value;
+                }
+            });
+        })
+    };
+
     let (selecting_btn_classes, reviewing_btn_classes, save_btn_classes) = match *dlg_state {
         SelectionState::Choosing => ("btn-check active", "btn-check", "btn-check make-disabled"),
         SelectionState::Reviewing => ("btn-check", "btn-check active", "btn-check"),
@@ -227,11 +410,28 @@ pub(crate) fn choose_spreaders_dlg() -> Html {
     };
 
     let mut selected_users = BTreeMap::new();
+    let mut num_bags = 0;
+    let mut spreader_bags = BTreeMap::new();
+    let mut completion = SpreadingCompletion::default();
     META.with(|metarc| {
         if let Some(meta) = &*metarc.borrow() {
             selected_users = meta.selected_users.clone();
+            num_bags = meta.num_bags;
+            spreader_bags = meta.spreader_bags.clone();
+            completion = meta.completion.clone();
         }
     });
+    let is_split_editable = selected_users.len() > 1 && 0 != num_bags;
+    let even_split_str = if is_split_editable {
+        get_calculated_bags_spread_per_user(
+            &Vec::from_iter(selected_users.keys().cloned()),
+            num_bags as usize,
+        )
+        .round_dp(2)
+        .to_string()
+    } else {
+        String::new()
+    };
 
     html! {
         <div class="modal fade" id="spreadingDlg"
@@ -256,7 +456,10 @@ pub(crate) fn choose_spreaders_dlg() -> Html {
                                              {
                                                  get_users()
                                                      .iter()
-                                                     .filter(|(_,user_info)| "Bear"!=user_info.group && "Bogus"!=user_info.group)
+                                                     .filter(|(_,user_info)| {
+                                                         !is_timecard_excluded_group(&user_info.group)
+                                                             && !is_allocation_excluded_group(&user_info.group)
+                                                     })
                                                      .map(|(userid, user_info)|{
                                                          let name = &user_info.name;
                                                          let (li_classes, is_checked, lbl_classes) = if selected_users.contains_key(userid) {
@@ -289,17 +492,50 @@ pub(crate) fn choose_spreaders_dlg() -> Html {
                                             <label for={"spreadingDlgSpreaderSelectionReview"}>
                                                 {"Review Spreaders"}
                                             </label>
+                                            if is_split_editable {
+                                                <div class="form-text">
+                                                    {format!("{num_bags} bags to spread. Enter the bags each spreader did or leave them blank to split evenly.")}
+                                                </div>
+                                            }
                                             <ul class="list-group" id="spreadingDlgSpreaderSelectionReview">
                                             {
-                                                selected_users.values().into_iter().map(|name| {
+                                                selected_users.iter().map(|(uid, name)| {
                                                     html!{
-                                                        <li class="list-group-item">
+                                                        <li class="list-group-item d-flex justify-content-between align-items-center">
                                                             {name}
+                                                            if is_split_editable {
+                                                                <input type="number" min="0" class="form-control form-control-sm w-25"
+                                                                    aria-label="Bags Spread"
+                                                                    data-uid={uid.clone()}
+                                                                    placeholder={even_split_str.clone()}
+                                                                    value={spreader_bags.get(uid).map_or("".to_string(), |v| v.to_string())}
+                                                                    oninput={on_spreader_bags_change.clone()}/>
+                                                            }
                                                         </li>
                                                     }
                                                  }).collect::<Html>()
                                             }
                                             </ul>
+                                            <div class="mt-2">
+                                                <label for="spreadingDlgCompletedDate">{"Completed On"}</label>
+                                                <input type="date" class="form-control" id="spreadingDlgCompletedDate"
+                                                    value={completion.completed_date.clone()}
+                                                    onchange={on_completed_date_change}/>
+                                            </div>
+                                            <div class="mt-2">
+                                                <label for="spreadingDlgNotes">{"Customer Sign-off Note"}</label>
+                                                <textarea class="form-control" id="spreadingDlgNotes" rows="2"
+                                                    value={completion.notes.clone()}
+                                                    oninput={on_notes_change}/>
+                                            </div>
+                                            <div class="form-check mt-2">
+                                                <input class="form-check-input" type="checkbox" id="spreadingDlgHasIssue"
+                                                    checked={completion.has_issue}
+                                                    onchange={on_has_issue_change}/>
+                                                <label class="form-check-label" for="spreadingDlgHasIssue">
+                                                    {"Customer reported an issue"}
+                                                </label>
+                                            </div>
                                         }
                                     </div>
                                 }
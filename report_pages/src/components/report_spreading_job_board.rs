@@ -0,0 +1,272 @@
+use crate::components::report_loading_spinny::*;
+use data_model::*;
+use tracing::info;
+use wasm_bindgen::JsCast;
+use web_sys::{Event, HtmlElement, HtmlInputElement, HtmlSelectElement, MouseEvent};
+use yew::prelude::*;
+
+#[derive(PartialEq, Clone, Debug)]
+enum JobBoardViewState {
+    IsLoading,
+    Loaded(Vec<SpreadingJob>),
+}
+
+/////////////////////////////////////////////////
+fn get_dataset_value(evt: &MouseEvent, key: &str) -> Option<String> {
+    evt.current_target()
+        .and_then(|t| t.dyn_into::<HtmlElement>().ok())
+        .and_then(|t| t.dataset().get(key))
+}
+
+/////////////////////////////////////////////////
+// Every change comes back with the board as saved so other people's changes show up too
+fn update_job_board<F>(view_state: UseStateHandle<JobBoardViewState>, action: &'static str, fut: F)
+where
+    F: Future<Output = Result<Vec<SpreadingJob>, Box<dyn std::error::Error>>> + 'static,
+{
+    wasm_bindgen_futures::spawn_local(async move {
+        match fut.await {
+            Ok(jobs) => view_state.set(JobBoardViewState::Loaded(jobs)),
+            Err(err) => gloo::dialogs::alert(&format!("Failed to {action}: {err}")),
+        }
+    });
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(SpreadingJobBoardReportView)]
+pub(crate) fn report_spreading_job_board_view() -> Html {
+    let view_state = use_state_eq(|| JobBoardViewState::IsLoading);
+    let max_claims = use_state_eq(|| get_fr_config().max_spreading_job_claims);
+    let active_user = get_active_user();
+    let is_admin = active_user.is_admin();
+    let active_uid = active_user.get_id();
+
+    {
+        let view_state = view_state.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Downloading Spreading Job Board");
+                match get_spreading_job_board().await {
+                    Ok(jobs) => view_state.set(JobBoardViewState::Loaded(jobs)),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to get spreading jobs: {err:#?}"));
+                        view_state.set(JobBoardViewState::Loaded(Vec::new()));
+                    }
+                }
+            });
+            || {}
+        });
+    }
+
+    let on_max_claims_change = {
+        let max_claims = max_claims.clone();
+        Callback::from(move |evt: Event| {
+            let input = evt.target_unchecked_into::<HtmlInputElement>();
+            let Ok(new_max_claims) = input.value().parse::<u32>() else {
+                input.set_value(&max_claims.to_string());
+                return;
+            };
+            max_claims.set(new_max_claims);
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(err) = set_max_spreading_job_claims(new_max_claims).await {
+                    gloo::dialogs::alert(&format!("Failed to save the claim limit: {err:#?}"));
+                }
+            });
+        })
+    };
+
+    let on_claim = {
+        let view_state = view_state.clone();
+        Callback::from(move |evt: MouseEvent| {
+            let Some(order_id) = get_dataset_value(&evt, "orderid") else {
+                return;
+            };
+            update_job_board(view_state.clone(), "claim job", async move {
+                claim_spreading_job(&order_id).await
+            });
+        })
+    };
+
+    let on_release = {
+        let view_state = view_state.clone();
+        Callback::from(move |evt: MouseEvent| {
+            let Some(order_id) = get_dataset_value(&evt, "orderid") else {
+                return;
+            };
+            update_job_board(view_state.clone(), "release job", async move {
+                release_spreading_job(&order_id).await
+            });
+        })
+    };
+
+    let on_approve_claim = {
+        let view_state = view_state.clone();
+        Callback::from(move |evt: MouseEvent| {
+            let (Some(order_id), Some(uid)) = (
+                get_dataset_value(&evt, "orderid"),
+                get_dataset_value(&evt, "uid"),
+            ) else {
+                return;
+            };
+            update_job_board(view_state.clone(), "approve claim", async move {
+                approve_spreading_job_claim(&order_id, &uid).await
+            });
+        })
+    };
+
+    let on_remove_claim = {
+        let view_state = view_state.clone();
+        Callback::from(move |evt: MouseEvent| {
+            let (Some(order_id), Some(uid)) = (
+                get_dataset_value(&evt, "orderid"),
+                get_dataset_value(&evt, "uid"),
+            ) else {
+                return;
+            };
+            update_job_board(view_state.clone(), "remove claim", async move {
+                remove_spreading_job_claim(&order_id, &uid).await
+            });
+        })
+    };
+
+    let on_reassign = {
+        let view_state = view_state.clone();
+        Callback::from(move |evt: Event| {
+            let select = evt.target_unchecked_into::<HtmlSelectElement>();
+            let uid = select.value();
+            select.set_value("");
+            let Some(order_id) = select.dataset().get("orderid") else {
+                return;
+            };
+            if uid.is_empty()
+                || !gloo::dialogs::confirm(&format!(
+                    "Reassign this job to {}? Anyone else signed up will be removed.",
+                    get_username_from_id(&uid).unwrap_or(uid.clone())
+                ))
+            {
+                return;
+            }
+            update_job_board(view_state.clone(), "reassign job", async move {
+                reassign_spreading_job(&order_id, &uid).await
+            });
+        })
+    };
+
+    let JobBoardViewState::Loaded(jobs) = &*view_state else {
+        return html! { <ReportLoadingSpinny/> };
+    };
+
+    let num_claimed = jobs.iter().filter(|v| v.is_claimed_by(&active_uid)).count();
+    let is_at_claim_limit = num_claimed >= *max_claims as usize;
+    let users = get_users();
+    let mut spreaders = users
+        .iter()
+        .filter(|(_, v)| {
+            !is_timecard_excluded_group(&v.group) && !is_allocation_excluded_group(&v.group)
+        })
+        .map(|(uid, v)| (v.name.clone(), uid.clone()))
+        .collect::<Vec<(String, String)>>();
+    spreaders.sort();
+
+    html! {
+        <div>
+            <div class="row g-2 mt-1">
+                <div class="col-md-8 d-flex align-items-center">
+                    {format!("You have claimed {num_claimed} of {} spreading jobs. Jobs leave the board once their spreaders are recorded.", *max_claims)}
+                </div>
+                if is_admin {
+                    <div class="form-floating col-md-2">
+                        <input class="form-control" type="number" min="0" id="formMaxSpreadingJobClaims"
+                            value={max_claims.to_string()} onchange={on_max_claims_change}/>
+                        <label for="formMaxSpreadingJobClaims">{"Claim Limit"}</label>
+                    </div>
+                }
+            </div>
+            if jobs.is_empty() {
+                <div class="mt-2">{"There are no spreading jobs waiting on spreaders"}</div>
+            } else {
+                <table class="table table-sm table-striped mt-2">
+                    <thead>
+                        <tr>
+                            <th>{"Delivery Date"}</th>
+                            <th>{"Address"}</th>
+                            <th>{"Neighborhood"}</th>
+                            <th>{"Bags"}</th>
+                            <th>{"Instructions"}</th>
+                            <th>{"Claimed By"}</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                    {
+                        jobs.iter().map(|job| {
+                            let is_mine = job.is_claimed_by(&active_uid);
+                            html! {
+                                <tr class={classes!(is_mine.then_some("table-info"))}>
+                                    <td>{job.delivery_id.map_or("".to_string(), |v| get_delivery_date(&v))}</td>
+                                    <td>{job.address.clone()}</td>
+                                    <td>{job.neighborhood.clone()}</td>
+                                    <td>{job.num_bags}</td>
+                                    <td>{job.special_instructions.clone()}</td>
+                                    <td>
+                                    {
+                                        job.claims.iter().map(|claim| html! {
+                                            <span class={classes!("badge", "me-1", if claim.is_approved { "text-bg-success" } else { "text-bg-warning" })}>
+                                                {get_username_from_id(&claim.uid).unwrap_or(claim.uid.clone())}
+                                                if !claim.is_approved {
+                                                    {" (pending)"}
+                                                }
+                                                if is_admin && !claim.is_approved {
+                                                    <button type="button" class="btn btn-link btn-sm p-0 ms-1 text-reset" title="Approve"
+                                                        data-orderid={job.order_id.clone()} data-uid={claim.uid.clone()}
+                                                        onclick={on_approve_claim.clone()}>
+                                                        <i class="bi bi-check-lg" fill="currentColor"></i>
+                                                    </button>
+                                                }
+                                                if is_admin {
+                                                    <button type="button" class="btn btn-link btn-sm p-0 ms-1 text-reset" title="Remove"
+                                                        data-orderid={job.order_id.clone()} data-uid={claim.uid.clone()}
+                                                        onclick={on_remove_claim.clone()}>
+                                                        <i class="bi bi-x-lg" fill="currentColor"></i>
+                                                    </button>
+                                                }
+                                            </span>
+                                        }).collect::<Html>()
+                                    }
+                                    </td>
+                                    <td class="d-flex">
+                                        if is_mine {
+                                            <button type="button" class="btn btn-outline-secondary btn-sm me-1"
+                                                data-orderid={job.order_id.clone()} onclick={on_release.clone()}>
+                                                {"Release"}
+                                            </button>
+                                        } else {
+                                            <button type="button" class="btn btn-outline-primary btn-sm me-1"
+                                                data-orderid={job.order_id.clone()} onclick={on_claim.clone()}
+                                                disabled={is_at_claim_limit}>
+                                                {"Claim"}
+                                            </button>
+                                        }
+                                        if is_admin {
+                                            <select class="form-select form-select-sm" aria-label="Reassign"
+                                                data-orderid={job.order_id.clone()} onchange={on_reassign.clone()}>
+                                                <option value="" selected=true>{"Reassign..."}</option>
+                                                {
+                                                    spreaders.iter().map(|(name, uid)| html! {
+                                                        <option value={uid.clone()}>{name.clone()}</option>
+                                                    }).collect::<Html>()
+                                                }
+                                            </select>
+                                        }
+                                    </td>
+                                </tr>
+                            }
+                        }).collect::<Html>()
+                    }
+                    </tbody>
+                </table>
+            }
+        </div>
+    }
+}
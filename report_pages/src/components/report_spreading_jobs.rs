@@ -107,6 +107,7 @@ pub(crate) fn report_quick_view(props: &SpreadingJobsReportViewProps) -> Html {
                     <th>{"Address"}</th>
                     <th>{"Neighborhood"}</th>
                     <th>{"Spreaders"}</th>
+                    <th>{"Spread Completed"}</th>
                     <th>{"Spreading"}</th>
                     <th>{"Order Owner"}</th>
                     <th>{"Actions"}</th>
@@ -139,6 +140,11 @@ pub(crate) fn report_quick_view(props: &SpreadingJobsReportViewProps) -> Html {
                                 let spreaders: String = serde_json::from_value::<Vec<String>>(v["spreaders"].clone())
                                     .unwrap_or_default()
                                     .join(",");
+                                let spreader_bags = spreader_bags_to_str(
+                                    &serde_json::from_value::<Vec<SpreaderBags>>(v["spreaderBags"].clone())
+                                        .unwrap_or_default());
+                                let completion = get_spreading_completion(v);
+                                let spread_completed = completion.as_ref().map_or("".to_string(), |v| v.to_report_str());
                                 let uid = v["ownerId"].as_str().unwrap();
                                 html!{
                                     <tr>
@@ -150,6 +156,7 @@ pub(crate) fn report_quick_view(props: &SpreadingJobsReportViewProps) -> Html {
                                         <td>{&address}</td>
                                         <td>{v["customer"]["neighborhood"].as_str().unwrap_or("")}</td>
                                         <td>{spreaders.clone()}</td>
+                                        <td>{spread_completed}</td>
                                         <td>{spreading.to_string()}</td>
                                         <td>{get_username_from_id(uid).map_or(uid.to_string(), |v|format!("{v}[{uid}]"))}</td>
                                         <td>
@@ -162,6 +169,9 @@ pub(crate) fn report_quick_view(props: &SpreadingJobsReportViewProps) -> Html {
                                                 oneditorder={on_view_or_edit_order.clone()}
                                                 oneditspreading={on_edit_spreading.clone()}
                                                 spreaders={spreaders}
+                                                spreadingbags={spreading}
+                                                spreaderbags={spreader_bags}
+                                                spreadingcompletion={completion.map_or("".to_string(), |v| serde_json::to_string(&v).unwrap_or_default())}
                                             />
                                         </td>
                                     </tr>
@@ -46,6 +46,8 @@ pub(crate) fn report_quick_view() -> Html {
                     <th>{"Name"}</th>
                     <th>{"Delivery Date"}</th>
                     <th>{"Bags Left To Spread"}</th>
+                    <th>{"Bags Committed"}</th>
+                    <th>{"Committed Spreaders"}</th>
                 </tr>
             };
             html! {
@@ -62,12 +64,28 @@ pub(crate) fn report_quick_view() -> Html {
                                     Some(delivery_id) => (get_delivery_date(&(delivery_id as u32)), delivery_id.to_string()),
                                     None => ("N/A".to_string(), "N/A".to_string()),
                                 };
+                                let committed_to = v["committedTo"].as_array().unwrap_or(&Vec::new())
+                                    .iter()
+                                    .map(|claim| {
+                                        let uid = claim["uid"].as_str().unwrap_or_default();
+                                        let name = get_username_from_id(uid).unwrap_or(uid.to_string());
+                                        let num_bags = claim["numBags"].as_u64().unwrap_or(0);
+                                        if claim["isApproved"].as_bool().unwrap_or(false) {
+                                            format!("{name}: {num_bags}")
+                                        } else {
+                                            format!("{name}: {num_bags} (pending)")
+                                        }
+                                    })
+                                    .collect::<Vec<String>>()
+                                    .join(", ");
                                 html!{
                                     <tr>
                                         <td>{owner_id.to_string()}</td>
                                         <td>{get_username_from_id(owner_id).unwrap_or("".to_string())}</td>
                                         <td data-deliveryid={delivery_id}>{delivery_date}</td>
                                         <td>{v["bagsLeft"].as_u64().unwrap_or(0).to_string()}</td>
+                                        <td>{v["bagsCommitted"].as_u64().unwrap_or(0).to_string()}</td>
+                                        <td>{committed_to}</td>
                                     </tr>
                                 }
                             }).collect::<Html>()
@@ -0,0 +1,305 @@
+use crate::components::report_loading_spinny::*;
+use chrono::prelude::*;
+use data_model::*;
+use gloo::file::File;
+use tracing::info;
+use wasm_bindgen::JsCast;
+use web_sys::{Event, HtmlAnchorElement, HtmlInputElement, HtmlSelectElement, MouseEvent, Url};
+use yew::prelude::*;
+
+#[derive(PartialEq, Clone, Debug)]
+enum SupplierOrderState {
+    Choosing,
+    IsLoading,
+    Generated(Vec<SupplierOrder>),
+}
+
+/////////////////////////////////////////////////
+fn get_product_label(product_id: &str) -> String {
+    get_products()
+        .get(product_id)
+        .map_or(product_id.to_string(), |v| v.label.clone())
+}
+
+/////////////////////////////////////////////////
+fn download_supplier_orders_csv(orders: &[SupplierOrder]) {
+    use csv::Writer;
+
+    let delivery_date = orders
+        .first()
+        .map_or("".to_string(), |v| get_delivery_date(&v.delivery_id));
+    let mut wtr = Writer::from_writer(vec![]);
+    wtr.write_record([
+        "Delivery Date",
+        "Distribution Point",
+        "Address",
+        "Contact",
+        "Contact Phone",
+        "Product",
+        "Sold",
+        "Overage %",
+        "To Order",
+    ])
+    .unwrap();
+    for order in orders {
+        let dist_point = order.get_dist_point_info().unwrap_or_default();
+        for line in &order.lines {
+            wtr.write_record([
+                delivery_date.as_str(),
+                order.dist_point.as_str(),
+                dist_point.address.as_str(),
+                dist_point.contact_name.as_deref().unwrap_or(""),
+                dist_point.contact_phone.as_deref().unwrap_or(""),
+                get_product_label(&line.product_id).as_str(),
+                line.num_allocated.to_string().as_str(),
+                order.overage_percent.to_string().as_str(),
+                line.num_to_order.to_string().as_str(),
+            ])
+            .unwrap();
+        }
+    }
+
+    let data = String::from_utf8(wtr.into_inner().unwrap()).unwrap();
+    let f = File::new_with_options(
+        &format!("SupplierOrder_{}.csv", delivery_date.replace('/', "-")),
+        data.as_str(),
+        Some("text/plain;charset=utf-8"),
+        Some(Utc::now().into()),
+    );
+    let link = gloo::utils::document()
+        .create_element("a")
+        .ok()
+        .and_then(|t| t.dyn_into::<HtmlAnchorElement>().ok())
+        .unwrap();
+    let url = Url::create_object_url_with_blob(f.as_ref()).unwrap();
+
+    link.set_target("_blank");
+    link.set_href(url.as_str());
+    link.set_download(f.name().as_str());
+    link.click();
+}
+
+/////////////////////////////////////////////////
+// Each order prints on its own page so it can be handed to the supplier per drop off
+fn gen_supplier_order_doc(order: &SupplierOrder) -> Html {
+    let dist_point = order.get_dist_point_info();
+    let total_to_order: u64 = order.lines.iter().map(|v| v.num_to_order).sum();
+    html! {
+        <div class="card mt-2" style="break-after: page;">
+            <div class="card-body">
+                <h5 class="card-title">
+                    {format!("Supplier Order: {} - {}", get_delivery_date(&order.delivery_id), &order.dist_point)}
+                </h5>
+                if let Some(dist_point) = dist_point.as_ref() {
+                    <div>{format!("Deliver To: {}", &dist_point.address)}</div>
+                    if dist_point.contact_name.is_some() || dist_point.contact_phone.is_some() {
+                        <div>
+                            {format!("Contact: {} {}",
+                                dist_point.contact_name.as_deref().unwrap_or(""),
+                                dist_point.contact_phone.as_deref().unwrap_or(""))}
+                        </div>
+                    }
+                }
+                <table class="table table-sm mt-2">
+                    <thead>
+                        <tr>
+                            <th>{"Product"}</th>
+                            <th>{"Sold"}</th>
+                            <th>{format!("Overage ({}%)", order.overage_percent)}</th>
+                            <th>{"To Order"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                    {
+                        order.lines.iter().map(|v| html! {
+                            <tr>
+                                <td>{get_product_label(&v.product_id)}</td>
+                                <td>{v.num_allocated}</td>
+                                <td>{v.num_to_order - v.num_allocated}</td>
+                                <td>{v.num_to_order}</td>
+                            </tr>
+                        }).collect::<Html>()
+                    }
+                    </tbody>
+                    <tfoot>
+                        <tr>
+                            <th colspan="3">{"Total"}</th>
+                            <th>{total_to_order}</th>
+                        </tr>
+                    </tfoot>
+                </table>
+            </div>
+        </div>
+    }
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(SupplierOrdersReportView)]
+pub(crate) fn report_supplier_orders_view() -> Html {
+    let order_state = use_state_eq(|| SupplierOrderState::Choosing);
+    let delivery_id = use_state_eq(|| get_deliveries().keys().next().copied().unwrap_or(0));
+    let overage_percent = use_state_eq(|| get_fr_config().supplier_overage_percent);
+    let is_recording = use_state_eq(|| false);
+
+    let on_delivery_change = {
+        let delivery_id = delivery_id.clone();
+        let order_state = order_state.clone();
+        Callback::from(move |evt: Event| {
+            let value = evt.target_unchecked_into::<HtmlSelectElement>().value();
+            delivery_id.set(value.parse::<u32>().unwrap_or(0));
+            order_state.set(SupplierOrderState::Choosing);
+        })
+    };
+
+    let on_overage_change = {
+        let overage_percent = overage_percent.clone();
+        let order_state = order_state.clone();
+        Callback::from(move |evt: Event| {
+            let input = evt.target_unchecked_into::<HtmlInputElement>();
+            let Ok(percent) = input.value().parse::<u32>() else {
+                input.set_value(&overage_percent.to_string());
+                return;
+            };
+            overage_percent.set(percent);
+            order_state.set(SupplierOrderState::Choosing);
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(err) = set_supplier_overage_percent(percent).await {
+                    gloo::dialogs::alert(&format!("Failed to save the overage: {err:#?}"));
+                }
+            });
+        })
+    };
+
+    let on_generate = {
+        let delivery_id = delivery_id.clone();
+        let overage_percent = overage_percent.clone();
+        let order_state = order_state.clone();
+        Callback::from(move |_evt: MouseEvent| {
+            let delivery_id = *delivery_id;
+            let overage_percent = *overage_percent;
+            let order_state = order_state.clone();
+            order_state.set(SupplierOrderState::IsLoading);
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Generating supplier orders for delivery: {delivery_id}");
+                match gen_supplier_orders(delivery_id, overage_percent).await {
+                    Ok(orders) => order_state.set(SupplierOrderState::Generated(orders)),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!(
+                            "Failed to generate supplier order: {err:#?}"
+                        ));
+                        order_state.set(SupplierOrderState::Choosing);
+                    }
+                }
+            });
+        })
+    };
+
+    let on_download_csv = {
+        let order_state = order_state.clone();
+        Callback::from(move |_evt: MouseEvent| {
+            if let SupplierOrderState::Generated(orders) = &*order_state {
+                download_supplier_orders_csv(orders);
+            }
+        })
+    };
+
+    let on_print = Callback::from(move |_evt: MouseEvent| {
+        let _ = gloo::utils::window().print();
+    });
+
+    let on_record = {
+        let order_state = order_state.clone();
+        let delivery_id = delivery_id.clone();
+        let is_recording = is_recording.clone();
+        Callback::from(move |_evt: MouseEvent| {
+            let SupplierOrderState::Generated(orders) = &*order_state else {
+                return;
+            };
+            if !gloo::dialogs::confirm(
+                "Record these quantities as ordered? This replaces any previously recorded order for this date.",
+            ) {
+                return;
+            }
+            let orders = orders.clone();
+            let delivery_id = *delivery_id;
+            let is_recording = is_recording.clone();
+            is_recording.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                match record_supplier_orders(delivery_id, &orders).await {
+                    Ok(skipped) if skipped.is_empty() => {
+                        gloo::dialogs::alert("The supplier order has been recorded")
+                    }
+                    Ok(skipped) => gloo::dialogs::alert(&format!(
+                        "The supplier order has been recorded except for these distribution points that need to be configured first:\n{}",
+                        skipped.join("\n")
+                    )),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to record supplier order: {err:#?}"));
+                    }
+                }
+                is_recording.set(false);
+            });
+        })
+    };
+
+    html! {
+        <div>
+            <div class="row g-2 mt-1 d-print-none">
+                <div class="form-floating col-md-4">
+                    <select class="form-select" id="formSupplierDelivery" onchange={on_delivery_change}>
+                    {
+                        get_deliveries().iter().map(|(id, v)| html! {
+                            <option value={id.to_string()} selected={*id == *delivery_id}>
+                                {v.get_delivery_date_str()}
+                            </option>
+                        }).collect::<Html>()
+                    }
+                    </select>
+                    <label for="formSupplierDelivery">{"Delivery Date"}</label>
+                </div>
+                <div class="form-floating col-md-2">
+                    <input class="form-control" type="number" min="0" id="formSupplierOverage"
+                        value={overage_percent.to_string()} onchange={on_overage_change}/>
+                    <label for="formSupplierOverage">{"Overage %"}</label>
+                </div>
+                <div class="col-md-6 d-flex align-items-center">
+                    <button type="button" class="btn btn-primary me-2" onclick={on_generate}
+                        disabled={SupplierOrderState::IsLoading == *order_state}>
+                        {"Generate Supplier Order"}
+                    </button>
+                    if let SupplierOrderState::Generated(orders) = &*order_state && !orders.is_empty() {
+                        <button type="button" class="btn btn-outline-secondary me-2" onclick={on_download_csv}>
+                            <i class="bi bi-cloud-download me-1" fill="currentColor" />
+                            {"CSV"}
+                        </button>
+                        <button type="button" class="btn btn-outline-secondary me-2" onclick={on_print}
+                            title="Print or save as a PDF">
+                            <i class="bi bi-printer me-1" fill="currentColor" />
+                            {"PDF"}
+                        </button>
+                        <button type="button" class="btn btn-outline-primary" onclick={on_record}
+                            disabled={*is_recording}>
+                            if *is_recording {
+                                <span class="spinner-border spinner-border-sm me-1" role="status" aria-hidden="true"/>
+                            }
+                            {"Record as Ordered"}
+                        </button>
+                    }
+                </div>
+            </div>
+            {
+                match &*order_state {
+                    SupplierOrderState::Choosing => html! {},
+                    SupplierOrderState::IsLoading => html! { <ReportLoadingSpinny/> },
+                    SupplierOrderState::Generated(orders) if orders.is_empty() => html! {
+                        <div class="mt-2">{"Nothing has been sold for this delivery date"}</div>
+                    },
+                    SupplierOrderState::Generated(orders) => {
+                        orders.iter().map(gen_supplier_order_doc).collect::<Html>()
+                    },
+                }
+            }
+        </div>
+    }
+}
@@ -0,0 +1,101 @@
+use crate::components::report_loading_spinny::*;
+use data_model::*;
+use tracing::info;
+use yew::prelude::*;
+
+#[derive(PartialEq, Clone, Debug)]
+enum AnomaliesViewState {
+    IsLoading,
+    Loaded(Vec<TimecardAnomaly>),
+}
+
+/////////////////////////////////////////////////
+// Server times are "00:00:00" but seconds aren't shown anywhere else
+fn time_to_display(time_str: &str) -> String {
+    time_str.get(0..5).unwrap_or(time_str).to_string()
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(TimecardAnomaliesReportView)]
+pub(crate) fn report_timecard_anomalies_view() -> Html {
+    let view_state = use_state_eq(|| AnomaliesViewState::IsLoading);
+
+    {
+        let view_state = view_state.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Downloading Timecard Anomalies");
+                match get_timecard_anomalies().await {
+                    Ok(mut anomalies) => {
+                        anomalies.sort_by_key(|v| {
+                            (
+                                v.timecard.delivery_id,
+                                get_username_from_id(&v.timecard.uid).unwrap_or_default(),
+                            )
+                        });
+                        view_state.set(AnomaliesViewState::Loaded(anomalies));
+                    }
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to get timecards: {err:#?}"));
+                        view_state.set(AnomaliesViewState::Loaded(Vec::new()));
+                    }
+                }
+            });
+            || {}
+        });
+    }
+
+    match &*view_state {
+        AnomaliesViewState::IsLoading => html! { <ReportLoadingSpinny/> },
+        AnomaliesViewState::Loaded(anomalies) if anomalies.is_empty() => html! {
+            <div class="mt-2">{"No timecard anomalies found"}</div>
+        },
+        AnomaliesViewState::Loaded(anomalies) => {
+            let users = get_users();
+            html! {
+                <div>
+                    <div class="mt-2">
+                        {format!("{} anomalies found. Corrections are made on the Timecards page.", anomalies.len())}
+                    </div>
+                    <table class="table table-sm table-striped mt-2">
+                        <thead>
+                            <tr>
+                                <th>{"Delivery Date"}</th>
+                                <th>{"Scout"}</th>
+                                <th>{"Group"}</th>
+                                <th>{"Time In"}</th>
+                                <th>{"Time Out"}</th>
+                                <th>{"Total"}</th>
+                                <th>{"Issue"}</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                        {
+                            anomalies.iter().map(|v| {
+                                let tc = &v.timecard;
+                                let user_info = users.get(&tc.uid);
+                                html! {
+                                    <tr>
+                                        <td>
+                                            {get_deliveries()
+                                                .get(&tc.delivery_id)
+                                                .map_or(tc.delivery_id.to_string(), |v| v.get_delivery_date_str())}
+                                        </td>
+                                        <td>{user_info.map_or(tc.uid.clone(), |v| v.name.clone())}</td>
+                                        <td>{user_info.map_or("".to_string(), |v| v.group.clone())}</td>
+                                        <td>{time_to_display(&tc.time_in)}</td>
+                                        <td>{time_to_display(&tc.time_out)}</td>
+                                        <td>{time_to_display(&tc.time_total)}</td>
+                                        <td class="text-danger">{v.kind.to_string()}</td>
+                                    </tr>
+                                }
+                            }).collect::<Html>()
+                        }
+                        </tbody>
+                    </table>
+                </div>
+            }
+        }
+    }
+}
@@ -8,15 +8,24 @@ use data_model::*;
 
 use crate::components::delete_report_order_dlg::DeleteOrderDlg;
 use crate::components::report_assisted_spreading_jobs::SpreadingAssistJobsReportView;
+use crate::components::report_cancelled_orders::CancelledOrdersReportView;
 use crate::components::report_deliveries::DeliveriesReportView;
+use crate::components::report_delivery_progress::DeliveryProgressReportView;
 use crate::components::report_distribution_points::DistributionPointsReportView;
+use crate::components::report_driver_view::DriverReportView;
 use crate::components::report_full::FullReportView;
+use crate::components::report_inventory::InventoryReportView;
 use crate::components::report_money_collection::MoneyCollectionReportView;
 use crate::components::report_quick::QuickReportView;
+use crate::components::report_route_planner::RoutePlannerReportView;
 use crate::components::report_sell_map::SellMapReportView;
+use crate::components::report_service_hours::ServiceHoursReportView;
 use crate::components::report_spreaders_dlg::ChooseSpreadersDlg;
+use crate::components::report_spreading_job_board::SpreadingJobBoardReportView;
 use crate::components::report_spreading_jobs::SpreadingJobsReportView;
 use crate::components::report_spreading_jobs_unfinished::SpreadingJobsUnfinishedReportView;
+use crate::components::report_supplier_orders::SupplierOrdersReportView;
+use crate::components::report_timecard_anomalies::TimecardAnomaliesReportView;
 use crate::components::report_verify::OrderVerificationView;
 
 /////////////////////////////////////////////////
@@ -161,11 +170,20 @@ pub fn reports_page() -> Html {
                     ReportViews::MoneyCollection=>html!{<MoneyCollectionReportView seller={current_settings.seller_id_filter.clone()}/>},
                     ReportViews::SpreadingJobs=>html!{<SpreadingJobsReportView seller={current_settings.seller_id_filter.clone()}/>},
                     ReportViews::SpreadingAssistJobs=>html!{<SpreadingAssistJobsReportView spreader={current_settings.seller_id_filter.clone()}/>},
+                    ReportViews::SpreadingJobBoard=>html!{<SpreadingJobBoardReportView />},
                     ReportViews::UnfinishedSpreadingJobs=>html!{<SpreadingJobsUnfinishedReportView />},
                     ReportViews::OrderVerification=>html!{<OrderVerificationView seller={current_settings.seller_id_filter.clone()}/>},
                     ReportViews::Deliveries=>html!{<DeliveriesReportView />},
+                    ReportViews::RoutePlanner=>html!{<RoutePlannerReportView />},
+                    ReportViews::DeliveryProgress=>html!{<DeliveryProgressReportView />},
+                    ReportViews::Inventory=>html!{<InventoryReportView />},
+                    ReportViews::SupplierOrders=>html!{<SupplierOrdersReportView />},
+                    ReportViews::TimecardAnomalies=>html!{<TimecardAnomaliesReportView />},
+                    ReportViews::ServiceHours=>html!{<ServiceHoursReportView />},
+                    ReportViews::DriverView=>html!{<DriverReportView />},
                     ReportViews::DistributionPoints=>html!{<DistributionPointsReportView />},
                     ReportViews::SellMap=>html!{<SellMapReportView />},
+                    ReportViews::CancelledOrders=>html!{<CancelledOrdersReportView />},
                     _=>html!{<h6>{"Not Yet Implemented"}</h6>},
                 }
             }
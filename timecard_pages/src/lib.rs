@@ -1,3 +1,4 @@
 mod pages;
 
 pub use pages::timecards::*;
+pub use pages::timeclock::*;
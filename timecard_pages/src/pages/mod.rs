@@ -1 +1,4 @@
 pub mod timecards;
+pub(crate) mod timecards_export;
+pub(crate) mod timecards_import;
+pub mod timeclock;
@@ -7,17 +7,24 @@ use web_sys::{
 };
 use yew::prelude::*;
 
+use super::timecards_export::*;
+use super::timecards_import::*;
 use data_model::*;
+use js::bootstrap;
 use std::time::Duration;
 use tracing::{error, info};
 
+// Saved total and activity keyed by uid and span id
+type CurrentTimecardsMap =
+    std::collections::HashMap<(String, String), (Duration, TimecardActivity)>;
+
 thread_local! {
-    static CURRENT_TIMECARDS: Rc<RefCell<std::collections::HashMap<String, Duration>>> =
+    static CURRENT_TIMECARDS: Rc<RefCell<CurrentTimecardsMap>> =
         Rc::new(RefCell::new(std::collections::HashMap::new()));
 }
 
 /////////////////////////////////////////////////
-fn get_selected_delivery_id() -> Option<u32> {
+pub(crate) fn get_selected_delivery_id() -> Option<u32> {
     let document = gloo::utils::document();
     let value = document
         .get_element_by_id("timeSheetSelectDeliveryDate")
@@ -33,7 +40,7 @@ fn get_selected_delivery_id() -> Option<u32> {
 }
 
 /////////////////////////////////////////////////
-fn server_time_to_display(server_time: &str) -> String {
+pub(crate) fn server_time_to_display(server_time: &str) -> String {
     if server_time.len() == 8 {
         server_time[0..5].to_string()
     } else {
@@ -46,12 +53,164 @@ fn get_uid_from_row(row_elm: &HtmlElement) -> String {
     row_elm.dataset().get("uid").unwrap()
 }
 
+/////////////////////////////////////////////////
+fn get_span_id_from_row(row_elm: &HtmlElement) -> String {
+    row_elm.dataset().get("spanid").unwrap_or_default()
+}
+
+/////////////////////////////////////////////////
+fn get_activity_from_row(row_elm: &HtmlElement) -> TimecardActivity {
+    row_elm
+        .query_selector(".activity")
+        .ok()
+        .flatten()
+        .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+        .and_then(|t| t.value().parse::<TimecardActivity>().ok())
+        .unwrap_or_default()
+}
+
+/////////////////////////////////////////////////
+// Inputs are wrapped in a col and form-floating div inside the row
+fn get_row_from_input(target: Option<web_sys::EventTarget>) -> Option<HtmlElement> {
+    target
+        .and_then(|t| t.dyn_into::<HtmlElement>().ok())
+        .and_then(|t| t.parent_element())
+        .and_then(|t| t.parent_element())
+        .and_then(|t| t.parent_element())
+        .and_then(|t| t.dyn_into::<HtmlElement>().ok())
+}
+
+/////////////////////////////////////////////////
+// Shows the total and the save button when the row no longer matches what was saved
+fn validate_timecard_row(row_elm: &HtmlElement) {
+    let key = (get_uid_from_row(row_elm), get_span_id_from_row(row_elm));
+    let activity = get_activity_from_row(row_elm);
+
+    fn read_time_val(elm: Result<Option<Element>, JsValue>) -> Option<Duration> {
+        elm.ok()
+            .flatten()
+            .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+            .and_then(|t| {
+                // info!("IEVal: {}", t.value());
+                time_val_str_to_duration(&t.value())
+            })
+    }
+
+    let time_in_val = read_time_val(row_elm.query_selector(".time-in"));
+    // info!("TI Val: {}", time_in_val.unwrap_or(Duration::from_secs(0)).as_secs());
+    let time_out_val = read_time_val(row_elm.query_selector(".time-out"));
+    // info!("TO Val: {}", time_out_val.unwrap_or(Duration::from_secs(0)).as_secs());
+
+    let time_calc_elm = row_elm
+        .query_selector(".time-calc")
+        .ok()
+        .flatten()
+        .and_then(|t| t.dyn_into::<HtmlElement>().ok())
+        .unwrap();
+
+    let btn_elm = row_elm
+        .query_selector(".save-btn")
+        .ok()
+        .flatten()
+        .and_then(|t| t.dyn_into::<HtmlElement>().ok())
+        .unwrap();
+
+    if time_in_val.is_none() || time_out_val.is_none() {
+        let _ = btn_elm.class_list().add_1("invisible");
+        time_calc_elm.set_inner_text("00:00");
+        CURRENT_TIMECARDS.with(|f| {
+            if time_in_val.is_none() && time_out_val.is_none() && f.borrow().contains_key(&key) {
+                let _ = btn_elm.class_list().remove_1("invisible");
+                let _ = time_calc_elm.class_list().remove_1("is-invalid");
+            }
+        });
+        return;
+    }
+
+    let time_in = time_in_val.unwrap();
+    let time_out = time_out_val.unwrap();
+
+    if let Some(err) = get_timecard_span_error(&time_in, &time_out) {
+        let _ = time_calc_elm.class_list().add_1("is-invalid");
+        let _ = time_calc_elm.set_attribute("title", &err.to_string());
+        let _ = btn_elm.class_list().add_1("invisible");
+        time_calc_elm.set_inner_text("00:00");
+        return;
+    }
+
+    let new_time_total = time_out - time_in;
+    CURRENT_TIMECARDS.with(|f| {
+        let new_time_total_secs = new_time_total.as_secs();
+        let _ = time_calc_elm.class_list().remove_1("is-invalid");
+        let _ = time_calc_elm.remove_attribute("title");
+
+        if let Some(saved) = f.borrow().get(&key)
+            && (new_time_total, activity) == *saved
+        {
+            let _ = btn_elm.class_list().add_1("invisible");
+            return;
+        }
+
+        let new_hours: u64 = (new_time_total_secs as f64 / (60.0 * 60.0)).floor() as u64;
+        let new_mins: u64 = ((new_time_total_secs as f64 % (60.0 * 60.0)) / 60.0).floor() as u64;
+        let new_time_total_str = format!("{new_hours:02}:{new_mins:02}");
+        if 0 == new_hours && 0 == new_mins {
+            let _ = btn_elm.class_list().add_1("invisible");
+            return;
+        }
+
+        let _ = btn_elm.class_list().remove_1("invisible");
+        time_calc_elm.set_inner_text(&new_time_total_str);
+    });
+}
+
+/////////////////////////////////////////////////
+// tuple of uid, username, Timecards for each span of time worked
+pub(crate) type TimecardsDataType = (String, String, Vec<TimeCard>);
+
+/////////////////////////////////////////////////
+fn load_timecards_data(
+    delivery_id: u32,
+    timecards_data_ready: UseStateHandle<Option<Vec<TimecardsDataType>>>,
+) {
+    info!("Downloading timecard data for: {delivery_id}");
+    wasm_bindgen_futures::spawn_local(async move {
+        match get_timecards_data(Some(delivery_id), None).await {
+            Ok(resp) => {
+                CURRENT_TIMECARDS.with(|f| {
+                    info!("Timecards data ready");
+                    *f.borrow_mut() = resp
+                        .iter()
+                        .flat_map(|v| v.2.iter())
+                        .filter_map(|tc| {
+                            // Rememeber time str from server is "00:00:00" and seconds are ignored.
+                            // Bad totals are skipped so they show up in the anomaly report instead
+                            let dur = time_val_str_to_duration(&tc.time_total)?;
+                            info!(
+                                "Loading Duration {}/{} : {}",
+                                &tc.uid,
+                                &tc.span_id,
+                                dur.as_secs()
+                            );
+                            Some(((tc.uid.clone(), tc.span_id.clone()), (dur, tc.activity)))
+                        })
+                        .collect::<CurrentTimecardsMap>();
+                    timecards_data_ready.set(Some(resp));
+                });
+            }
+            Err(err) => {
+                let err_str = format!("Failed to get retrieve timecard data: {err:#?}");
+                error!("{}", &err_str);
+                gloo::dialogs::alert(err_str.as_str());
+            }
+        };
+    });
+}
+
 /////////////////////////////////////////////////
 /////////////////////////////////////////////////
 #[component(Timecards)]
 pub fn timecards_page() -> Html {
-    // tuple of uid, username, Timecard
-    type TimecardsDataType = (String, String, Option<TimeCard>);
     let timecards_data_ready: UseStateHandle<Option<Vec<TimecardsDataType>>> =
         use_state_eq(|| None);
     let is_delivery_date_selected = use_state_eq(|| false);
@@ -61,6 +220,46 @@ pub fn timecards_page() -> Html {
             evt.prevent_default();
             evt.stop_propagation();
             info!("on_export_timecards");
+            let Some(elm) = evt
+                .current_target()
+                .and_then(|t| t.dyn_into::<HtmlElement>().ok())
+            else {
+                return;
+            };
+            let format = match elm.dataset().get("format").as_deref() {
+                Some("xlsx") => TimecardExportFormat::Xlsx,
+                _ => TimecardExportFormat::Csv,
+            };
+            let delivery_id = if Some("all") == elm.dataset().get("scope").as_deref() {
+                None
+            } else {
+                get_selected_delivery_id()
+            };
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(err) = export_timecards(delivery_id, format).await {
+                    let err_str = format!("Failed to export timecards: {err:#?}");
+                    error!("{}", &err_str);
+                    gloo::dialogs::alert(err_str.as_str());
+                }
+            });
+        })
+    };
+
+    let on_import_timecards = {
+        Callback::from(move |evt: MouseEvent| {
+            evt.prevent_default();
+            evt.stop_propagation();
+            bootstrap::modal_op("timecardImportDlg", "show");
+        })
+    };
+
+    let on_timecards_imported = {
+        let timecards_data_ready = timecards_data_ready.clone();
+        Callback::from(move |_| {
+            if let Some(delivery_id) = get_selected_delivery_id() {
+                timecards_data_ready.set(None);
+                load_timecards_data(delivery_id, timecards_data_ready.clone());
+            }
         })
     };
 
@@ -74,44 +273,7 @@ pub fn timecards_page() -> Html {
             if let Some(delivery_id) = get_selected_delivery_id() {
                 is_delivery_date_selected.set(true);
                 timecards_data_ready.set(None);
-                info!("Downloading timecard data for: {delivery_id}");
-                let timecards_data_ready = timecards_data_ready.clone();
-                wasm_bindgen_futures::spawn_local(async move {
-                    match get_timecards_data(Some(delivery_id), None).await {
-                        Ok(resp) => {
-                            CURRENT_TIMECARDS.with(|f| {
-                                info!("Timecards data ready");
-                                *f.borrow_mut() = resp
-                                    .iter()
-                                    .filter(|v| v.2.is_some())
-                                    .map(|v| {
-                                        // Rememeber time str from server is "00:00:00"
-                                        let time_comps =
-                                            v.2.as_ref()
-                                                .unwrap()
-                                                .time_total
-                                                .split(":")
-                                                .map(|v| v.parse::<u64>().unwrap())
-                                                .collect::<Vec<u64>>();
-                                        let mut dur = Duration::from_secs(time_comps[0] * 60 * 60); //Hours
-                                        dur = dur
-                                            .checked_add(Duration::from_secs(time_comps[1] * 60))
-                                            .unwrap(); //min
-                                        info!("Loading Duration {} : {}", &v.0, dur.as_secs());
-                                        // ignore seconds
-                                        (v.0.clone(), dur)
-                                    })
-                                    .collect::<std::collections::HashMap<String, Duration>>();
-                                timecards_data_ready.set(Some(resp));
-                            });
-                        }
-                        Err(err) => {
-                            let err_str = format!("Failed to get retrieve timecard data: {err:#?}");
-                            error!("{}", &err_str);
-                            gloo::dialogs::alert(err_str.as_str());
-                        }
-                    };
-                });
+                load_timecards_data(delivery_id, timecards_data_ready.clone());
             }
         })
     };
@@ -121,109 +283,23 @@ pub fn timecards_page() -> Html {
             evt.prevent_default();
             evt.stop_propagation();
             info!("on_time_change");
-
-            let row_elm = evt
-                .target()
-                .and_then(|t| t.dyn_into::<HtmlElement>().ok())
-                .and_then(|t| t.parent_element())
-                .and_then(|t| t.parent_element())
-                .and_then(|t| t.parent_element())
-                .and_then(|t| t.dyn_into::<HtmlElement>().ok())
-                .unwrap();
-
-            let uid = get_uid_from_row(&row_elm);
-
-            fn read_time_val(elm: Result<Option<Element>, JsValue>) -> Option<Duration> {
-                elm.ok()
-                    .flatten()
-                    .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
-                    .and_then(|t| {
-                        // info!("IEVal: {}", t.value());
-                        time_val_str_to_duration(&t.value())
-                    })
+            if let Some(row_elm) = get_row_from_input(evt.target()) {
+                validate_timecard_row(&row_elm);
             }
+        })
+    };
 
-            let time_in_val = read_time_val(row_elm.query_selector(".time-in"));
-            // info!("TI Val: {}", time_in_val.unwrap_or(Duration::from_secs(0)).as_secs());
-            let time_out_val = read_time_val(row_elm.query_selector(".time-out"));
-            // info!("TO Val: {}", time_out_val.unwrap_or(Duration::from_secs(0)).as_secs());
-
-            let time_calc_elm = row_elm
-                .query_selector(".time-calc")
-                .ok()
-                .flatten()
-                .and_then(|t| t.dyn_into::<HtmlElement>().ok())
-                .unwrap();
-
-            let btn_elm = row_elm
-                .query_selector(".btn")
-                .ok()
-                .flatten()
-                .and_then(|t| t.dyn_into::<HtmlElement>().ok())
-                .unwrap();
-
-            if time_in_val.is_none() || time_out_val.is_none() {
-                let _ = btn_elm.class_list().add_1("invisible");
-                time_calc_elm.set_inner_text("00:00");
-                CURRENT_TIMECARDS.with(|f| {
-                    if time_in_val.is_none()
-                        && time_out_val.is_none()
-                        && f.borrow().contains_key(&uid)
-                    {
-                        let _ = btn_elm.class_list().remove_1("invisible");
-                        let _ = time_calc_elm.class_list().remove_1("is-invalid");
-                    }
-                });
-                return;
+    let on_activity_change = {
+        Callback::from(move |evt: Event| {
+            info!("on_activity_change");
+            if let Some(row_elm) = get_row_from_input(evt.target()) {
+                validate_timecard_row(&row_elm);
             }
-
-            let time_in = time_in_val.unwrap();
-            let time_out = time_out_val.unwrap();
-
-            let mark_invalid = || {
-                let _ = time_calc_elm.class_list().add_1("is-invalid");
-                let _ = btn_elm.class_list().add_1("invisible");
-                time_calc_elm.set_inner_text("00:00");
-            };
-
-            match time_out.checked_sub(time_in) {
-                Some(new_time_total) => {
-                    CURRENT_TIMECARDS.with(|f| {
-                        let new_time_total_secs = new_time_total.as_secs();
-                        if 0 == new_time_total_secs {
-                            mark_invalid();
-                            return;
-                        }
-
-                        let _ = time_calc_elm.class_list().remove_1("is-invalid");
-
-                        if let Some(saved_time_total) = f.borrow().get(&uid)
-                            && &new_time_total == saved_time_total
-                        {
-                            let _ = btn_elm.class_list().add_1("invisible");
-                            return;
-                        }
-
-                        let new_hours: u64 =
-                            (new_time_total_secs as f64 / (60.0 * 60.0)).floor() as u64;
-                        let new_mins: u64 =
-                            ((new_time_total_secs as f64 % (60.0 * 60.0)) / 60.0).floor() as u64;
-                        let new_time_total_str = format!("{new_hours:02}:{new_mins:02}");
-                        if 0 == new_hours && 0 == new_mins {
-                            let _ = btn_elm.class_list().add_1("invisible");
-                            return;
-                        }
-
-                        let _ = btn_elm.class_list().remove_1("invisible");
-                        time_calc_elm.set_inner_text(&new_time_total_str);
-                    });
-                }
-                None => mark_invalid(),
-            };
         })
     };
 
     let on_save_entry = {
+        let timecards_data_ready = timecards_data_ready.clone();
         Callback::from(move |evt: MouseEvent| {
             evt.prevent_default();
             evt.stop_propagation();
@@ -293,30 +369,55 @@ pub fn timecards_page() -> Html {
             };
 
             let uid = get_uid_from_row(&row_elm);
+            let span_id = get_span_id_from_row(&row_elm);
+            let activity = get_activity_from_row(&row_elm);
+
+            // Keep anything the time clock recorded for this span
+            let saved_tc = timecards_data_ready
+                .as_ref()
+                .and_then(|v| v.iter().find(|v| v.0 == uid))
+                .and_then(|v| v.2.iter().find(|tc| tc.span_id == span_id))
+                .cloned()
+                .unwrap_or_default();
 
             wasm_bindgen_futures::spawn_local(async move {
                 info!(
-                    "Saving: uid:{} ti: {} to:{} tt:{}",
-                    &uid, &time_in_val, &time_out_val, &time_calc_val
+                    "Saving: uid:{} span:{} ti: {} to:{} tt:{}",
+                    &uid, &span_id, &time_in_val, &time_out_val, &time_calc_val
                 );
 
-                let selected_delivery_id = get_selected_delivery_id().unwrap();
+                let Some(selected_delivery_id) = get_selected_delivery_id() else {
+                    gloo::dialogs::alert("Select a delivery date before saving");
+                    let _ = spinny_elm.class_list().add_1("d-none");
+                    btn_elm.set_disabled(false);
+                    return;
+                };
                 //Save to cloud
                 let tc = TimeCard {
                     uid: uid.clone(),
                     delivery_id: selected_delivery_id,
+                    span_id: span_id.clone(),
+                    activity,
                     time_in: time_in_val,
                     time_out: time_out_val,
                     time_total: time_calc_val.clone(),
+                    ..saved_tc
                 };
                 if let Err(err) = save_timecards_data(vec![tc]).await {
                     gloo::dialogs::alert(&format!("Failed to set timecard data: {}", &err));
+                    let _ = spinny_elm.class_list().add_1("d-none");
+                    btn_elm.set_disabled(false);
                     return;
                 }
 
-                CURRENT_TIMECARDS.with(|f| {
-                    let time_calc_val = time_val_str_to_duration(&time_calc_val).unwrap();
-                    let _ = f.borrow_mut().insert(uid.clone(), time_calc_val);
+                let key = (uid, span_id);
+                CURRENT_TIMECARDS.with(|f| match time_val_str_to_duration(&time_calc_val) {
+                    Some(time_calc_val) => {
+                        let _ = f.borrow_mut().insert(key, (time_calc_val, activity));
+                    }
+                    None => {
+                        let _ = f.borrow_mut().remove(&key);
+                    }
                 });
 
                 let _ = spinny_elm.class_list().add_1("d-none");
@@ -326,6 +427,44 @@ pub fn timecards_page() -> Html {
         })
     };
 
+    let on_add_time = {
+        let timecards_data_ready = timecards_data_ready.clone();
+        Callback::from(move |evt: MouseEvent| {
+            evt.prevent_default();
+            evt.stop_propagation();
+            let Some(uid) = evt
+                .current_target()
+                .and_then(|t| t.dyn_into::<HtmlElement>().ok())
+                .and_then(|t| t.dataset().get("uid"))
+            else {
+                return;
+            };
+            let (Some(delivery_id), Some(timecards_data)) =
+                (get_selected_delivery_id(), &*timecards_data_ready)
+            else {
+                return;
+            };
+            info!("on_add_time: {uid}");
+            let mut timecards_data = timecards_data.clone();
+            if let Some(entry) = timecards_data.iter_mut().find(|v| v.0 == uid) {
+                // A scout without any spans is already showing the blank legacy span
+                if entry.2.is_empty() {
+                    entry.2.push(TimeCard {
+                        uid: uid.clone(),
+                        delivery_id,
+                        ..Default::default()
+                    });
+                }
+                entry.2.push(TimeCard::new_span(
+                    &uid,
+                    delivery_id,
+                    TimecardActivity::Delivery,
+                ));
+            }
+            timecards_data_ready.set(Some(timecards_data));
+        })
+    };
+
     html! {
         <div class="col-xs-1 d-flex justify-content-center">
             <div class="card" style="width: 100%;">
@@ -347,60 +486,132 @@ pub fn timecards_page() -> Html {
                                 }
                                 <option value="none" selected=true disabled=true hidden=true>{"Select delivery date"}</option>
                             </select>
-                            <button type="button" class="btn reports-view-setting-btn invisible ms-3"
-                                    onclick={on_export_timecards} data-bs-toggle="tooltip"
-                                    title="Download Timecards">
-                                <i class="bi bi-cloud-download" fill="currentColor"></i>
+                            <div class="dropdown d-inline ms-3">
+                                <button type="button" class="btn reports-view-setting-btn dropdown-toggle"
+                                        data-bs-toggle="dropdown" aria-expanded="false"
+                                        title="Download Timecards">
+                                    <i class="bi bi-cloud-download" fill="currentColor"></i>
+                                </button>
+                                <ul class="dropdown-menu">
+                                {
+                                    [("selected", "csv", "Selected Delivery (CSV)"),
+                                     ("selected", "xlsx", "Selected Delivery (XLSX)"),
+                                     ("all", "csv", "All Deliveries (CSV)"),
+                                     ("all", "xlsx", "All Deliveries (XLSX)")]
+                                        .iter()
+                                        .map(|(scope, format, label)| {
+                                            let mut item_class = "dropdown-item".to_string();
+                                            if "selected" == *scope && !*is_delivery_date_selected {
+                                                item_class = format!("{item_class} disabled");
+                                            }
+                                            html! {
+                                                <li>
+                                                    <a class={item_class} href="#" data-scope={*scope} data-format={*format}
+                                                       onclick={on_export_timecards.clone()}>
+                                                        {*label}
+                                                    </a>
+                                                </li>
+                                            }
+                                        })
+                                        .collect::<Html>()
+                                }
+                                </ul>
+                            </div>
+                            <button type="button" class="btn reports-view-setting-btn ms-1"
+                                    title="Import Timecards" onclick={on_import_timecards}>
+                                <i class="bi bi-cloud-upload" fill="currentColor"></i>
                             </button>
                         </span>
                     </div>
+                    <TimecardImportDlg onimported={on_timecards_imported}/>
                     if !(*is_delivery_date_selected) {
                         <div>{"Select a delivery date"}</div>
                     } else if let Some(timecards_data) = &*timecards_data_ready {
                         <ul class="list-group" id="timeSheet"> {
-                            timecards_data.iter().map(|(uid, user_name, tc)| {
-                                let time_in_id = format!("timeInId-{}", &uid);
-                                let time_out_id = format!("timeOutId-{}", &uid);
-                                let time_calc_id = format!("timeCalcId-{}", &uid);
+                            timecards_data.iter().map(|(uid, user_name, tcs)| {
+                                // Every scout gets at least the blank span to type into
+                                let blank_tc = [TimeCard { uid: uid.clone(), ..Default::default() }];
+                                let tcs = if tcs.is_empty() { &blank_tc[..] } else { &tcs[..] };
                                 html!{
                                     <li class="list-group-item">
-                                        <div class="row" data-uid={uid.clone()} data-uname={user_name.clone()}>
-                                            <div class="col">
-                                                {user_name.clone()}
-                                            </div>
-                                            <div class="col">
-                                                <div class="form-floating">
-                                                    <input data-clocklet="format: HH:mm;" oninput={on_time_change.clone()}
-                                                           class="form-control time-in" id={time_in_id.clone()}
-                                                           value={tc.as_ref().map_or("".to_string(), |v|server_time_to_display(&v.time_in))}
-                                                    />
-                                                    <label for={time_in_id}>{"Time In"}</label>
-                                                </div>
-                                            </div>
-                                            <div class="col">
-                                                <div class="form-floating">
-                                                    <input data-clocklet="format: HH:mm;" oninput={on_time_change.clone()}
-                                                           class="form-control time-out" id={time_out_id.clone()}
-                                                           value={tc.as_ref().map_or("".to_string(), |v|server_time_to_display(&v.time_out))}
-                                                    />
-                                                    <label for={time_out_id}>{"Time Out"}</label>
-                                                </div>
-                                            </div>
-                                            <div class="col">
-                                                <div class="form-floating">
-                                                    <div id={time_calc_id.clone()} class="form-control time-calc">
-                                                        {tc.as_ref().map_or("00:00".to_string(), |v|server_time_to_display(&v.time_total))}
-                                                    </div>
-                                                    <label for={time_calc_id}>{"Total Time"}</label>
-                                                </div>
-                                            </div>
-                                            <div class="col">
-                                                <button type="button" class="btn btn-primary invisible" onclick={on_save_entry.clone()}>
-                                                    <span class="spinner-border spinner-border-sm me-1 d-none" role="status" aria-hidden="true" />
-                                                    {"Save"}
-                                                </button>
-                                            </div>
+                                        <div class="d-flex align-items-center mb-1">
+                                            {user_name.clone()}
+                                            if tcs.iter().any(|v| v.is_missing_clock_out()) {
+                                                <span class="badge bg-danger ms-1" title="Clocked in with the time clock but never clocked out">
+                                                    {"Missed clock-out"}
+                                                </span>
+                                            }
+                                            <button type="button" class="btn btn-sm btn-outline-secondary ms-auto"
+                                                    data-uid={uid.clone()} onclick={on_add_time.clone()}>
+                                                <i class="bi bi-plus-square" fill="currentColor"></i>
+                                                {" Add Time"}
+                                            </button>
                                         </div>
+                                        {
+                                            tcs.iter().map(|tc| {
+                                                let id_suffix = format!("{}-{}", &uid, &tc.span_id);
+                                                let activity_id = format!("activityId-{id_suffix}");
+                                                let time_in_id = format!("timeInId-{id_suffix}");
+                                                let time_out_id = format!("timeOutId-{id_suffix}");
+                                                let time_calc_id = format!("timeCalcId-{id_suffix}");
+                                                html!{
+                                                    <div class="row mb-1" key={tc.span_id.clone()} data-uid={uid.clone()}
+                                                         data-uname={user_name.clone()} data-spanid={tc.span_id.clone()}>
+                                                        <div class="col">
+                                                            <div class="form-floating">
+                                                                <select class="form-select activity" id={activity_id.clone()}
+                                                                        onchange={on_activity_change.clone()}>
+                                                                {
+                                                                    TimecardActivity::all().iter().map(|activity| html! {
+                                                                        <option value={activity.as_api_str()} selected={*activity == tc.activity}>
+                                                                            {activity.to_string()}
+                                                                        </option>
+                                                                    }).collect::<Html>()
+                                                                }
+                                                                </select>
+                                                                <label for={activity_id}>{"Activity"}</label>
+                                                            </div>
+                                                        </div>
+                                                        <div class="col">
+                                                            <div class="form-floating">
+                                                                <input data-clocklet="format: HH:mm;" oninput={on_time_change.clone()}
+                                                                       class="form-control time-in" id={time_in_id.clone()}
+                                                                       value={server_time_to_display(&tc.time_in)}
+                                                                />
+                                                                <label for={time_in_id}>{"Time In"}</label>
+                                                            </div>
+                                                        </div>
+                                                        <div class="col">
+                                                            <div class="form-floating">
+                                                                <input data-clocklet="format: HH:mm;" oninput={on_time_change.clone()}
+                                                                       class="form-control time-out" id={time_out_id.clone()}
+                                                                       value={server_time_to_display(&tc.time_out)}
+                                                                />
+                                                                <label for={time_out_id}>{"Time Out"}</label>
+                                                            </div>
+                                                        </div>
+                                                        <div class="col">
+                                                            <div class="form-floating">
+                                                                <div id={time_calc_id.clone()} class="form-control time-calc">
+                                                                    if tc.time_total.is_empty() {
+                                                                        {"00:00"}
+                                                                    } else {
+                                                                        {server_time_to_display(&tc.time_total)}
+                                                                    }
+                                                                </div>
+                                                                <label for={time_calc_id}>{"Total Time"}</label>
+                                                            </div>
+                                                        </div>
+                                                        <div class="col">
+                                                            <button type="button" class="btn btn-primary save-btn invisible" onclick={on_save_entry.clone()}>
+                                                                <span class="spinner-border spinner-border-sm me-1 d-none" role="status" aria-hidden="true" />
+                                                                {"Save"}
+                                                            </button>
+                                                        </div>
+                                                    </div>
+                                                }
+                                            }).collect::<Html>()
+                                        }
                                     </li>
                                 }
                             }).collect::<Html>()
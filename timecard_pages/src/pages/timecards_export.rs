@@ -0,0 +1,223 @@
+use super::timecards::server_time_to_display;
+use chrono::prelude::*;
+use data_model::*;
+use gloo::file::File;
+use rust_xlsxwriter::{Format, Workbook};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlAnchorElement, Url};
+
+static EXPORT_HEADERS: [&str; 8] = [
+    "Delivery Date",
+    "Scout",
+    "Group",
+    "Activity",
+    "Time In",
+    "Time Out",
+    "Total",
+    "Hours",
+];
+
+/////////////////////////////////////////////////
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum TimecardExportFormat {
+    Csv,
+    Xlsx,
+}
+
+/////////////////////////////////////////////////
+#[derive(Default, Clone, PartialEq, Debug)]
+struct ExportRow {
+    delivery_date: String,
+    scout: String,
+    group: String,
+    activity: String,
+    time_in: String,
+    time_out: String,
+    total: Duration,
+    is_subtotal: bool,
+}
+
+impl ExportRow {
+    fn new_subtotal(delivery_date: &str, scout: &str, total: Duration) -> Self {
+        ExportRow {
+            delivery_date: delivery_date.to_string(),
+            scout: scout.to_string(),
+            total,
+            is_subtotal: true,
+            ..Default::default()
+        }
+    }
+
+    fn get_total_str(&self) -> String {
+        let mins = self.total.as_secs() / 60;
+        format!("{:02}:{:02}", mins / 60, mins % 60)
+    }
+
+    fn get_hours(&self) -> f64 {
+        ((self.total.as_secs() / 60) as f64 / 60.0 * 100.0).round() / 100.0
+    }
+
+    fn to_record(&self) -> [String; 8] {
+        [
+            self.delivery_date.clone(),
+            self.scout.clone(),
+            self.group.clone(),
+            self.activity.clone(),
+            self.time_in.clone(),
+            self.time_out.clone(),
+            self.get_total_str(),
+            self.get_hours().to_string(),
+        ]
+    }
+}
+
+/////////////////////////////////////////////////
+/// Every timecard is included, not just the ones shown on the timesheet, so the totals
+/// add up to the delivery time closeout uses
+fn gen_export_rows(timecards: Vec<TimeCard>) -> Vec<ExportRow> {
+    let users = get_users();
+    let get_user = |uid: &str| {
+        users
+            .get(uid)
+            .map_or((uid.to_string(), "".to_string()), |v| {
+                (v.name.clone(), v.group.clone())
+            })
+    };
+
+    let mut delivery_map: BTreeMap<u32, Vec<ExportRow>> = BTreeMap::new();
+    // Keyed by name then id so the subtotals sort by name without merging namesakes
+    let mut scout_totals: BTreeMap<(String, String), (String, Duration)> = BTreeMap::new();
+    for tc in timecards {
+        let total = tc.get_time_total();
+        if total.is_zero() {
+            continue;
+        }
+        let (scout, group) = get_user(&tc.uid);
+        scout_totals
+            .entry((scout.clone(), tc.uid.clone()))
+            .or_insert_with(|| (group.clone(), Duration::ZERO))
+            .1 += total;
+        delivery_map
+            .entry(tc.delivery_id)
+            .or_default()
+            .push(ExportRow {
+                delivery_date: get_delivery_date(&tc.delivery_id),
+                scout,
+                group,
+                activity: tc.activity.to_string(),
+                time_in: server_time_to_display(&tc.time_in),
+                time_out: server_time_to_display(&tc.time_out),
+                total,
+                is_subtotal: false,
+            });
+    }
+
+    let mut rows = Vec::new();
+    let mut grand_total = Duration::ZERO;
+    for (delivery_id, mut delivery_rows) in delivery_map {
+        delivery_rows.sort_by(|a, b| (&a.scout, &a.time_in).cmp(&(&b.scout, &b.time_in)));
+        let delivery_total: Duration = delivery_rows.iter().map(|v| v.total).sum();
+        grand_total += delivery_total;
+        rows.append(&mut delivery_rows);
+        rows.push(ExportRow::new_subtotal(
+            &format!("{} Subtotal", get_delivery_date(&delivery_id)),
+            "",
+            delivery_total,
+        ));
+    }
+
+    rows.extend(
+        scout_totals
+            .into_iter()
+            .map(|((scout, _), (group, total))| ExportRow {
+                group,
+                ..ExportRow::new_subtotal("Scout Subtotal", &scout, total)
+            }),
+    );
+    rows.push(ExportRow::new_subtotal("Total", "", grand_total));
+    rows
+}
+
+/////////////////////////////////////////////////
+fn gen_csv(rows: &[ExportRow]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record(EXPORT_HEADERS)?;
+    for row in rows {
+        wtr.write_record(row.to_record())?;
+    }
+    Ok(wtr.into_inner()?)
+}
+
+/////////////////////////////////////////////////
+fn gen_xlsx(rows: &[ExportRow]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut workbook = Workbook::new();
+    let bold = Format::new().set_bold();
+    let worksheet = workbook.add_worksheet().set_name("Timecards")?;
+
+    for (col, header) in EXPORT_HEADERS.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, *header, &bold)?;
+    }
+    for (idx, row) in rows.iter().enumerate() {
+        let row_num = idx as u32 + 1;
+        let record = row.to_record();
+        for (col, value) in record.iter().take(7).enumerate() {
+            if row.is_subtotal {
+                worksheet.write_string_with_format(row_num, col as u16, value, &bold)?;
+            } else {
+                worksheet.write_string(row_num, col as u16, value)?;
+            }
+        }
+        if row.is_subtotal {
+            worksheet.write_number_with_format(row_num, 7, row.get_hours(), &bold)?;
+        } else {
+            worksheet.write_number(row_num, 7, row.get_hours())?;
+        }
+    }
+    worksheet.autofit();
+
+    Ok(workbook.save_to_buffer()?)
+}
+
+/////////////////////////////////////////////////
+fn download_file(file_name: &str, data: &[u8], mime_type: &str) {
+    let f = File::new_with_options(file_name, data, Some(mime_type), Some(Utc::now().into()));
+    let link = gloo::utils::document()
+        .create_element("a")
+        .ok()
+        .and_then(|t| t.dyn_into::<HtmlAnchorElement>().ok())
+        .unwrap();
+    let url = Url::create_object_url_with_blob(f.as_ref()).unwrap();
+
+    link.set_target("_blank");
+    link.set_href(url.as_str());
+    link.set_download(f.name().as_str());
+    link.click();
+}
+
+/////////////////////////////////////////////////
+/// Exports the timecards for a delivery or for every delivery when there isn't one
+pub(crate) async fn export_timecards(
+    delivery_id: Option<u32>,
+    format: TimecardExportFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let rows = gen_export_rows(get_timecards(delivery_id).await?);
+    let file_name = delivery_id.map_or("Timecards".to_string(), |v| {
+        format!("Timecards_{}", get_delivery_date(&v).replace('/', "-"))
+    });
+
+    match format {
+        TimecardExportFormat::Csv => download_file(
+            &format!("{file_name}.csv"),
+            &gen_csv(&rows)?,
+            "text/plain;charset=utf-8",
+        ),
+        TimecardExportFormat::Xlsx => download_file(
+            &format!("{file_name}.xlsx"),
+            &gen_xlsx(&rows)?,
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        ),
+    }
+    Ok(())
+}
@@ -0,0 +1,583 @@
+use super::timecards::{get_selected_delivery_id, server_time_to_display};
+use chrono::prelude::*;
+use data_model::*;
+use gloo::file::File;
+use js::bootstrap;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
+use tracing::info;
+use wasm_bindgen::JsCast;
+use web_sys::{Event, FileList, HtmlInputElement, MouseEvent};
+use yew::prelude::*;
+
+/////////////////////////////////////////////////
+#[derive(Debug, Deserialize, PartialEq, Default)]
+struct TimecardFileRec {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    first_name: Option<String>,
+    #[serde(default)]
+    last_name: Option<String>,
+    #[serde(default)]
+    uid: Option<String>,
+    #[serde(default)]
+    delivery_date: Option<String>,
+    #[serde(default)]
+    time_in: Option<String>,
+    #[serde(default)]
+    time_out: Option<String>,
+    #[serde(default)]
+    activity: Option<String>,
+}
+
+impl TimecardFileRec {
+    fn get_name(&self) -> String {
+        match (self.first_name.as_deref(), self.last_name.as_deref()) {
+            (Some(first_name), Some(last_name)) => {
+                format!("{} {}", first_name.trim(), last_name.trim())
+            }
+            _ => self.name.as_deref().unwrap_or("").trim().to_string(),
+        }
+    }
+
+    fn is_blank(&self) -> bool {
+        [
+            &self.name,
+            &self.first_name,
+            &self.last_name,
+            &self.uid,
+            &self.delivery_date,
+            &self.time_in,
+            &self.time_out,
+            &self.activity,
+        ]
+        .iter()
+        .all(|v| v.as_deref().is_none_or(|v| v.trim().is_empty()))
+    }
+}
+
+/////////////////////////////////////////////////
+#[derive(Clone, PartialEq, Debug)]
+struct ImportRow {
+    row_num: usize,
+    name: String,
+    uid: Option<String>,
+    delivery_id: Option<u32>,
+    activity: TimecardActivity,
+    time_in: Option<Duration>,
+    time_out: Option<Duration>,
+    error: Option<String>,
+}
+
+impl ImportRow {
+    fn to_timecard(&self) -> Option<TimeCard> {
+        if self.error.is_some() {
+            return None;
+        }
+        let time_in = self.time_in?;
+        let time_out = self.time_out?;
+        Some(TimeCard {
+            uid: self.uid.clone()?,
+            delivery_id: self.delivery_id?,
+            activity: self.activity,
+            time_in: duration_to_server_time(&time_in),
+            time_out: duration_to_server_time(&time_out),
+            time_total: duration_to_server_time(&time_out.checked_sub(time_in)?),
+            ..Default::default()
+        })
+    }
+}
+
+/////////////////////////////////////////////////
+fn duration_to_server_time(dur: &Duration) -> String {
+    let mins = dur.as_secs() / 60;
+    format!("{:02}:{:02}:00", mins / 60, mins % 60)
+}
+
+/////////////////////////////////////////////////
+fn duration_to_display(dur: &Option<Duration>) -> String {
+    dur.as_ref().map_or("".to_string(), |v| {
+        server_time_to_display(&duration_to_server_time(v))
+    })
+}
+
+/////////////////////////////////////////////////
+/// Handles typed in times (08:30, 8:30 AM, 08:30:00) and spreadsheet time cells which
+/// show up as a fraction of a day (xlsx) or an ISO duration like PT08H30M00S (ods)
+fn parse_import_time(time_str: &str) -> Option<Duration> {
+    let time_str = time_str.trim();
+    if time_str.is_empty() {
+        return None;
+    }
+
+    if let Ok(day_fraction) = time_str.parse::<f64>() {
+        let mins = (day_fraction.fract() * 24.0 * 60.0).round() as u64;
+        return Some(Duration::from_secs(mins * 60));
+    }
+
+    if let Some(iso_str) = time_str.strip_prefix("PT") {
+        let mut mins = 0;
+        let mut num_str = String::new();
+        for c in iso_str.chars() {
+            match c {
+                'H' => mins += num_str.parse::<u64>().ok()? * 60,
+                'M' => mins += num_str.parse::<u64>().ok()?,
+                'S' => {}
+                _ => {
+                    num_str.push(c);
+                    continue;
+                }
+            }
+            num_str.clear();
+        }
+        return Some(Duration::from_secs(mins * 60));
+    }
+
+    let upper_time_str = time_str.to_ascii_uppercase();
+    ["%H:%M", "%H:%M:%S", "%I:%M %p", "%I:%M%p", "%I:%M:%S %p"]
+        .iter()
+        .find_map(|fmt| NaiveTime::parse_from_str(&upper_time_str, fmt).ok())
+        .map(|v| Duration::from_secs(u64::from(v.num_seconds_from_midnight()) / 60 * 60))
+}
+
+/////////////////////////////////////////////////
+/// Spreadsheet date cells come in as the number of days since 12/30/1899 (xlsx) or an
+/// ISO date (ods)
+fn parse_import_date(date_str: &str) -> Option<NaiveDate> {
+    let date_str = date_str.trim();
+    if let Ok(serial) = date_str.parse::<f64>() {
+        return NaiveDate::from_ymd_opt(1899, 12, 30)?
+            .checked_add_days(chrono::Days::new(serial.trunc() as u64));
+    }
+
+    let date_str = date_str.split('T').next().unwrap_or(date_str);
+    ["%Y-%m-%d", "%m/%d/%Y", "%m/%d/%y"]
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(date_str, fmt).ok())
+}
+
+/////////////////////////////////////////////////
+fn normalize_name(name: &str) -> String {
+    // Sign in sheets are often written last name first
+    let name = match name.split_once(',') {
+        Some((last_name, first_name)) => format!("{first_name} {last_name}"),
+        None => name.to_string(),
+    };
+    name.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/////////////////////////////////////////////////
+fn gen_import_rows(
+    records: Vec<TimecardFileRec>,
+    default_delivery_id: Option<u32>,
+) -> Vec<ImportRow> {
+    let users = get_users();
+    let mut name_map: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (uid, user_info) in users.iter() {
+        name_map
+            .entry(normalize_name(&user_info.name))
+            .or_default()
+            .push(uid.clone());
+    }
+    let delivery_map: BTreeMap<NaiveDate, u32> = get_deliveries()
+        .iter()
+        .filter_map(|(id, v)| parse_import_date(&v.get_delivery_date_str()).map(|d| (d, *id)))
+        .collect();
+
+    // Spans already read for each user and delivery to catch overlapping rows
+    let mut seen_spans: HashMap<(String, u32), Vec<(Duration, Duration)>> = HashMap::new();
+    records
+        .into_iter()
+        .enumerate()
+        .filter(|(_, rec)| !rec.is_blank())
+        .map(|(idx, rec)| {
+            let mut errors: Vec<String> = Vec::new();
+            let name = rec.get_name();
+
+            let uid = match rec.uid.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+                Some(uid) if users.contains_key(uid) => Some(uid.to_string()),
+                Some(uid) => {
+                    errors.push(format!("Unknown user id: {uid}"));
+                    None
+                }
+                None => match name_map.get(&normalize_name(&name)).map(Vec::as_slice) {
+                    Some([uid]) => Some(uid.clone()),
+                    Some(_) => {
+                        errors
+                            .push("More than one user has this name, add a uid column".to_string());
+                        None
+                    }
+                    None if name.is_empty() => {
+                        errors.push("Missing name".to_string());
+                        None
+                    }
+                    None => {
+                        errors.push("No user found with this name".to_string());
+                        None
+                    }
+                },
+            };
+
+            let delivery_id = match rec
+                .delivery_date
+                .as_deref()
+                .filter(|v| !v.trim().is_empty())
+            {
+                Some(date_str) => {
+                    let delivery_id =
+                        parse_import_date(date_str).and_then(|v| delivery_map.get(&v).copied());
+                    if delivery_id.is_none() {
+                        errors.push(format!("No delivery on: {date_str}"));
+                    }
+                    delivery_id
+                }
+                None => {
+                    if default_delivery_id.is_none() {
+                        errors.push(
+                            "Missing delivery date and no delivery date is selected".to_string(),
+                        );
+                    }
+                    default_delivery_id
+                }
+            };
+
+            let activity = match rec.activity.as_deref().filter(|v| !v.trim().is_empty()) {
+                Some(activity_str) => {
+                    activity_str
+                        .parse::<TimecardActivity>()
+                        .unwrap_or_else(|_| {
+                            errors.push(format!("Invalid activity: {activity_str}"));
+                            TimecardActivity::default()
+                        })
+                }
+                None => TimecardActivity::default(),
+            };
+
+            let mut parse_time = |time_str: &Option<String>, label: &str| {
+                let time = time_str.as_deref().and_then(parse_import_time);
+                if time.is_none() {
+                    errors.push(format!(
+                        "Invalid {label}: {}",
+                        time_str.as_deref().unwrap_or("")
+                    ));
+                }
+                time
+            };
+            let time_in = parse_time(&rec.time_in, "time in");
+            let time_out = parse_time(&rec.time_out, "time out");
+
+            if let (Some(time_in), Some(time_out)) = (time_in, time_out)
+                && let Some(err) = get_timecard_span_error(&time_in, &time_out)
+            {
+                errors.push(err.to_string());
+            }
+
+            if let (Some(uid), Some(delivery_id)) = (uid.as_ref(), delivery_id) {
+                let spans = seen_spans.entry((uid.clone(), delivery_id)).or_default();
+                if let (Some(time_in), Some(time_out)) = (time_in, time_out)
+                    && spans
+                        .iter()
+                        .any(|(other_in, other_out)| time_in < *other_out && *other_in < time_out)
+                {
+                    errors.push("Overlaps another row for this user".to_string());
+                }
+                if let (Some(time_in), Some(time_out)) = (time_in, time_out) {
+                    spans.push((time_in, time_out));
+                }
+            }
+
+            ImportRow {
+                // Account for the header row so this matches the spreadsheet row
+                row_num: idx + 2,
+                name: if name.is_empty() {
+                    uid.as_deref()
+                        .and_then(get_username_from_id)
+                        .unwrap_or_default()
+                } else {
+                    name
+                },
+                uid,
+                delivery_id,
+                activity,
+                time_in,
+                time_out,
+                error: if errors.is_empty() {
+                    None
+                } else {
+                    Some(errors.join("; "))
+                },
+            }
+        })
+        .collect()
+}
+
+/////////////////////////////////////////////////
+/// Importing replaces what a user already has for a delivery. Their existing span ids are
+/// reused in time order and any spans left over are cleared so importing the same file
+/// again doesn't double count.
+async fn gen_replacement_timecards(
+    timecards: Vec<TimeCard>,
+) -> Result<Vec<TimeCard>, Box<dyn std::error::Error>> {
+    let mut existing_spans: HashMap<(String, u32), Vec<TimeCard>> = HashMap::new();
+    for tc in get_timecards(None).await? {
+        existing_spans
+            .entry((tc.uid.clone(), tc.delivery_id))
+            .or_default()
+            .push(tc);
+    }
+    existing_spans
+        .values_mut()
+        .for_each(|v| v.sort_by_key(|v| time_val_str_to_duration(&v.time_in)));
+
+    let mut imported_keys = HashSet::new();
+    let mut replacements = Vec::new();
+    for mut tc in timecards {
+        let key = (tc.uid.clone(), tc.delivery_id);
+        let spans = existing_spans.entry(key.clone()).or_default();
+        tc.span_id = if spans.is_empty() {
+            TimeCard::new_span(&tc.uid, tc.delivery_id, tc.activity).span_id
+        } else {
+            spans.remove(0).span_id
+        };
+        imported_keys.insert(key);
+        replacements.push(tc);
+    }
+
+    for ((uid, delivery_id), spans) in existing_spans {
+        if !imported_keys.contains(&(uid.clone(), delivery_id)) {
+            continue;
+        }
+        replacements.extend(spans.into_iter().map(|v| TimeCard {
+            uid: uid.clone(),
+            delivery_id,
+            span_id: v.span_id,
+            activity: v.activity,
+            ..Default::default()
+        }));
+    }
+    Ok(replacements)
+}
+
+/////////////////////////////////////////////////
+fn reset_file_input() {
+    if let Some(input) = gloo::utils::document()
+        .get_element_by_id("timecardImportFile")
+        .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+    {
+        input.set_value("");
+    }
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[derive(Properties, PartialEq, Clone, Debug)]
+pub(crate) struct TimecardImportDlgProps {
+    pub(crate) onimported: Callback<()>,
+}
+
+#[component(TimecardImportDlg)]
+pub(crate) fn timecard_import_dlg(props: &TimecardImportDlgProps) -> Html {
+    let rows = use_state_eq(Vec::<ImportRow>::new);
+    let is_working = use_state_eq(|| false);
+    let is_saving = use_state_eq(|| false);
+
+    let on_cancel = {
+        let rows = rows.clone();
+        move |_evt: MouseEvent| {
+            rows.set(Vec::new());
+            reset_file_input();
+        }
+    };
+
+    let on_file_input_change = {
+        let is_working = is_working.clone();
+        let rows = rows.clone();
+        move |evt: Event| {
+            rows.set(Vec::new());
+            let input: HtmlInputElement = evt.target_unchecked_into();
+            let files: Option<FileList> = input.files();
+            let Some(file) = files
+                .and_then(|files| js_sys::try_iter(&files).ok().flatten())
+                .and_then(|mut files| files.next())
+                .and_then(|v| v.ok())
+                .map(|v| File::from(web_sys::File::from(v)))
+            else {
+                info!("No files so returning");
+                return;
+            };
+
+            is_working.set(true);
+            let is_working = is_working.clone();
+            let rows = rows.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let file_name = file.name();
+                let file_type = file.raw_mime_type();
+                info!("Loading: {file_name} type: {file_type}");
+                let records = match gloo::file::futures::read_as_bytes(&file).await {
+                    Ok(data) => read_uploaded_file_records(&file_name, &file_type, data),
+                    Err(err) => Err(err.into()),
+                };
+                match records {
+                    Ok(records) => rows.set(gen_import_rows(records, get_selected_delivery_id())),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!(
+                            "Error in timecards file make sure proper headers are in place:\n{err:#?}"
+                        ));
+                        input.set_value("");
+                    }
+                }
+                is_working.set(false);
+            });
+        }
+    };
+
+    let timecards = rows
+        .iter()
+        .filter_map(ImportRow::to_timecard)
+        .collect::<Vec<TimeCard>>();
+    let num_errors = rows.iter().filter(|v| v.error.is_some()).count();
+
+    let on_submit = {
+        let onimported = props.onimported.clone();
+        let rows = rows.clone();
+        let is_saving = is_saving.clone();
+        let timecards = timecards.clone();
+        move |_evt: MouseEvent| {
+            let onimported = onimported.clone();
+            let rows = rows.clone();
+            let is_saving = is_saving.clone();
+            let timecards = timecards.clone();
+            is_saving.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Importing {} timecards", timecards.len());
+                let result = match gen_replacement_timecards(timecards).await {
+                    Ok(timecards) => save_timecards_data(timecards).await,
+                    Err(err) => Err(err),
+                };
+                match result {
+                    Ok(_) => {
+                        rows.set(Vec::new());
+                        reset_file_input();
+                        bootstrap::modal_op("timecardImportDlg", "hide");
+                        onimported.emit(());
+                    }
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to import timecards: {err:#?}"));
+                    }
+                }
+                is_saving.set(false);
+            });
+        }
+    };
+
+    html! {
+        <div class="modal fade" id="timecardImportDlg"
+             tabIndex="-1" role="dialog" aria-labelledby="timecardImportDlgTitle" aria-hidden="true">
+            <div class="modal-dialog modal-dialog-centered modal-xl" role="document">
+                <div class="modal-content">
+                    <div class="modal-header">
+                        <h5 class="modal-title" id="timecardImportDlgTitle">
+                           {"Import Timecards"}
+                        </h5>
+                    </div>
+                    <div class="modal-body">
+                        <div class="container-sm">
+                            <div class="row">
+                                {"Uploads a .csv/.xlsx/.ods formatted file."}
+                                <br/>
+                                <br/>
+                                {"Files should have a header row with:"}
+                                <br/>
+                                {"name,delivery_date,time_in,time_out,activity"}
+                                <br/>
+                                <br/>
+                                {"CSV file example formatting:"}
+                                <br/>
+                                {"James Kirk,2025-04-12,07:30,12:15,Delivery"}
+                                <br/>
+                                <br/>
+                                {"Names are matched to users. A uid column or first_name/last_name columns can be used instead."}
+                                {" When delivery_date is left out the selected delivery date is used."}
+                                {" The activity column is optional and can be Delivery, Truck Loading or Spreading."}
+                                {" A user can have more than one row for a delivery as long as the times don't overlap."}
+                                {" A user's existing timecards for a delivery are replaced by their rows in the file."}
+                            </div>
+                            <div class="row mt-2">
+                                <input
+                                    id="timecardImportFile"
+                                    type="file"
+                                    accept=".csv,.ods,.xlsx"
+                                    multiple={false}
+                                    onchange={on_file_input_change}
+                                />
+                            </div>
+                            <div class="row mt-2">
+                            if *is_working {
+                                <span class="spinner-border spinner-border-sm me-1" role="status"
+                                aria-hidden="true" style="display: block;" />
+                            } else if !rows.is_empty() {
+                                <div>
+                                    {format!("{} timecards ready to import", timecards.len())}
+                                    if 0 != num_errors {
+                                        <span class="text-danger">
+                                            {format!(", {num_errors} rows with errors will be skipped")}
+                                        </span>
+                                    }
+                                </div>
+                                <table class="table table-sm">
+                                    <thead>
+                                        <tr>
+                                            <th>{"Row"}</th>
+                                            <th>{"Name"}</th>
+                                            <th>{"Delivery Date"}</th>
+                                            <th>{"Activity"}</th>
+                                            <th>{"Time In"}</th>
+                                            <th>{"Time Out"}</th>
+                                            <th>{"Total"}</th>
+                                            <th>{"Error"}</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                    {
+                                        rows.iter().map(|v| {
+                                            let tc = v.to_timecard();
+                                            html! {
+                                                <tr class={if v.error.is_some() { "table-danger" } else { "" }}>
+                                                    <td>{v.row_num}</td>
+                                                    <td>{&v.name}</td>
+                                                    <td>{v.delivery_id.map_or("".to_string(), |v| get_delivery_date(&v))}</td>
+                                                    <td>{v.activity.to_string()}</td>
+                                                    <td>{duration_to_display(&v.time_in)}</td>
+                                                    <td>{duration_to_display(&v.time_out)}</td>
+                                                    <td>{tc.map_or("".to_string(), |v| server_time_to_display(&v.time_total))}</td>
+                                                    <td>{v.error.clone().unwrap_or_default()}</td>
+                                                </tr>
+                                            }
+                                        }).collect::<Html>()
+                                    }
+                                    </tbody>
+                                </table>
+                            }
+                            </div>
+                        </div>
+                    </div>
+                    <div class="modal-footer">
+                        <button type="button" class="btn btn-secondary" data-bs-dismiss="modal" onclick={on_cancel}>{"Cancel"}</button>
+                        <button type="button" class="btn btn-primary float-end" onclick={on_submit}
+                            disabled={timecards.is_empty() || *is_saving}>
+                            if *is_saving {
+                                <span class="spinner-border spinner-border-sm me-1" role="status" aria-hidden="true"/>
+                            }
+                            {"Import"}
+                        </button>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
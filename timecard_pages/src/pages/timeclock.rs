@@ -0,0 +1,321 @@
+use super::timecards::{TimecardsDataType, server_time_to_display};
+use data_model::*;
+use js::geolocate::get_current_position;
+use tracing::info;
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement, InputEvent, MouseEvent};
+use yew::prelude::*;
+
+/////////////////////////////////////////////////
+// Start on today's delivery if there is one otherwise the next one coming up
+fn get_default_delivery_id() -> u32 {
+    let deliveries = get_deliveries();
+    deliveries
+        .iter()
+        .find(|(_, v)| !v.has_delivery_date_passed())
+        .or_else(|| deliveries.iter().last())
+        .map_or(0, |(id, _)| *id)
+}
+
+/////////////////////////////////////////////////
+async fn get_clock_location(is_capturing_location: bool) -> Option<GeoPoint> {
+    if !is_capturing_location {
+        return None;
+    }
+    get_current_position().await.map(|v| GeoPoint {
+        lat: v.coords.latitude,
+        lng: v.coords.longitude,
+    })
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[derive(Properties, PartialEq, Clone, Debug)]
+struct TimeClockRowProps {
+    uid: String,
+    name: String,
+    timecards: Vec<TimeCard>,
+    deliveryid: u32,
+    activity: TimecardActivity,
+    capturelocation: bool,
+    isdeliverytoday: bool,
+    onchange: Callback<TimeCard>,
+}
+
+#[component(TimeClockRow)]
+fn time_clock_row(props: &TimeClockRowProps) -> Html {
+    let is_saving = use_state_eq(|| false);
+
+    let on_clock = {
+        let is_saving = is_saving.clone();
+        let props = props.clone();
+        Callback::from(move |_evt: MouseEvent| {
+            let is_saving = is_saving.clone();
+            let props = props.clone();
+            is_saving.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                let location = get_clock_location(props.capturelocation).await;
+                // Clocking in again after a break starts a new span
+                let tc = match props.timecards.into_iter().find(|v| v.is_clocked_in()) {
+                    Some(mut tc) => tc.clock_out(location).map(|_| tc),
+                    None => TimeCard::new_clock_in(
+                        &props.uid,
+                        props.deliveryid,
+                        props.activity,
+                        location,
+                    ),
+                };
+                let result = match tc {
+                    Ok(tc) => save_timecards_data(vec![tc.clone()]).await.map(|_| tc),
+                    Err(err) => Err(err),
+                };
+                match result {
+                    Ok(tc) => {
+                        info!(
+                            "Time clock saved: {} in: {} out: {}",
+                            &tc.uid, &tc.time_in, &tc.time_out
+                        );
+                        props.onchange.emit(tc);
+                    }
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!("Failed to save time clock entry: {err:#?}"));
+                    }
+                }
+                is_saving.set(false);
+            });
+        })
+    };
+
+    let tcs = props
+        .timecards
+        .iter()
+        .filter(|v| !v.time_in.is_empty())
+        .collect::<Vec<_>>();
+    let clocked_in_tc = tcs.iter().find(|v| v.is_clocked_in());
+    let is_missing_clock_out = tcs.iter().any(|v| v.is_missing_clock_out());
+    html! {
+        <li class="list-group-item d-flex justify-content-between align-items-center">
+            <div>
+                <div>{&props.name}</div>
+                {
+                    tcs.iter().map(|tc| html! {
+                        <div><small class="text-muted">
+                            if tc.is_clocked_in() {
+                                {format!("{} In: {}", tc.activity, server_time_to_display(&tc.time_in))}
+                            } else {
+                                {format!("{} {} - {} ({})",
+                                    tc.activity,
+                                    server_time_to_display(&tc.time_in),
+                                    server_time_to_display(&tc.time_out),
+                                    server_time_to_display(&tc.time_total))}
+                            }
+                        </small></div>
+                    }).collect::<Html>()
+                }
+            </div>
+            {
+                match clocked_in_tc {
+                    Some(tc) => html! {
+                        <div class="d-flex align-items-center">
+                            if is_missing_clock_out {
+                                <span class="badge bg-danger me-2">{"Missed clock-out"}</span>
+                            }
+                            if tc.can_clock_out() {
+                                <button type="button" class="btn btn-lg btn-warning" onclick={on_clock}
+                                    disabled={*is_saving}>
+                                    if *is_saving {
+                                        <span class="spinner-border spinner-border-sm me-1" role="status" aria-hidden="true"/>
+                                    }
+                                    {"Clock Out"}
+                                </button>
+                            }
+                        </div>
+                    },
+                    None if !props.isdeliverytoday && !tcs.is_empty() => html! {
+                        <span class="badge bg-secondary">{"Done"}</span>
+                    },
+                    None => html! {
+                        <button type="button" class="btn btn-lg btn-success" onclick={on_clock}
+                            disabled={*is_saving || !props.isdeliverytoday}>
+                            if *is_saving {
+                                <span class="spinner-border spinner-border-sm me-1" role="status" aria-hidden="true"/>
+                            }
+                            {"Clock In"}
+                        </button>
+                    },
+                }
+            }
+        </li>
+    }
+}
+
+/////////////////////////////////////////////////
+/////////////////////////////////////////////////
+#[component(TimeClock)]
+pub fn time_clock_page() -> Html {
+    let timecards_data: UseStateHandle<Option<Vec<TimecardsDataType>>> = use_state_eq(|| None);
+    let delivery_id = use_state_eq(get_default_delivery_id);
+    let name_filter = use_state_eq(String::new);
+    let is_capturing_location = use_state_eq(|| true);
+    let activity = use_state_eq(TimecardActivity::default);
+
+    {
+        let timecards_data = timecards_data.clone();
+        use_effect_with(*delivery_id, move |delivery_id| {
+            let delivery_id = *delivery_id;
+            timecards_data.set(None);
+            wasm_bindgen_futures::spawn_local(async move {
+                info!("Downloading time clock data for: {delivery_id}");
+                match get_timecards_data(Some(delivery_id), None).await {
+                    Ok(resp) => timecards_data.set(Some(resp)),
+                    Err(err) => {
+                        gloo::dialogs::alert(&format!(
+                            "Failed to get retrieve timecard data: {err:#?}"
+                        ));
+                    }
+                }
+            });
+            || {}
+        });
+    }
+
+    let on_delivery_change = {
+        let delivery_id = delivery_id.clone();
+        Callback::from(move |evt: Event| {
+            let value = evt.target_unchecked_into::<HtmlSelectElement>().value();
+            delivery_id.set(value.parse::<u32>().unwrap_or(0));
+        })
+    };
+
+    let on_name_filter_change = {
+        let name_filter = name_filter.clone();
+        Callback::from(move |evt: InputEvent| {
+            let value = evt.target_unchecked_into::<HtmlInputElement>().value();
+            name_filter.set(value.trim().to_lowercase());
+        })
+    };
+
+    let on_capture_location_change = {
+        let is_capturing_location = is_capturing_location.clone();
+        Callback::from(move |evt: Event| {
+            is_capturing_location.set(evt.target_unchecked_into::<HtmlInputElement>().checked());
+        })
+    };
+
+    let on_activity_change = {
+        let activity = activity.clone();
+        Callback::from(move |evt: Event| {
+            let value = evt.target_unchecked_into::<HtmlSelectElement>().value();
+            activity.set(value.parse::<TimecardActivity>().unwrap_or_default());
+        })
+    };
+
+    let on_timecard_change = {
+        let timecards_data = timecards_data.clone();
+        Callback::from(move |tc: TimeCard| {
+            if let Some(data) = &*timecards_data {
+                let mut data = data.clone();
+                if let Some(entry) = data.iter_mut().find(|v| v.0 == tc.uid) {
+                    match entry.2.iter_mut().find(|v| v.span_id == tc.span_id) {
+                        Some(saved_tc) => *saved_tc = tc,
+                        None => entry.2.push(tc),
+                    }
+                }
+                timecards_data.set(Some(data));
+            }
+        })
+    };
+
+    let is_delivery_passed = get_deliveries()
+        .get(&*delivery_id)
+        .is_some_and(|v| v.has_delivery_date_passed());
+    let is_delivery_today = get_deliveries()
+        .get(&*delivery_id)
+        .is_some_and(|v| v.is_delivery_today());
+
+    html! {
+        <div class="col-xs-1 d-flex justify-content-center">
+            <div class="card" style="width: 100%;">
+                <div class="card-body">
+                    <h6 class="card-title">{"Time Clock"}</h6>
+                    <div class="row g-2 mb-2">
+                        <div class="form-floating col-md-4">
+                            <select class="form-select" id="timeClockDeliveryDate" onchange={on_delivery_change}>
+                            {
+                                get_deliveries().iter().map(|(id, v)| html! {
+                                    <option value={id.to_string()} selected={*id == *delivery_id}>
+                                        {v.get_delivery_date_str()}
+                                    </option>
+                                }).collect::<Html>()
+                            }
+                            </select>
+                            <label for="timeClockDeliveryDate">{"Delivery Date"}</label>
+                        </div>
+                        <div class="form-floating col-md-3">
+                            <select class="form-select" id="timeClockActivity" onchange={on_activity_change}>
+                            {
+                                TimecardActivity::all().iter().map(|v| html! {
+                                    <option value={v.as_api_str()} selected={*v == *activity}>
+                                        {v.to_string()}
+                                    </option>
+                                }).collect::<Html>()
+                            }
+                            </select>
+                            <label for="timeClockActivity">{"Clocking In For"}</label>
+                        </div>
+                        <div class="form-floating col-md-3">
+                            <input class="form-control" type="search" id="timeClockNameFilter"
+                                oninput={on_name_filter_change}/>
+                            <label for="timeClockNameFilter">{"Find Scout"}</label>
+                        </div>
+                        <div class="col-md-2 d-flex align-items-center">
+                            <div class="form-check form-switch">
+                                <input class="form-check-input" type="checkbox" id="timeClockCaptureLocation"
+                                    checked={*is_capturing_location} onchange={on_capture_location_change}/>
+                                <label class="form-check-label" for="timeClockCaptureLocation">
+                                    {"Record Location"}
+                                </label>
+                            </div>
+                        </div>
+                    </div>
+                    if is_delivery_passed {
+                        <div class="alert alert-warning">
+                            {"This delivery date has passed. Corrections are made on the Timecards page."}
+                        </div>
+                    } else if !is_delivery_today {
+                        <div class="alert alert-info">
+                            {"Clocking in opens on the day of this delivery."}
+                        </div>
+                    }
+                    if let Some(data) = &*timecards_data {
+                        <div class="mb-2">
+                            {format!("Clocked In: {} | Missed Clock-outs: {}",
+                                data.iter().filter(|v| v.2.iter().any(|v| v.is_clocked_in())).count(),
+                                data.iter().filter(|v| v.2.iter().any(|v| v.is_missing_clock_out())).count())}
+                        </div>
+                        <ul class="list-group">
+                        {
+                            data.iter()
+                                .filter(|(_, name, _)| {
+                                    name_filter.is_empty() || name.to_lowercase().contains(&*name_filter)
+                                })
+                                .map(|(uid, name, tcs)| html! {
+                                    <TimeClockRow key={uid.clone()} uid={uid.clone()} name={name.clone()}
+                                        timecards={tcs.clone()} deliveryid={*delivery_id} activity={*activity}
+                                        capturelocation={*is_capturing_location}
+                                        isdeliverytoday={is_delivery_today}
+                                        onchange={on_timecard_change.clone()}/>
+                                })
+                                .collect::<Html>()
+                        }
+                        </ul>
+                    } else {
+                        <div class="justify-content-center text-center">
+                            <h2>{"Loading Timecard data..."}</h2>
+                            <span class="loader"></span>
+                        </div>
+                    }
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -65,6 +65,9 @@ pub(crate) fn app_nav(props: &AppNavProps) -> Html {
                             <Link<AppRoutes> classes="dropdown-item" to={AppRoutes::Timecards} >
                                 {"Timecards"}
                             </Link<AppRoutes>>
+                            <Link<AppRoutes> classes="dropdown-item" to={AppRoutes::TimeClock} >
+                                {"Time Clock"}
+                            </Link<AppRoutes>>
                             <Link<AppRoutes> classes="dropdown-item" to={AppRoutes::FundraiserCloseout} >
                                 {"Closeout Fundraiser"}
                             </Link<AppRoutes>>
@@ -8,7 +8,7 @@ use yew_router::prelude::*;
 use data_model::{
     AppRoutes, NUM_TOP_SELLERS_TO_GET, are_sales_still_allowed, clear_local_storage,
     clear_session_storage, get_active_user, get_active_user_async, get_summary_report_data,
-    is_active_order, load_config, save_to_active_order,
+    is_active_order, load_config, load_cutoff_overrides, save_to_active_order,
 };
 use js::auth_utils::{is_authenticated, login, logout};
 
@@ -27,7 +27,7 @@ use order_pages::{
     pages::{OrderDonations, OrderForm, OrderProducts},
 };
 use report_pages::pages::Reports;
-use timecard_pages::Timecards;
+use timecard_pages::{TimeClock, Timecards};
 
 /////////////////////////////////////////////////
 /////////////////////////////////////////////////
@@ -84,6 +84,7 @@ fn App() -> Html {
                 AppRoutes::OrderDonations => html! {<OrderDonations/>},
                 AppRoutes::Reports => html! {<Reports/>},
                 AppRoutes::Timecards => html! {<Timecards/>},
+                AppRoutes::TimeClock => html! {<TimeClock/>},
                 AppRoutes::FundraiserCloseout => html! {<CloseoutFundraiser/>},
                 AppRoutes::FrConfigEditor => html! {<FrConfigEditor/>},
                 AppRoutes::NotFound => html! { <h1>{ "404" }</h1> },
@@ -121,6 +122,7 @@ fn App() -> Html {
                             Ok(user_info) => {
                                 // We are authenticated so get initial config stuff before we bring up ui
                                 load_config().await;
+                                load_cutoff_overrides().await;
                                 // Preload summary_report data TODO: this is goofy
                                 let _ = get_summary_report_data(
                                     &user_info.get_id(),